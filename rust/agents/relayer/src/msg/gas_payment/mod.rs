@@ -1,24 +1,29 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 use async_trait::async_trait;
+use derive_new::new;
 use eyre::Result;
 use hyperlane_base::db::HyperlaneRocksDB;
 use hyperlane_core::{
     FixedPointNumber, GasPaymentKey, HyperlaneMessage, InterchainGasExpenditure,
     InterchainGasPayment, TxCostEstimate, TxOutcome, U256,
 };
-use tracing::{debug, error, trace};
+use tokio::sync::RwLock;
+use tracing::{debug, error, trace, warn};
 
-use self::policies::{GasPaymentPolicyMinimum, GasPaymentPolicyNone};
+use self::policies::{GasPaymentPolicyExchangeRate, GasPaymentPolicyMinimum, GasPaymentPolicyNone};
 use crate::{
     msg::gas_payment::policies::GasPaymentPolicyOnChainFeeQuoting,
     settings::{
-        matching_list::MatchingList, GasPaymentEnforcementConf, GasPaymentEnforcementPolicy,
+        matching_list::MatchingList, reloader::ReloadableConf, GasPaymentEnforcementConf,
+        GasPaymentEnforcementPolicy,
     },
 };
 
 mod policies;
 
+pub(crate) use policies::ExchangeRates;
+
 #[async_trait]
 pub trait GasPaymentPolicy: Debug + Send + Sync {
     /// Returns Some(gas_limit) if the policy has approved the transaction or
@@ -30,47 +35,56 @@ pub trait GasPaymentPolicy: Debug + Send + Sync {
         current_expenditure: &InterchainGasExpenditure,
         tx_cost_estimate: &TxCostEstimate,
     ) -> Result<Option<U256>>;
-}
 
-#[derive(Debug)]
-pub struct GasPaymentEnforcer {
-    /// List of policies and a whitelist to decide if it should be used for a
-    /// given transaction. It is highly recommended to have the last policy
-    /// use a wild-card white list to ensure all messages fall into one
-    /// policy or another. If a message matches multiple policies'
-    /// whitelists, then whichever is first in the list will be used.
-    policies: Vec<(Box<dyn GasPaymentPolicy>, MatchingList)>,
-    db: HyperlaneRocksDB,
+    /// The exchange rates this policy values payments and costs with, if any
+    fn exchange_rates(&self) -> Option<&ExchangeRates> {
+        None
+    }
 }
 
-impl GasPaymentEnforcer {
-    /// Note that `policy_configs` should not be empty. In the settings,
-    /// a default of vec![GasPaymentEnforcementConf::default()] is used.
-    pub fn new(
-        policy_configs: impl IntoIterator<Item = GasPaymentEnforcementConf>,
-        db: HyperlaneRocksDB,
-    ) -> Self {
-        let policies = policy_configs
-            .into_iter()
-            .map(|cfg| {
-                let p: Box<dyn GasPaymentPolicy> = match cfg.policy {
-                    GasPaymentEnforcementPolicy::None => Box::new(GasPaymentPolicyNone),
-                    GasPaymentEnforcementPolicy::Minimum { payment } => {
-                        Box::new(GasPaymentPolicyMinimum::new(payment))
-                    }
-                    GasPaymentEnforcementPolicy::OnChainFeeQuoting {
-                        gas_fraction_numerator: n,
-                        gas_fraction_denominator: d,
-                    } => Box::new(GasPaymentPolicyOnChainFeeQuoting::new(n, d)),
-                };
-                (p, cfg.matching_list)
-            })
-            .collect();
-
-        Self { policies, db }
+/// List of policies and a whitelist to decide if it should be used for a
+/// given transaction. It is highly recommended to have the last policy
+/// use a wild-card white list to ensure all messages fall into one
+/// policy or another. If a message matches multiple policies'
+/// whitelists, then whichever is first in the list will be used.
+#[derive(Debug, Default)]
+pub struct GasPaymentPolicies(Vec<(Box<dyn GasPaymentPolicy>, MatchingList)>);
+
+impl GasPaymentPolicies {
+    pub fn new(policy_configs: impl IntoIterator<Item = GasPaymentEnforcementConf>) -> Self {
+        Self(
+            policy_configs
+                .into_iter()
+                .map(|cfg| {
+                    let p: Box<dyn GasPaymentPolicy> = match cfg.policy {
+                        GasPaymentEnforcementPolicy::None => Box::new(GasPaymentPolicyNone),
+                        GasPaymentEnforcementPolicy::Minimum { payment } => {
+                            Box::new(GasPaymentPolicyMinimum::new(payment))
+                        }
+                        GasPaymentEnforcementPolicy::OnChainFeeQuoting {
+                            gas_fraction_numerator: n,
+                            gas_fraction_denominator: d,
+                        } => Box::new(GasPaymentPolicyOnChainFeeQuoting::new(n, d)),
+                        GasPaymentEnforcementPolicy::ExchangeRate(conf) => {
+                            Box::new(GasPaymentPolicyExchangeRate::new(conf))
+                        }
+                    };
+                    (p, cfg.matching_list)
+                })
+                .collect(),
+        )
     }
 }
 
+#[derive(Debug, new)]
+pub struct GasPaymentEnforcer {
+    /// Holds the policies, which can be replaced while the relayer is running.
+    /// Note that the policies should not be empty. In the settings, a default
+    /// of vec![GasPaymentEnforcementConf::default()] is used.
+    conf: Arc<RwLock<ReloadableConf>>,
+    db: HyperlaneRocksDB,
+}
+
 impl GasPaymentEnforcer {
     /// Returns Some(gas_limit) if the enforcer has approved the transaction or
     /// None if the transaction is not approved.
@@ -89,7 +103,9 @@ impl GasPaymentEnforcer {
             .retrieve_gas_payment_by_gas_payment_key(gas_payment_key)?;
         let current_expenditure = self.db.retrieve_gas_expenditure_by_message_id(msg_id)?;
 
-        for (policy, whitelist) in &self.policies {
+        // Don't hold the lock while evaluating, policies may make requests
+        let policies = self.conf.read().await.gas_payment_policies.clone();
+        for (policy, whitelist) in policies.0.iter() {
             if !whitelist.msg_matches(message, true) {
                 trace!(
                     msg=%message,
@@ -125,38 +141,100 @@ impl GasPaymentEnforcer {
 
         error!(
             msg=%message,
-            policies=?self.policies,
+            ?policies,
             "No gas payment policy matched for message; consider adding a default policy to the end of the policies array which uses a wildcard whitelist."
         );
         Ok(None)
     }
 
+    /// Records the gas spent by a transaction attempting to deliver a message,
+    /// and returns the tokens it used.
     pub fn record_tx_outcome(&self, message: &HyperlaneMessage, outcome: TxOutcome) -> Result<()> {
         self.db.process_gas_expenditure(InterchainGasExpenditure {
             message_id: message.id(),
             gas_used: outcome.gas_used,
-            tokens_used: (FixedPointNumber::try_from(outcome.gas_used)? * outcome.gas_price)
-                .try_into()?,
+            tokens_used: Self::tokens_used(&outcome)?,
         })?;
         Ok(())
     }
+
+    /// The tokens a transaction used, in the smallest unit of the native token
+    /// of the chain it was sent to.
+    pub fn tokens_used(outcome: &TxOutcome) -> Result<U256> {
+        Ok(
+            (FixedPointNumber::try_from(outcome.gas_used)? * outcome.gas_price.clone())
+                .try_into()?,
+        )
+    }
+
+    /// The value of `amount` of the smallest unit of `domain`'s native token in
+    /// the common currency of the policy that applies to `message`. `None` if
+    /// that policy doesn't use exchange rates or the amount can't be converted.
+    pub async fn value_of(
+        &self,
+        message: &HyperlaneMessage,
+        domain: u32,
+        amount: U256,
+    ) -> Option<f64> {
+        let policies = self.conf.read().await.gas_payment_policies.clone();
+        let (policy, _) = policies
+            .0
+            .iter()
+            .find(|(_, whitelist)| whitelist.msg_matches(message, true))?;
+        let exchange_rates = policy.exchange_rates()?;
+        let value: Result<f64> = async {
+            let value = exchange_rates.value_of(domain, amount.try_into()?).await?;
+            Ok(value.to_string().parse()?)
+        }
+        .await;
+        value
+            .map_err(|err| warn!(error = ?err, domain, "Failed to convert amount to the common currency"))
+            .ok()
+    }
+
+    /// The sum of the gas payments made for a message so far.
+    pub fn total_payment(&self, message: &HyperlaneMessage) -> Result<U256> {
+        Ok(self
+            .db
+            .retrieve_gas_payment_by_gas_payment_key(GasPaymentKey {
+                message_id: message.id(),
+                destination: message.destination,
+            })?
+            .payment)
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use std::str::FromStr;
+    use std::{collections::HashMap, str::FromStr, sync::Arc, time::Duration};
 
     use hyperlane_base::db::{test_utils, HyperlaneRocksDB};
     use hyperlane_core::{
-        HyperlaneDomain, HyperlaneMessage, InterchainGasPayment, LogMeta, TxCostEstimate, H160,
-        H256, U256,
+        FixedPointNumber, HyperlaneDomain, HyperlaneMessage, InterchainGasPayment, LogMeta,
+        TxCostEstimate, H160, H256, U256,
     };
+    use tokio::sync::RwLock;
 
     use super::GasPaymentEnforcer;
     use crate::settings::{
-        matching_list::MatchingList, GasPaymentEnforcementConf, GasPaymentEnforcementPolicy,
+        matching_list::MatchingList, reloader::ReloadableConf, ExchangeRateConf,
+        GasPaymentEnforcementConf, GasPaymentEnforcementPolicy, TokenExchangeRate,
     };
 
+    fn shared_conf(policies: Vec<GasPaymentEnforcementConf>) -> Arc<RwLock<ReloadableConf>> {
+        Arc::new(RwLock::new(ReloadableConf::new(
+            Default::default(),
+            policies,
+        )))
+    }
+
+    fn enforcer_with_policies(
+        policies: Vec<GasPaymentEnforcementConf>,
+        db: HyperlaneRocksDB,
+    ) -> GasPaymentEnforcer {
+        GasPaymentEnforcer::new(shared_conf(policies), db)
+    }
+
     #[tokio::test]
     async fn test_empty_whitelist() {
         test_utils::run_test_db(|db| async move {
@@ -165,7 +243,7 @@ mod test {
                 db,
             );
 
-            let enforcer = GasPaymentEnforcer::new(
+            let enforcer = enforcer_with_policies(
                 // Require a payment
                 vec![GasPaymentEnforcementConf {
                     policy: GasPaymentEnforcementPolicy::Minimum {
@@ -199,7 +277,7 @@ mod test {
             let hyperlane_db =
                 HyperlaneRocksDB::new(&HyperlaneDomain::new_test_domain("test_no_match"), db);
             let matching_list = serde_json::from_str(r#"[{"origindomain": 234}]"#).unwrap();
-            let enforcer = GasPaymentEnforcer::new(
+            let enforcer = enforcer_with_policies(
                 // Require a payment
                 vec![GasPaymentEnforcementConf {
                     policy: GasPaymentEnforcementPolicy::None,
@@ -234,7 +312,7 @@ mod test {
                 &HyperlaneDomain::new_test_domain("test_different_destinations"),
                 db,
             );
-            let enforcer = GasPaymentEnforcer::new(
+            let enforcer = enforcer_with_policies(
                 vec![GasPaymentEnforcementConf {
                     policy: GasPaymentEnforcementPolicy::Minimum {
                         payment: U256::one(),
@@ -291,7 +369,7 @@ mod test {
                 db,
             );
 
-            let enforcer = GasPaymentEnforcer::new(
+            let enforcer = enforcer_with_policies(
                 vec![GasPaymentEnforcementConf {
                     policy: GasPaymentEnforcementPolicy::Minimum {
                         payment: U256::from(2),
@@ -345,7 +423,7 @@ mod test {
                 &format!(r#"[{{"senderaddress": "{sender_address}", "recipientaddress": "{recipient_address}"}}]"#)
             ).unwrap();
 
-            let enforcer = GasPaymentEnforcer::new(
+            let enforcer = enforcer_with_policies(
                 vec![
                     GasPaymentEnforcementConf {
                         // No payment for special cases
@@ -403,4 +481,111 @@ mod test {
         })
         .await;
     }
+
+    #[tokio::test]
+    async fn test_update_policies() {
+        test_utils::run_test_db(|db| async move {
+            let hyperlane_db = HyperlaneRocksDB::new(
+                &HyperlaneDomain::new_test_domain("test_update_policies"),
+                db,
+            );
+            let conf = shared_conf(vec![GasPaymentEnforcementConf {
+                policy: GasPaymentEnforcementPolicy::Minimum {
+                    payment: U256::one(),
+                },
+                matching_list: MatchingList::default(),
+            }]);
+            let enforcer = GasPaymentEnforcer::new(conf.clone(), hyperlane_db);
+
+            // No payment was made, so the minimum policy is not met
+            assert!(enforcer
+                .message_meets_gas_payment_requirement(
+                    &HyperlaneMessage::default(),
+                    &TxCostEstimate::default(),
+                )
+                .await
+                .unwrap()
+                .is_none());
+
+            *conf.write().await = ReloadableConf::new(
+                Default::default(),
+                vec![GasPaymentEnforcementConf::default()],
+            );
+
+            // The default policy doesn't require any payment
+            assert!(enforcer
+                .message_meets_gas_payment_requirement(
+                    &HyperlaneMessage::default(),
+                    &TxCostEstimate::default(),
+                )
+                .await
+                .unwrap()
+                .is_some());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_value_of_uses_the_matching_policy() {
+        test_utils::run_test_db(|db| async move {
+            let hyperlane_db = HyperlaneRocksDB::new(
+                &HyperlaneDomain::new_test_domain("test_value_of_uses_the_matching_policy"),
+                db,
+            );
+            let exchange_rate = |price: &str| {
+                GasPaymentEnforcementPolicy::ExchangeRate(ExchangeRateConf {
+                    margin: FixedPointNumber::from(1),
+                    exchange_rates: HashMap::from([(
+                        "test".to_owned(),
+                        TokenExchangeRate {
+                            price: FixedPointNumber::from_str(price).unwrap(),
+                            decimals: 18,
+                        },
+                    )]),
+                    exchange_rates_source: None,
+                    refresh_interval: Duration::from_secs(60),
+                    chain_names: HashMap::from([(1, "test".to_owned())]),
+                })
+            };
+            let sender: H256 = H160::random().into();
+            let enforcer = enforcer_with_policies(
+                vec![
+                    GasPaymentEnforcementConf {
+                        policy: exchange_rate("2"),
+                        matching_list: serde_json::from_str(&format!(
+                            r#"[{{"senderaddress": "{sender:?}"}}]"#
+                        ))
+                        .unwrap(),
+                    },
+                    GasPaymentEnforcementConf {
+                        policy: exchange_rate("3"),
+                        matching_list: MatchingList::default(),
+                    },
+                ],
+                hyperlane_db,
+            );
+            let one_token = U256::exp10(18);
+
+            let matching_message = HyperlaneMessage {
+                sender,
+                ..HyperlaneMessage::default()
+            };
+            assert_eq!(
+                enforcer.value_of(&matching_message, 1, one_token).await,
+                Some(2.)
+            );
+            assert_eq!(
+                enforcer
+                    .value_of(&HyperlaneMessage::default(), 1, one_token)
+                    .await,
+                Some(3.)
+            );
+            // There is no exchange rate for this domain
+            assert_eq!(
+                enforcer.value_of(&matching_message, 2, one_token).await,
+                None
+            );
+        })
+        .await;
+    }
 }
@@ -0,0 +1,330 @@
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use eyre::{eyre, Context, Result};
+use hyperlane_core::{
+    FixedPointNumber, HyperlaneMessage, InterchainGasExpenditure, InterchainGasPayment,
+    TxCostEstimate, U256,
+};
+use tokio::{sync::RwLock, time::timeout};
+use tracing::{debug, warn};
+
+use crate::{
+    msg::gas_payment::GasPaymentPolicy,
+    settings::{ExchangeRateConf, TokenExchangeRate},
+};
+
+/// How long to wait for the exchange rates source before giving up
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The exchange rates of the chains' native tokens, read from the config and
+/// optionally refreshed from `exchange_rates_source`.
+#[derive(Debug)]
+pub struct ExchangeRates {
+    conf: ExchangeRateConf,
+    /// Rates from `exchange_rates_source`, and when they were last read
+    fetched_rates: RwLock<Option<(Instant, HashMap<String, TokenExchangeRate>)>>,
+}
+
+impl ExchangeRates {
+    pub fn new(conf: ExchangeRateConf) -> Self {
+        Self {
+            conf,
+            fetched_rates: RwLock::new(None),
+        }
+    }
+
+    /// The value of `amount` of the smallest unit of `domain`'s native token in
+    /// the common currency.
+    pub async fn value_of(
+        &self,
+        domain: u32,
+        amount: FixedPointNumber,
+    ) -> Result<FixedPointNumber> {
+        let rate = self.exchange_rate(domain).await?;
+        let unit = FixedPointNumber::from_str(&format!("1e-{}", rate.decimals))?;
+        Ok(amount * rate.price * unit)
+    }
+
+    /// Re-read the exchange rates from the source if they are stale. If reading
+    /// them fails, the previous rates are kept.
+    async fn refresh_rates(&self) {
+        let Some(source) = &self.conf.exchange_rates_source else {
+            return;
+        };
+        if !self.rates_are_stale(&*self.fetched_rates.read().await) {
+            return;
+        }
+
+        // Fetch without holding the lock, so a slow source doesn't block
+        // messages that only need to read the current rates
+        let fetched = match timeout(FETCH_TIMEOUT, fetch_exchange_rates(source)).await {
+            Ok(fetched) => fetched,
+            Err(_) => Err(eyre!("Timed out after {FETCH_TIMEOUT:?}")),
+        };
+
+        let mut fetched_rates = self.fetched_rates.write().await;
+        // Another message may have refreshed the rates in the meantime
+        if !self.rates_are_stale(&fetched_rates) {
+            return;
+        }
+        match fetched {
+            Ok(rates) => {
+                debug!(?rates, %source, "Fetched exchange rates");
+                *fetched_rates = Some((Instant::now(), rates));
+            }
+            Err(err) => {
+                warn!(error = ?err, %source, "Failed to fetch exchange rates, keeping previous rates");
+                // Wait for the refresh interval before trying again. Without
+                // previous rates, the configured ones are used until then.
+                match fetched_rates.as_mut() {
+                    Some((fetched_at, _)) => *fetched_at = Instant::now(),
+                    None => *fetched_rates = Some((Instant::now(), HashMap::new())),
+                }
+            }
+        }
+    }
+
+    fn rates_are_stale(
+        &self,
+        fetched_rates: &Option<(Instant, HashMap<String, TokenExchangeRate>)>,
+    ) -> bool {
+        fetched_rates.as_ref().map_or(true, |(fetched_at, _)| {
+            fetched_at.elapsed() >= self.conf.refresh_interval
+        })
+    }
+
+    async fn exchange_rate(&self, domain: u32) -> Result<TokenExchangeRate> {
+        self.refresh_rates().await;
+        let chain_name = self
+            .conf
+            .chain_names
+            .get(&domain)
+            .ok_or_else(|| eyre!("No chain config for domain {domain}"))?;
+        if let Some((_, rates)) = self.fetched_rates.read().await.as_ref() {
+            if let Some(rate) = rates.get(chain_name) {
+                return Ok(rate.clone());
+            }
+        }
+        self.conf
+            .exchange_rates
+            .get(chain_name)
+            .cloned()
+            .ok_or_else(|| eyre!("No exchange rate configured for chain {chain_name}"))
+    }
+}
+
+async fn fetch_exchange_rates(source: &str) -> Result<HashMap<String, TokenExchangeRate>> {
+    if source.starts_with("http://") || source.starts_with("https://") {
+        Ok(reqwest::get(source)
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    } else {
+        let contents = tokio::fs::read_to_string(source)
+            .await
+            .with_context(|| format!("Failed to read exchange rates file {source}"))?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+}
+
+/// Approves a message if the value of its payment on the origin chain covers
+/// the value of the estimated transaction cost on the destination chain times
+/// a margin. Both are converted to a common currency using the exchange rates
+/// of the chains' native tokens.
+#[derive(Debug)]
+pub struct GasPaymentPolicyExchangeRate {
+    margin: FixedPointNumber,
+    rates: ExchangeRates,
+}
+
+impl GasPaymentPolicyExchangeRate {
+    pub fn new(conf: ExchangeRateConf) -> Self {
+        Self {
+            margin: conf.margin.clone(),
+            rates: ExchangeRates::new(conf),
+        }
+    }
+}
+
+#[async_trait]
+impl GasPaymentPolicy for GasPaymentPolicyExchangeRate {
+    async fn message_meets_gas_payment_requirement(
+        &self,
+        message: &HyperlaneMessage,
+        current_payment: &InterchainGasPayment,
+        current_expenditure: &InterchainGasExpenditure,
+        tx_cost_estimate: &TxCostEstimate,
+    ) -> Result<Option<U256>> {
+        let payment_value = self
+            .rates
+            .value_of(message.origin, current_payment.payment.try_into()?)
+            .await?;
+        // Tokens spent on previous attempts to deliver this message are paid for
+        // by the same payment
+        let spent_value = self
+            .rates
+            .value_of(
+                message.destination,
+                current_expenditure.tokens_used.try_into()?,
+            )
+            .await?;
+        let cost = FixedPointNumber::try_from(tx_cost_estimate.gas_limit)?
+            * tx_cost_estimate.gas_price.clone();
+        let cost_value =
+            self.rates.value_of(message.destination, cost).await? * self.margin.clone();
+
+        debug!(
+            ?payment_value,
+            ?spent_value,
+            ?cost_value,
+            "Comparing the value of the gas payment to the transaction cost"
+        );
+        if payment_value >= spent_value + cost_value {
+            Ok(Some(tx_cost_estimate.gas_limit))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn exchange_rates(&self) -> Option<&ExchangeRates> {
+        Some(&self.rates)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::H256;
+
+    use super::*;
+
+    const ORIGIN: u32 = 1;
+    const DESTINATION: u32 = 2;
+
+    fn rate(price: &str, decimals: u32) -> TokenExchangeRate {
+        TokenExchangeRate {
+            price: FixedPointNumber::from_str(price).unwrap(),
+            decimals,
+        }
+    }
+
+    fn policy(margin: &str) -> GasPaymentPolicyExchangeRate {
+        GasPaymentPolicyExchangeRate::new(ExchangeRateConf {
+            margin: FixedPointNumber::from_str(margin).unwrap(),
+            // The origin token is worth 2000 and has 18 decimals, the destination
+            // token is worth 0.5 and has 6 decimals
+            exchange_rates: HashMap::from([
+                ("origin".to_owned(), rate("2000", 18)),
+                ("destination".to_owned(), rate("0.5", 6)),
+            ]),
+            exchange_rates_source: None,
+            refresh_interval: Duration::from_secs(60),
+            chain_names: HashMap::from([
+                (ORIGIN, "origin".to_owned()),
+                (DESTINATION, "destination".to_owned()),
+            ]),
+        })
+    }
+
+    fn payment(payment: U256) -> InterchainGasPayment {
+        InterchainGasPayment {
+            message_id: H256::zero(),
+            destination: DESTINATION,
+            payment,
+            gas_amount: U256::zero(),
+        }
+    }
+
+    fn expenditure(tokens_used: U256) -> InterchainGasExpenditure {
+        InterchainGasExpenditure {
+            message_id: H256::zero(),
+            gas_used: U256::zero(),
+            tokens_used,
+        }
+    }
+
+    fn message() -> HyperlaneMessage {
+        HyperlaneMessage {
+            origin: ORIGIN,
+            destination: DESTINATION,
+            ..Default::default()
+        }
+    }
+
+    // Costs 100_000 * 10 = 1_000_000 units of the destination token, i.e. one
+    // whole token worth 0.5
+    fn cost_estimate() -> TxCostEstimate {
+        TxCostEstimate {
+            gas_limit: U256::from(100_000),
+            gas_price: FixedPointNumber::from(10),
+            l2_gas_limit: None,
+        }
+    }
+
+    // 0.5 / 2000 = 0.00025 of the origin token, i.e. 25 * 10^13 of its smallest unit
+    const BREAK_EVEN_PAYMENT: u64 = 250_000_000_000_000;
+
+    #[tokio::test]
+    async fn test_payment_covers_cost_with_margin() {
+        let policy = policy("1.1");
+        let meets = |payment_amount: u64| {
+            let policy = &policy;
+            async move {
+                policy
+                    .message_meets_gas_payment_requirement(
+                        &message(),
+                        &payment(payment_amount.into()),
+                        &expenditure(U256::zero()),
+                        &cost_estimate(),
+                    )
+                    .await
+                    .unwrap()
+            }
+        };
+
+        assert_eq!(meets(BREAK_EVEN_PAYMENT).await, None);
+        assert_eq!(
+            meets(BREAK_EVEN_PAYMENT * 11 / 10).await,
+            Some(cost_estimate().gas_limit)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_previous_expenditure_is_deducted() {
+        let policy = policy("1");
+        let result = policy
+            .message_meets_gas_payment_requirement(
+                &message(),
+                &payment(BREAK_EVEN_PAYMENT.into()),
+                // A previous attempt already spent some of the payment
+                &expenditure(U256::from(1)),
+                &cost_estimate(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result, None);
+    }
+
+    #[tokio::test]
+    async fn test_missing_exchange_rate_is_an_error() {
+        let policy = policy("1");
+        let message = HyperlaneMessage {
+            destination: 3,
+            ..message()
+        };
+        assert!(policy
+            .message_meets_gas_payment_requirement(
+                &message,
+                &payment(U256::MAX),
+                &expenditure(U256::zero()),
+                &cost_estimate(),
+            )
+            .await
+            .is_err());
+    }
+}
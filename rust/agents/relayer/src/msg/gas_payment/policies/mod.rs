@@ -1,7 +1,9 @@
+mod exchange_rate;
 mod minimum;
 mod none;
 mod on_chain_fee_quoting;
 
+pub(crate) use exchange_rate::{ExchangeRates, GasPaymentPolicyExchangeRate};
 pub(crate) use minimum::GasPaymentPolicyMinimum;
 pub(crate) use none::GasPaymentPolicyNone;
 pub(crate) use on_chain_fee_quoting::GasPaymentPolicyOnChainFeeQuoting;
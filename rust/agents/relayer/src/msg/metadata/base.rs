@@ -10,11 +10,14 @@ use std::{
 use crate::{
     merkle_tree::builder::MerkleTreeBuilder,
     msg::metadata::{
-        multisig::{MerkleRootMultisigMetadataBuilder, MessageIdMultisigMetadataBuilder},
+        multisig::{
+            MerkleRootMultisigMetadataBuilder, MessageIdMultisigMetadataBuilder,
+            QuorumCheckpointCache,
+        },
         AggregationIsmMetadataBuilder, CcipReadIsmMetadataBuilder, NullMetadataBuilder,
         RoutingIsmMetadataBuilder,
     },
-    settings::matching_list::MatchingList,
+    settings::reloader::ReloadableConf,
 };
 use async_trait::async_trait;
 use derive_new::new;
@@ -22,7 +25,7 @@ use eyre::{Context, Result};
 use hyperlane_base::db::HyperlaneRocksDB;
 use hyperlane_base::{
     settings::{ChainConf, CheckpointSyncerConf},
-    CheckpointSyncer, CoreMetrics, MultisigCheckpointSyncer,
+    CheckpointCache, CheckpointSyncer, CoreMetrics, MultisigCheckpointSyncer,
 };
 use hyperlane_core::{
     accumulator::merkle::Proof, AggregationIsm, CcipReadIsm, Checkpoint, HyperlaneDomain,
@@ -113,11 +116,11 @@ pub struct IsmAwareAppContextClassifier {
 impl IsmAwareAppContextClassifier {
     pub fn new(
         destination_mailbox: Arc<dyn Mailbox>,
-        app_matching_lists: Vec<(MatchingList, String)>,
+        reloadable_conf: Arc<RwLock<ReloadableConf>>,
     ) -> Self {
         Self {
             default_ism: DefaultIsmCache::new(destination_mailbox),
-            app_context_classifier: AppContextClassifier::new(app_matching_lists),
+            app_context_classifier: AppContextClassifier::new(reloadable_conf),
         }
     }
 
@@ -141,7 +144,8 @@ impl IsmAwareAppContextClassifier {
 /// Classifies messages into an app context if they have one.
 #[derive(Debug, new)]
 pub struct AppContextClassifier {
-    app_matching_lists: Vec<(MatchingList, String)>,
+    /// Holds the `metric_app_contexts` matching lists, which can be reloaded
+    reloadable_conf: Arc<RwLock<ReloadableConf>>,
 }
 
 impl AppContextClassifier {
@@ -150,13 +154,14 @@ impl AppContextClassifier {
     /// An app context is a string that identifies the app that sent the message
     /// and exists just for metrics.
     /// An app context is chosen based on:
-    /// - the first element in `app_matching_lists` that matches the message
+    /// - the first element in `metric_app_contexts` that matches the message
     /// - if the message's ISM is the default ISM, the app context is "default_ism"
     pub async fn get_app_context(&self, message: &HyperlaneMessage) -> Result<Option<String>> {
         // Give priority to the matching list. If the app from the matching list happens
         // to use the default ISM, it's preferable to use the app context from the matching
         // list.
-        for (matching_list, app_context) in self.app_matching_lists.iter() {
+        let conf = self.reloadable_conf.read().await;
+        for (matching_list, app_context) in conf.message_filters.metric_app_contexts.iter() {
             if matching_list.msg_matches(message, false) {
                 return Ok(Some(app_context.clone()));
             }
@@ -280,6 +285,12 @@ pub struct BaseMetadataBuilder {
     db: HyperlaneRocksDB,
     max_depth: u32,
     app_context_classifier: IsmAwareAppContextClassifier,
+    /// Validator checkpoints shared by every metadata builder of the origin
+    checkpoint_cache: Arc<CheckpointCache>,
+    /// Quorum checkpoints that messages to this destination can be proven
+    /// against
+    #[new(default)]
+    quorum_checkpoints: QuorumCheckpointCache,
 }
 
 impl Debug for BaseMetadataBuilder {
@@ -320,6 +331,10 @@ impl BaseMetadataBuilder {
         Ok(proof)
     }
 
+    pub fn quorum_checkpoints(&self) -> &QuorumCheckpointCache {
+        &self.quorum_checkpoints
+    }
+
     pub async fn highest_known_leaf_index(&self) -> Option<u32> {
         self.origin_prover_sync.read().await.count().checked_sub(1)
     }
@@ -428,6 +443,7 @@ impl BaseMetadataBuilder {
             checkpoint_syncers,
             self.metrics.clone(),
             app_context,
+            self.checkpoint_cache.clone(),
         ))
     }
 }
@@ -46,6 +46,17 @@ pub trait MultisigIsmMetadataBuilder: AsRef<MessageMetadataBuilder> + Send + Syn
         checkpoint_syncer: &MultisigCheckpointSyncer,
     ) -> Result<Option<MultisigMetadata>>;
 
+    /// Metadata that can be built without fetching checkpoints from
+    /// validators, e.g. from a quorum checkpoint resolved for another message.
+    async fn cached_metadata(
+        &self,
+        _validators: &[H256],
+        _threshold: u8,
+        _message: &HyperlaneMessage,
+    ) -> Result<Option<MultisigMetadata>> {
+        Ok(None)
+    }
+
     fn token_layout(&self) -> Vec<MetadataToken>;
 
     fn format_metadata(&self, metadata: MultisigMetadata) -> Result<Vec<u8>> {
@@ -115,6 +126,15 @@ impl<T: MultisigIsmMetadataBuilder> MetadataBuilder for T {
             return Ok(None);
         }
 
+        if let Some(metadata) = self
+            .cached_metadata(&validators, threshold, message)
+            .await
+            .context(CTX)?
+        {
+            debug!(?message, ?metadata.checkpoint, "Reusing cached checkpoint with quorum");
+            return Ok(Some(self.format_metadata(metadata)?));
+        }
+
         let checkpoint_syncer = self
             .as_ref()
             .build_checkpoint_syncer(&validators, self.as_ref().app_context.clone())
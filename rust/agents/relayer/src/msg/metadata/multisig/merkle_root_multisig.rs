@@ -6,8 +6,8 @@ use derive_new::new;
 
 use eyre::{Context, Result};
 use hyperlane_base::MultisigCheckpointSyncer;
-use hyperlane_core::{unwrap_or_none_result, HyperlaneMessage, H256};
-use tracing::debug;
+use hyperlane_core::{unwrap_or_none_result, HyperlaneMessage, MultisigSignedCheckpoint, H256};
+use tracing::{debug, warn};
 
 use crate::msg::metadata::MessageMetadataBuilder;
 
@@ -28,6 +28,34 @@ impl MultisigIsmMetadataBuilder for MerkleRootMultisigMetadataBuilder {
         ]
     }
 
+    async fn cached_metadata(
+        &self,
+        validators: &[H256],
+        threshold: u8,
+        message: &HyperlaneMessage,
+    ) -> Result<Option<MultisigMetadata>> {
+        let (leaf_index, highest_leaf_index) =
+            unwrap_or_none_result!(self.leaf_index_range(message).await?);
+        let quorum_checkpoint = unwrap_or_none_result!(self.quorum_checkpoints().get(
+            validators,
+            threshold,
+            leaf_index,
+            highest_leaf_index
+        ));
+        match self
+            .metadata_with_proof(quorum_checkpoint, leaf_index)
+            .await
+        {
+            Ok(metadata) => Ok(Some(metadata)),
+            Err(err) => {
+                // Fall back to fetching a new checkpoint from the validators
+                warn!(error = ?err, "Failed to build metadata from cached quorum checkpoint, evicting it");
+                self.quorum_checkpoints().remove(validators, threshold);
+                Ok(None)
+            }
+        }
+    }
+
     async fn fetch_metadata(
         &self,
         validators: &[H256],
@@ -36,19 +64,8 @@ impl MultisigIsmMetadataBuilder for MerkleRootMultisigMetadataBuilder {
         checkpoint_syncer: &MultisigCheckpointSyncer,
     ) -> Result<Option<MultisigMetadata>> {
         const CTX: &str = "When fetching MerkleRootMultisig metadata";
-        let highest_leaf_index = unwrap_or_none_result!(
-            self.highest_known_leaf_index().await,
-            debug!("Couldn't get highest known leaf index")
-        );
-        let leaf_index = unwrap_or_none_result!(
-            self.get_merkle_leaf_id_by_message_id(message.id())
-                .await
-                .context(CTX)?,
-            debug!(
-                ?message,
-                "No merkle leaf found for message id, must have not been enqueued in the tree"
-            )
-        );
+        let (leaf_index, highest_leaf_index) =
+            unwrap_or_none_result!(self.leaf_index_range(message).await?);
         let quorum_checkpoint = unwrap_or_none_result!(
             checkpoint_syncer
                 .fetch_checkpoint_in_range(
@@ -66,14 +83,50 @@ impl MultisigIsmMetadataBuilder for MerkleRootMultisigMetadataBuilder {
                 highest_leaf_index, "Couldn't get checkpoint in range"
             )
         );
+        // Every message below this checkpoint can now be proven against it
+        self.quorum_checkpoints()
+            .insert(validators, threshold, quorum_checkpoint.clone());
+        self.metadata_with_proof(quorum_checkpoint, leaf_index)
+            .await
+            .map(Some)
+    }
+}
+
+impl MerkleRootMultisigMetadataBuilder {
+    /// The message's leaf index and the highest leaf index of the local merkle
+    /// tree, between which a quorum checkpoint can be used to prove the message.
+    async fn leaf_index_range(&self, message: &HyperlaneMessage) -> Result<Option<(u32, u32)>> {
+        const CTX: &str = "When fetching MerkleRootMultisig metadata";
+        let highest_leaf_index = unwrap_or_none_result!(
+            self.highest_known_leaf_index().await,
+            debug!("Couldn't get highest known leaf index")
+        );
+        let leaf_index = unwrap_or_none_result!(
+            self.get_merkle_leaf_id_by_message_id(message.id())
+                .await
+                .context(CTX)?,
+            debug!(
+                ?message,
+                "No merkle leaf found for message id, must have not been enqueued in the tree"
+            )
+        );
+        Ok(Some((leaf_index, highest_leaf_index)))
+    }
+
+    async fn metadata_with_proof(
+        &self,
+        quorum_checkpoint: MultisigSignedCheckpoint,
+        leaf_index: u32,
+    ) -> Result<MultisigMetadata> {
+        const CTX: &str = "When fetching MerkleRootMultisig metadata";
         let proof = self
             .get_proof(leaf_index, quorum_checkpoint.checkpoint.checkpoint)
             .await
             .context(CTX)?;
-        Ok(Some(MultisigMetadata::new(
+        Ok(MultisigMetadata::new(
             quorum_checkpoint,
             leaf_index,
             Some(proof),
-        )))
+        ))
     }
 }
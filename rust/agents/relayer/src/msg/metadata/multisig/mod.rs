@@ -1,8 +1,10 @@
 mod base;
 mod merkle_root_multisig;
 mod message_id_multisig;
+mod quorum_checkpoint_cache;
 
 pub use base::{MetadataToken, MultisigIsmMetadataBuilder, MultisigMetadata};
 
 pub use merkle_root_multisig::MerkleRootMultisigMetadataBuilder;
 pub use message_id_multisig::MessageIdMultisigMetadataBuilder;
+pub use quorum_checkpoint_cache::QuorumCheckpointCache;
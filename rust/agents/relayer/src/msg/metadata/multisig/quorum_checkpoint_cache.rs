@@ -0,0 +1,143 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use hyperlane_core::{MultisigSignedCheckpoint, H256};
+
+/// How long a cached quorum checkpoint is reused before checkpoints are
+/// fetched from validators again
+const QUORUM_CHECKPOINT_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// The highest quorum checkpoint resolved for each validator set and
+/// threshold.
+///
+/// A `MerkleRootMultisig` ISM accepts any quorum checkpoint at or above a
+/// message's leaf index, as long as the message can be proven against its
+/// root. So once a quorum checkpoint has been resolved for one message, every
+/// message below it can derive its proof from the local merkle tree instead
+/// of fetching checkpoints from validators again.
+///
+/// Checkpoints expire after a while, and are evicted when metadata built from
+/// them fails, so a bad checkpoint can't block messages forever.
+#[derive(Debug)]
+pub struct QuorumCheckpointCache {
+    ttl: Duration,
+    checkpoints: Mutex<HashMap<(Vec<H256>, u8), (MultisigSignedCheckpoint, Instant)>>,
+}
+
+impl Default for QuorumCheckpointCache {
+    fn default() -> Self {
+        Self::new(QUORUM_CHECKPOINT_TTL)
+    }
+}
+
+impl QuorumCheckpointCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            checkpoints: Default::default(),
+        }
+    }
+
+    /// Gets the cached quorum checkpoint of a validator set, if it hasn't
+    /// expired and its index is in `minimum_index..=maximum_index`.
+    pub fn get(
+        &self,
+        validators: &[H256],
+        threshold: u8,
+        minimum_index: u32,
+        maximum_index: u32,
+    ) -> Option<MultisigSignedCheckpoint> {
+        let mut checkpoints = self.checkpoints.lock().unwrap();
+        let key = (validators.to_vec(), threshold);
+        let (checkpoint, cached_at) = checkpoints.get(&key)?;
+        if cached_at.elapsed() >= self.ttl {
+            checkpoints.remove(&key);
+            return None;
+        }
+        Some(checkpoint)
+            .filter(|checkpoint| {
+                (minimum_index..=maximum_index).contains(&checkpoint.checkpoint.index)
+            })
+            .cloned()
+    }
+
+    /// Caches a quorum checkpoint of a validator set, unless a higher one is
+    /// already cached.
+    pub fn insert(&self, validators: &[H256], threshold: u8, checkpoint: MultisigSignedCheckpoint) {
+        let mut checkpoints = self.checkpoints.lock().unwrap();
+        let key = (validators.to_vec(), threshold);
+        let is_higher = checkpoints.get(&key).map_or(true, |(cached, cached_at)| {
+            cached_at.elapsed() >= self.ttl || checkpoint.checkpoint.index > cached.checkpoint.index
+        });
+        if is_higher {
+            checkpoints.insert(key, (checkpoint, Instant::now()));
+        }
+    }
+
+    /// Evicts the cached quorum checkpoint of a validator set.
+    pub fn remove(&self, validators: &[H256], threshold: u8) {
+        self.checkpoints
+            .lock()
+            .unwrap()
+            .remove(&(validators.to_vec(), threshold));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::{Checkpoint, CheckpointWithMessageId};
+
+    use super::*;
+
+    fn checkpoint(index: u32) -> MultisigSignedCheckpoint {
+        MultisigSignedCheckpoint {
+            checkpoint: CheckpointWithMessageId {
+                checkpoint: Checkpoint {
+                    merkle_tree_hook_address: H256::zero(),
+                    mailbox_domain: 1,
+                    root: H256::from_low_u64_be(index as u64),
+                    index,
+                },
+                message_id: H256::zero(),
+            },
+            signatures: vec![],
+        }
+    }
+
+    #[test]
+    fn test_keeps_highest_checkpoint_per_validator_set() {
+        let cache = QuorumCheckpointCache::default();
+        let validators = [H256::random(), H256::random()];
+
+        cache.insert(&validators, 2, checkpoint(10));
+        cache.insert(&validators, 2, checkpoint(5));
+        assert_eq!(
+            cache.get(&validators, 2, 3, 12).map(|c| c.checkpoint.index),
+            Some(10)
+        );
+        // Messages above the checkpoint, or a local tree that can't prove it,
+        // need a new checkpoint
+        assert!(cache.get(&validators, 2, 11, 12).is_none());
+        assert!(cache.get(&validators, 2, 3, 9).is_none());
+        // Other validator sets and thresholds are cached separately
+        assert!(cache.get(&validators, 1, 3, 12).is_none());
+        assert!(cache.get(&validators[..1], 2, 3, 12).is_none());
+    }
+
+    #[test]
+    fn test_expired_and_evicted_checkpoints_are_not_reused() {
+        let validators = [H256::random()];
+
+        let cache = QuorumCheckpointCache::new(Duration::ZERO);
+        cache.insert(&validators, 1, checkpoint(10));
+        assert!(cache.get(&validators, 1, 3, 12).is_none());
+
+        let cache = QuorumCheckpointCache::default();
+        cache.insert(&validators, 1, checkpoint(10));
+        cache.remove(&validators, 1);
+        assert!(cache.get(&validators, 1, 3, 12).is_none());
+    }
+}
@@ -28,7 +28,9 @@
 pub(crate) mod gas_payment;
 pub(crate) mod metadata;
 pub(crate) mod op_queue;
+pub(crate) mod op_scheduler;
 pub(crate) mod op_submitter;
 pub(crate) mod pending_message;
 pub(crate) mod pending_operation;
 pub(crate) mod processor;
+pub(crate) mod profitability;
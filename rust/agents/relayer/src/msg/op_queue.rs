@@ -1,17 +1,105 @@
-use std::{cmp::Reverse, collections::BinaryHeap, sync::Arc};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    sync::Arc,
+    time::Instant,
+};
 
 use derive_new::new;
-use hyperlane_core::MpmcReceiver;
+use hyperlane_core::{MpmcReceiver, H256};
 use prometheus::{IntGauge, IntGaugeVec};
 use tokio::sync::Mutex;
 use tracing::{info, instrument};
 
 use crate::server::MessageRetryRequest;
 
-use super::pending_operation::PendingOperation;
+use super::{op_scheduler::OpScheduler, pending_operation::PendingOperation};
 
 pub type QueueOperation = Box<dyn PendingOperation>;
 
+/// The priority queue backing an `OpQueue`, shared with anything that needs to inspect its contents
+pub type OperationPriorityQueue = Arc<Mutex<AppQueues>>;
+
+/// Operations waiting in an `OpQueue`, with a priority queue per app so that
+/// operations can be shared fairly between apps by only looking at the head of
+/// each app's queue
+#[derive(Debug, Default)]
+pub struct AppQueues {
+    queues: HashMap<String, BinaryHeap<Reverse<QueueOperation>>>,
+}
+
+impl AppQueues {
+    /// Push an operation onto the queue of `app`
+    pub fn push(&mut self, app: String, op: QueueOperation) {
+        self.queues.entry(app).or_default().push(Reverse(op));
+    }
+
+    /// The highest priority operation of `app`, if it is ready to be processed
+    pub fn peek_ready(&self, app: &str) -> Option<&QueueOperation> {
+        let Reverse(op) = self.queues.get(app)?.peek()?;
+        op.next_attempt_after()
+            .map_or(true, |next_attempt| next_attempt <= Instant::now())
+            .then_some(op)
+    }
+
+    /// The apps whose highest priority operation is ready to be processed.
+    /// Ready operations are always at the head of their app's queue, since
+    /// they have the earliest `next_attempt_after`.
+    pub fn ready_apps(&self) -> Vec<String> {
+        self.queues
+            .keys()
+            .filter(|app| self.peek_ready(app).is_some())
+            .cloned()
+            .collect()
+    }
+
+    /// Pop the highest priority operation of `app`
+    pub fn pop(&mut self, app: &str) -> Option<QueueOperation> {
+        let queue = self.queues.get_mut(app)?;
+        let Reverse(op) = queue.pop()?;
+        if queue.is_empty() {
+            self.queues.remove(app);
+        }
+        Some(op)
+    }
+
+    /// Pop the highest priority operation across all apps
+    pub fn pop_by_priority(&mut self) -> Option<QueueOperation> {
+        let app = self
+            .queues
+            .iter()
+            .filter_map(|(app, queue)| Some((app, queue.peek()?)))
+            .min_by(|(_, a), (_, b)| a.0.cmp(&b.0))
+            .map(|(app, _)| app.clone())?;
+        self.pop(&app)
+    }
+
+    /// The operations of `app`, in no particular order
+    pub fn app_ops<'a>(&'a self, app: &str) -> impl Iterator<Item = &'a QueueOperation> {
+        self.queues
+            .get(app)
+            .into_iter()
+            .flat_map(|queue| queue.iter().map(|Reverse(op)| op))
+    }
+
+    /// All operations, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = &QueueOperation> {
+        self.queues
+            .values()
+            .flat_map(|queue| queue.iter().map(|Reverse(op)| op))
+    }
+
+    /// The number of operations across all apps
+    pub fn len(&self) -> usize {
+        self.queues.values().map(BinaryHeap::len).sum()
+    }
+
+    /// Whether there are no operations
+    pub fn is_empty(&self) -> bool {
+        self.queues.values().all(BinaryHeap::is_empty)
+    }
+}
+
 /// Queue of generic operations that can be submitted to a destination chain.
 /// Includes logic for maintaining queue metrics by the destination and `app_context` of an operation
 #[derive(Debug, Clone, new)]
@@ -20,7 +108,11 @@ pub struct OpQueue {
     queue_metrics_label: String,
     retry_rx: MpmcReceiver<MessageRetryRequest>,
     #[new(default)]
-    queue: Arc<Mutex<BinaryHeap<Reverse<QueueOperation>>>>,
+    queue: OperationPriorityQueue,
+    /// Shares the queue fairly between apps. If not set, operations are popped
+    /// purely by priority.
+    #[new(default)]
+    scheduler: Option<Arc<Mutex<OpScheduler>>>,
 }
 
 impl OpQueue {
@@ -30,7 +122,11 @@ impl OpQueue {
         // increment the metric before pushing onto the queue, because we lose ownership afterwards
         self.get_operation_metric(op.as_ref()).inc();
 
-        self.queue.lock().await.push(Reverse(op));
+        let app = match &self.scheduler {
+            Some(scheduler) => scheduler.lock().await.group_of(&op),
+            None => String::new(),
+        };
+        self.queue.lock().await.push(app, op);
     }
 
     /// Pop an element from the queue and update metrics
@@ -45,11 +141,32 @@ impl OpQueue {
     pub async fn pop_many(&mut self, limit: usize) -> Vec<QueueOperation> {
         self.process_retry_requests().await;
         let mut queue = self.queue.lock().await;
-        let mut popped = vec![];
-        while let Some(Reverse(op)) = queue.pop() {
+        let popped = match &self.scheduler {
+            Some(scheduler) => {
+                let ready_apps = queue.ready_apps();
+                if ready_apps.is_empty() {
+                    // Nothing is ready, so there's nothing to share fairly
+                    Self::pop_by_priority(&mut queue, limit)
+                } else {
+                    scheduler
+                        .lock()
+                        .await
+                        .schedule(&mut queue, ready_apps, limit)
+                }
+            }
+            None => Self::pop_by_priority(&mut queue, limit),
+        };
+        for op in &popped {
             // even if the metric is decremented here, the operation may fail to process and be re-added to the queue.
             // in those cases, the queue length will look like it has spikes whose sizes are at most `limit`
             self.get_operation_metric(op.as_ref()).dec();
+        }
+        popped
+    }
+
+    fn pop_by_priority(queue: &mut AppQueues, limit: usize) -> Vec<QueueOperation> {
+        let mut popped = vec![];
+        while let Some(op) = queue.pop_by_priority() {
             popped.push(op);
             if popped.len() >= limit {
                 break;
@@ -58,6 +175,22 @@ impl OpQueue {
         popped
     }
 
+    /// Remove an operation from the queue by its id and update metrics
+    #[instrument(skip(self), fields(queue_label=%self.queue_metrics_label), level = "debug")]
+    pub async fn remove(&self, id: H256) -> Option<QueueOperation> {
+        let mut queue = self.queue.lock().await;
+        let app_queue = queue
+            .queues
+            .values_mut()
+            .find(|app_queue| app_queue.iter().any(|Reverse(op)| op.id() == id))?;
+        let (removed, mut kept): (Vec<_>, BinaryHeap<_>) =
+            app_queue.drain().partition(|Reverse(op)| op.id() == id);
+        app_queue.append(&mut kept);
+        let Reverse(op) = removed.into_iter().next()?;
+        self.get_operation_metric(op.as_ref()).dec();
+        Some(op)
+    }
+
     pub async fn process_retry_requests(&mut self) {
         // TODO: could rate-limit ourselves here, but we expect the volume of messages over this channel to
         // be very low.
@@ -71,23 +204,41 @@ impl OpQueue {
             return;
         }
         let mut queue = self.queue.lock().await;
-        let mut reprioritized_queue: BinaryHeap<_> = queue
-            .drain()
-            .map(|Reverse(mut op)| {
-                // Can check for equality here because of the PartialEq implementation for MessageRetryRequest,
-                // but can't use `contains` because the types are different
-                if message_retry_requests.iter().any(|r| r == op) {
-                    info!(
-                        operation = %op,
-                        queue_label = %self.queue_metrics_label,
-                        "Retrying OpQueue operation"
-                    );
-                    op.reset_attempts()
-                }
-                Reverse(op)
-            })
-            .collect();
-        queue.append(&mut reprioritized_queue);
+        for app_queue in queue.queues.values_mut() {
+            let mut reprioritized_queue: BinaryHeap<_> = app_queue
+                .drain()
+                .map(|Reverse(mut op)| {
+                    // Can check for equality here because of the PartialEq implementation for MessageRetryRequest,
+                    // but can't use `contains` because the types are different
+                    if message_retry_requests.iter().any(|r| r == op) {
+                        info!(
+                            operation = %op,
+                            queue_label = %self.queue_metrics_label,
+                            "Retrying OpQueue operation"
+                        );
+                        op.reset_attempts()
+                    }
+                    Reverse(op)
+                })
+                .collect();
+            app_queue.append(&mut reprioritized_queue);
+        }
+    }
+
+    /// Share this queue fairly between apps using the given scheduler
+    pub fn with_scheduler(mut self, scheduler: OpScheduler) -> Self {
+        self.scheduler = Some(Arc::new(Mutex::new(scheduler)));
+        self
+    }
+
+    /// The label of this queue, e.g. `prepare_queue`
+    pub fn label(&self) -> &str {
+        &self.queue_metrics_label
+    }
+
+    /// The underlying priority queue, e.g. for inspecting its contents
+    pub fn queue(&self) -> &OperationPriorityQueue {
+        &self.queue
     }
 
     /// Get the metric associated with this operation
@@ -99,12 +250,12 @@ impl OpQueue {
 }
 
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
     use super::*;
     use crate::msg::pending_operation::PendingOperationResult;
     use hyperlane_core::{
-        HyperlaneDomain, HyperlaneMessage, KnownHyperlaneDomain, MpmcChannel, TryBatchAs,
-        TxOutcome, H256,
+        HyperlaneDomain, HyperlaneMessage, KnownHyperlaneDomain, MpmcChannel, PendingMessageStage,
+        TryBatchAs, TxOutcome, H256,
     };
     use std::{
         collections::VecDeque,
@@ -112,20 +263,39 @@ mod test {
     };
 
     #[derive(Debug, Clone)]
-    struct MockPendingOperation {
+    pub(crate) struct MockPendingOperation {
         id: H256,
         seconds_to_next_attempt: u64,
         destination_domain: HyperlaneDomain,
+        sender_address: H256,
+        recipient_address: H256,
+        body: Vec<u8>,
     }
 
     impl MockPendingOperation {
-        fn new(seconds_to_next_attempt: u64, destination_domain: HyperlaneDomain) -> Self {
+        pub(crate) fn new(
+            seconds_to_next_attempt: u64,
+            destination_domain: HyperlaneDomain,
+        ) -> Self {
             Self {
                 id: H256::random(),
                 seconds_to_next_attempt,
                 destination_domain,
+                sender_address: H256::random(),
+                recipient_address: H256::random(),
+                body: vec![],
             }
         }
+
+        pub(crate) fn with_sender_address(mut self, sender_address: H256) -> Self {
+            self.sender_address = sender_address;
+            self
+        }
+
+        pub(crate) fn with_body(mut self, body: Vec<u8>) -> Self {
+            self.body = body;
+            self
+        }
     }
 
     impl TryBatchAs<HyperlaneMessage> for MockPendingOperation {}
@@ -149,14 +319,38 @@ mod test {
         }
 
         fn origin_domain_id(&self) -> u32 {
-            todo!()
+            0
         }
 
         fn destination_domain(&self) -> &HyperlaneDomain {
             &self.destination_domain
         }
 
+        fn sender_address(&self) -> &H256 {
+            &self.sender_address
+        }
+
+        fn recipient_address(&self) -> &H256 {
+            &self.recipient_address
+        }
+
+        fn nonce(&self) -> u32 {
+            0
+        }
+
+        fn body(&self) -> &[u8] {
+            &self.body
+        }
+
         fn app_context(&self) -> Option<String> {
+            None
+        }
+
+        fn stage(&self) -> PendingMessageStage {
+            PendingMessageStage::Prepare
+        }
+
+        fn set_stage(&mut self, _stage: PendingMessageStage) {
             todo!()
         }
 
@@ -164,6 +358,10 @@ mod test {
             todo!()
         }
 
+        async fn check_submittable(&mut self) -> PendingOperationResult {
+            todo!()
+        }
+
         /// Submit this operation to the blockchain and report if it was successful
         /// or not.
         async fn submit(&mut self) {
@@ -196,9 +394,17 @@ mod test {
         fn set_retries(&mut self, _retries: u32) {
             todo!()
         }
+
+        fn num_retries(&self) -> u32 {
+            0
+        }
+
+        fn last_error(&self) -> Option<String> {
+            None
+        }
     }
 
-    fn dummy_metrics_and_label() -> (IntGaugeVec, String) {
+    pub(crate) fn dummy_metrics_and_label() -> (IntGaugeVec, String) {
         (
             IntGaugeVec::new(
                 prometheus::Opts::new("op_queue", "OpQueue metrics"),
@@ -326,4 +532,35 @@ mod test {
         assert_eq!(popped[3], op_ids[0]);
         assert_eq!(popped[4], op_ids[1]);
     }
+
+    #[tokio::test]
+    async fn test_remove_by_id() {
+        let (metrics, queue_metrics_label) = dummy_metrics_and_label();
+        let mpmc_channel = MpmcChannel::new(100);
+        let mut op_queue = OpQueue::new(metrics, queue_metrics_label, mpmc_channel.receiver());
+
+        let destination_domain: HyperlaneDomain = KnownHyperlaneDomain::Injective.into();
+        let ops: Vec<_> = (1..=3)
+            .map(|seconds_to_next_attempt| {
+                Box::new(MockPendingOperation::new(
+                    seconds_to_next_attempt,
+                    destination_domain.clone(),
+                )) as QueueOperation
+            })
+            .collect();
+        let op_ids: Vec<_> = ops.iter().map(|op| op.id()).collect();
+        for op in ops {
+            op_queue.push(op).await;
+        }
+
+        let removed = op_queue.remove(op_ids[1]).await.unwrap();
+        assert_eq!(removed.id(), op_ids[1]);
+        assert!(op_queue.remove(op_ids[1]).await.is_none());
+
+        let mut popped = vec![];
+        while let Some(op) = op_queue.pop().await {
+            popped.push(op.id());
+        }
+        assert_eq!(popped, vec![op_ids[0], op_ids[2]]);
+    }
 }
@@ -0,0 +1,339 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
+
+use hyperlane_core::H256;
+use prometheus::IntCounterVec;
+use tracing::debug;
+
+use crate::settings::{AppSchedulingConf, RateLimitConf};
+
+use super::op_queue::{AppQueues, QueueOperation};
+
+/// The scheduling group of operations that don't match any configured app
+const UNKNOWN_APP: &str = "Unknown";
+
+/// Picks which of the ready operations in an `OpQueue` get popped, so that a
+/// single high volume app can't monopolize the queue.
+///
+/// Operations are grouped by the first configured app whose matching list they
+/// match, falling back to their `app_context`. Groups share the queue using
+/// stride scheduling: each pop advances the group's pass by `1 / weight` and
+/// the group with the lowest pass goes next, so over time each group with
+/// ready operations gets a share proportional to its weight. Each group has its
+/// own queue in `AppQueues`, so only the head of each group is looked at and
+/// operations keep their priority order within a group.
+///
+/// If rate limits are enforced, a group that is out of tokens is skipped until
+/// its bucket refills.
+#[derive(Debug)]
+pub struct OpScheduler {
+    apps: Vec<AppSchedulingConf>,
+    enforce_rate_limits: bool,
+    throttled: IntCounterVec,
+    destination: String,
+    queue_metrics_label: String,
+    groups: HashMap<String, GroupState>,
+    /// Operations of each group that were counted as throttled and are still
+    /// waiting, so they aren't counted again on every pop
+    throttled_ops: HashMap<String, HashSet<H256>>,
+    /// The pass of the last scheduled group. Groups that were idle start from
+    /// here so they can't save up a backlog of turns.
+    virtual_time: f64,
+}
+
+#[derive(Debug)]
+struct GroupState {
+    pass: f64,
+    stride: f64,
+    bucket: Option<TokenBucket>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    max_per_second: f64,
+    capacity: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(conf: RateLimitConf, now: Instant) -> Self {
+        Self {
+            max_per_second: conf.max_per_second,
+            capacity: conf.burst as f64,
+            tokens: conf.burst as f64,
+            last_refill: now,
+        }
+    }
+
+    fn try_take(&mut self, now: Instant) -> bool {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.max_per_second).min(self.capacity);
+        self.last_refill = now;
+        if self.tokens >= 1. {
+            self.tokens -= 1.;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl OpScheduler {
+    /// - `enforce_rate_limits`: whether this queue consumes rate limit tokens.
+    ///   Only one queue per destination should, so each operation is only
+    ///   counted once.
+    pub fn new(
+        apps: Vec<AppSchedulingConf>,
+        enforce_rate_limits: bool,
+        throttled: IntCounterVec,
+        destination: String,
+        queue_metrics_label: String,
+    ) -> Self {
+        Self {
+            apps,
+            enforce_rate_limits,
+            throttled,
+            destination,
+            queue_metrics_label,
+            groups: HashMap::new(),
+            throttled_ops: HashMap::new(),
+            virtual_time: 0.,
+        }
+    }
+
+    /// Pop up to `limit` operations from the queues of `ready_apps`, which
+    /// must be groups whose highest priority operation is ready.
+    pub fn schedule(
+        &mut self,
+        queues: &mut AppQueues,
+        mut ready_apps: Vec<String>,
+        limit: usize,
+    ) -> Vec<QueueOperation> {
+        let now = Instant::now();
+
+        let virtual_time = self.virtual_time;
+        for group in &ready_apps {
+            let state = self.group_state(group, now);
+            state.pass = state.pass.max(virtual_time);
+        }
+
+        let mut selected = vec![];
+        while selected.len() < limit {
+            // Ties are broken by the priority of the groups' heads
+            let next = ready_apps
+                .iter()
+                .enumerate()
+                .filter_map(|(i, group)| Some((i, group, queues.peek_ready(group)?)))
+                .min_by(|(_, a, a_head), (_, b, b_head)| {
+                    self.groups[a.as_str()]
+                        .pass
+                        .total_cmp(&self.groups[b.as_str()].pass)
+                        .then_with(|| a_head.cmp(b_head))
+                })
+                .map(|(i, _, _)| i);
+            let Some(i) = next else {
+                break;
+            };
+            let group = ready_apps[i].clone();
+            let state = self.groups.get_mut(group.as_str()).unwrap();
+            if self.enforce_rate_limits {
+                if let Some(bucket) = &mut state.bucket {
+                    if !bucket.try_take(now) {
+                        debug!(app = %group, queue_label = %self.queue_metrics_label, "App is rate limited, holding back operations");
+                        let throttled: HashSet<H256> = queues
+                            .app_ops(&group)
+                            .filter(|op| {
+                                op.next_attempt_after()
+                                    .map_or(true, |next_attempt| next_attempt <= Instant::now())
+                            })
+                            .map(|op| op.id())
+                            .collect();
+                        let previously_throttled = self
+                            .throttled_ops
+                            .insert(group.clone(), throttled.clone())
+                            .unwrap_or_default();
+                        self.throttled
+                            .with_label_values(&[
+                                &self.destination,
+                                &self.queue_metrics_label,
+                                group.as_str(),
+                            ])
+                            .inc_by(throttled.difference(&previously_throttled).count() as u64);
+                        ready_apps.swap_remove(i);
+                        continue;
+                    }
+                }
+            }
+            self.virtual_time = state.pass;
+            state.pass += state.stride;
+            let op = queues.pop(&group).unwrap();
+            if let Some(throttled) = self.throttled_ops.get_mut(&group) {
+                throttled.remove(&op.id());
+            }
+            selected.push(op);
+        }
+        selected
+    }
+
+    /// The scheduling group of an operation
+    pub fn group_of(&self, op: &QueueOperation) -> String {
+        self.apps
+            .iter()
+            .find(|app| app.matching_list.op_matches(op, false))
+            .map(|app| app.name.clone())
+            .or_else(|| op.app_context())
+            .unwrap_or_else(|| UNKNOWN_APP.to_owned())
+    }
+
+    fn group_state(&mut self, group: &str, now: Instant) -> &mut GroupState {
+        let apps = &self.apps;
+        self.groups.entry(group.to_owned()).or_insert_with(|| {
+            let app = apps.iter().find(|app| app.name == group);
+            GroupState {
+                pass: 0.,
+                stride: 1. / app.map_or(1, |app| app.weight.max(1)) as f64,
+                bucket: app
+                    .and_then(|app| app.rate_limit)
+                    .map(|conf| TokenBucket::new(conf, now)),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{msg::op_queue::test::MockPendingOperation, settings::matching_list::MatchingList};
+    use hyperlane_core::{HyperlaneDomain, KnownHyperlaneDomain, H256};
+
+    fn app(
+        name: &str,
+        sender: H256,
+        weight: u32,
+        rate_limit: Option<RateLimitConf>,
+    ) -> AppSchedulingConf {
+        AppSchedulingConf {
+            name: name.to_owned(),
+            matching_list: serde_json::from_str::<MatchingList>(&format!(
+                r#"[{{"senderaddress": "{:?}"}}]"#,
+                sender
+            ))
+            .unwrap(),
+            weight,
+            rate_limit,
+        }
+    }
+
+    fn ops_from(sender: H256, count: usize) -> Vec<QueueOperation> {
+        let destination: HyperlaneDomain = KnownHyperlaneDomain::Ethereum.into();
+        (0..count)
+            .map(|_| {
+                Box::new(
+                    MockPendingOperation::new(0, destination.clone()).with_sender_address(sender),
+                ) as QueueOperation
+            })
+            .collect()
+    }
+
+    fn queues_of(scheduler: &OpScheduler, ops: Vec<QueueOperation>) -> AppQueues {
+        let mut queues = AppQueues::default();
+        for op in ops {
+            queues.push(scheduler.group_of(&op), op);
+        }
+        queues
+    }
+
+    fn dummy_throttled_metric() -> IntCounterVec {
+        IntCounterVec::new(
+            prometheus::Opts::new("throttled", "Throttled operations"),
+            &["remote", "queue_name", "app"],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_weighted_fair_share() {
+        let (spammer, other) = (H256::random(), H256::random());
+        let mut scheduler = OpScheduler::new(
+            vec![
+                app("spammer", spammer, 1, None),
+                app("other", other, 2, None),
+            ],
+            true,
+            dummy_throttled_metric(),
+            "ethereum".to_owned(),
+            "submit_queue".to_owned(),
+        );
+
+        // The spammer's operations are all ahead of the other app's in priority order
+        let mut queues = queues_of(
+            &scheduler,
+            [ops_from(spammer, 10), ops_from(other, 10)].concat(),
+        );
+        let ready_apps = queues.ready_apps();
+        let selected = scheduler.schedule(&mut queues, ready_apps, 6);
+
+        let from_other = selected
+            .iter()
+            .filter(|op| *op.sender_address() == other)
+            .count();
+        assert_eq!(from_other, 4);
+        assert_eq!(selected.len() - from_other, 2);
+        assert_eq!(queues.len(), 14);
+    }
+
+    #[test]
+    fn test_rate_limit_holds_back_operations() {
+        let (spammer, other) = (H256::random(), H256::random());
+        let throttled = dummy_throttled_metric();
+        let mut scheduler = OpScheduler::new(
+            vec![app(
+                "spammer",
+                spammer,
+                1,
+                Some(RateLimitConf {
+                    max_per_second: 0.001,
+                    burst: 2,
+                }),
+            )],
+            true,
+            throttled.clone(),
+            "ethereum".to_owned(),
+            "submit_queue".to_owned(),
+        );
+
+        let mut queues = queues_of(
+            &scheduler,
+            [ops_from(spammer, 5), ops_from(other, 1)].concat(),
+        );
+        let ready_apps = queues.ready_apps();
+        let selected = scheduler.schedule(&mut queues, ready_apps, 10);
+        let held_back = queues.len();
+
+        // Only the burst of the spammer's operations gets through, while the
+        // unlimited app isn't affected
+        assert_eq!(selected.len(), 3);
+        assert_eq!(
+            selected
+                .iter()
+                .filter(|op| *op.sender_address() == spammer)
+                .count(),
+            2
+        );
+        assert_eq!(held_back, 3);
+        let throttled = throttled.with_label_values(&["ethereum", "submit_queue", "spammer"]);
+        assert_eq!(throttled.get(), 3);
+
+        // Operations that are held back again aren't counted twice
+        let ready_apps = queues.ready_apps();
+        let selected = scheduler.schedule(&mut queues, ready_apps, 10);
+        assert!(selected.is_empty());
+        assert_eq!(queues.len(), 3);
+        assert_eq!(throttled.get(), 3);
+    }
+}
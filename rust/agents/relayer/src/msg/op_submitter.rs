@@ -3,7 +3,7 @@ use std::time::Duration;
 use derive_new::new;
 use futures::future::join_all;
 use futures_util::future::try_join_all;
-use prometheus::{IntCounter, IntGaugeVec};
+use prometheus::{IntCounter, IntCounterVec, IntGaugeVec};
 use tokio::spawn;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
@@ -14,13 +14,15 @@ use tracing::{info, warn};
 use hyperlane_base::CoreMetrics;
 use hyperlane_core::{
     BatchItem, ChainCommunicationError, ChainResult, HyperlaneDomain, HyperlaneDomainProtocol,
-    HyperlaneMessage, MpmcReceiver, TxOutcome,
+    HyperlaneMessage, MpmcReceiver, PendingMessageStage, TxOutcome, U256,
 };
 
 use crate::msg::pending_message::CONFIRM_DELAY;
 use crate::server::MessageRetryRequest;
+use crate::settings::AppSchedulingConf;
 
 use super::op_queue::{OpQueue, QueueOperation};
+use super::op_scheduler::OpScheduler;
 use super::pending_operation::*;
 
 /// SerialSubmitter accepts operations over a channel. It is responsible for
@@ -65,60 +67,123 @@ use super::pending_operation::*;
 /// before op_b, all else equal. This is because we expect applications may
 /// prefer this even if they do not strictly rely on it for correctness.
 ///
-/// 3. Be [work-conserving](https://en.wikipedia.org/wiki/Work-conserving_scheduler) w.r.t.
+/// 3. A single high volume app should not monopolize the execution slot. If
+/// apps are configured for scheduling, ready operations are shared between
+/// apps by weight, and apps can be rate limited.
+///
+/// 4. Be [work-conserving](https://en.wikipedia.org/wiki/Work-conserving_scheduler) w.r.t.
 /// the single execution slot, i.e. so long as there is at least one message
 /// eligible for submission, we should be working on it within reason. This
 /// must be balanced with the cost of making RPCs that will almost certainly
 /// fail and potentially block new messages from being sent immediately.
-#[derive(Debug, new)]
+#[derive(Debug)]
 pub struct SerialSubmitter {
     /// Domain this submitter delivers to.
     domain: HyperlaneDomain,
     /// Receiver for new messages to submit.
     rx: mpsc::UnboundedReceiver<QueueOperation>,
-    /// Receiver for retry requests.
-    retry_rx: MpmcReceiver<MessageRetryRequest>,
     /// Metrics for serial submitter.
     metrics: SerialSubmitterMetrics,
     /// Max batch size for submitting messages
     max_batch_size: u32,
+    /// Queue of operations waiting to be prepared
+    prepare_queue: OpQueue,
+    /// Queue of operations waiting to be submitted
+    submit_queue: OpQueue,
+    /// Queue of operations waiting to be confirmed
+    confirm_queue: OpQueue,
+    /// Whether operations are only prepared, and never actually submitted
+    dry_run: bool,
 }
 
 impl SerialSubmitter {
-    pub fn spawn(self) -> Instrumented<JoinHandle<()>> {
-        let span = info_span!("SerialSubmitter", destination=%self.domain);
-        spawn(async move { self.run().await }).instrument(span)
-    }
-
-    async fn run(self) {
-        let Self {
-            domain,
-            metrics,
-            rx: rx_prepare,
-            retry_rx,
-            max_batch_size,
-        } = self;
-        let prepare_queue = OpQueue::new(
+    pub fn new(
+        domain: HyperlaneDomain,
+        rx: mpsc::UnboundedReceiver<QueueOperation>,
+        retry_rx: MpmcReceiver<MessageRetryRequest>,
+        metrics: SerialSubmitterMetrics,
+        max_batch_size: u32,
+        app_scheduling: Vec<AppSchedulingConf>,
+        dry_run: bool,
+    ) -> Self {
+        let mut prepare_queue = OpQueue::new(
             metrics.submitter_queue_length.clone(),
             "prepare_queue".to_string(),
             retry_rx.clone(),
         );
-        let submit_queue = OpQueue::new(
+        let mut submit_queue = OpQueue::new(
             metrics.submitter_queue_length.clone(),
             "submit_queue".to_string(),
             retry_rx.clone(),
         );
+        if !app_scheduling.is_empty() {
+            // Both queues are shared fairly, but rate limits are only enforced on
+            // submission so each operation only consumes one token
+            prepare_queue = prepare_queue.with_scheduler(OpScheduler::new(
+                app_scheduling.clone(),
+                false,
+                metrics.ops_throttled.clone(),
+                domain.name().to_owned(),
+                "prepare_queue".to_string(),
+            ));
+            submit_queue = submit_queue.with_scheduler(OpScheduler::new(
+                app_scheduling,
+                true,
+                metrics.ops_throttled.clone(),
+                domain.name().to_owned(),
+                "submit_queue".to_string(),
+            ));
+        }
         let confirm_queue = OpQueue::new(
             metrics.submitter_queue_length.clone(),
             "confirm_queue".to_string(),
             retry_rx,
         );
+        Self {
+            domain,
+            rx,
+            metrics,
+            max_batch_size,
+            prepare_queue,
+            submit_queue,
+            confirm_queue,
+            dry_run,
+        }
+    }
+
+    /// The prepare, submit and confirm queues of this submitter, in that order.
+    pub fn op_queues(&self) -> Vec<OpQueue> {
+        vec![
+            self.prepare_queue.clone(),
+            self.submit_queue.clone(),
+            self.confirm_queue.clone(),
+        ]
+    }
+
+    pub fn spawn(self) -> Instrumented<JoinHandle<()>> {
+        let span = info_span!("SerialSubmitter", destination=%self.domain);
+        spawn(async move { self.run().await }).instrument(span)
+    }
+
+    async fn run(self) {
+        let Self {
+            domain,
+            metrics,
+            rx: rx_prepare,
+            max_batch_size,
+            prepare_queue,
+            submit_queue,
+            confirm_queue,
+            dry_run,
+        } = self;
 
         let tasks = [
             spawn(receive_task(
                 domain.clone(),
                 rx_prepare,
                 prepare_queue.clone(),
+                submit_queue.clone(),
+                confirm_queue.clone(),
             )),
             spawn(prepare_task(
                 domain.clone(),
@@ -130,6 +195,7 @@ impl SerialSubmitter {
             )),
             spawn(submit_task(
                 domain.clone(),
+                prepare_queue.clone(),
                 submit_queue,
                 confirm_queue.clone(),
                 max_batch_size,
@@ -140,6 +206,7 @@ impl SerialSubmitter {
                 prepare_queue,
                 confirm_queue,
                 max_batch_size,
+                dry_run,
                 metrics,
             )),
         ];
@@ -159,6 +226,8 @@ async fn receive_task(
     domain: HyperlaneDomain,
     mut rx: mpsc::UnboundedReceiver<QueueOperation>,
     prepare_queue: OpQueue,
+    submit_queue: OpQueue,
+    confirm_queue: OpQueue,
 ) {
     // Pull any messages sent to this submitter
     while let Some(op) = rx.recv().await {
@@ -166,7 +235,12 @@ async fn receive_task(
         // make sure things are getting wired up correctly; if this works in testing it
         // should also be valid in production.
         debug_assert_eq!(*op.destination_domain(), domain);
-        prepare_queue.push(op).await;
+        // Operations restored after a restart resume from the stage they were in
+        match op.stage() {
+            PendingMessageStage::Prepare => prepare_queue.push(op).await,
+            PendingMessageStage::Submit => submit_queue.push(op).await,
+            PendingMessageStage::Confirm => confirm_queue.push(op).await,
+        }
     }
 }
 
@@ -207,11 +281,12 @@ async fn prepare_task(
             })
             .count();
         let batch_len = batch.len();
-        for (op, prepare_result) in batch.into_iter().zip(res.into_iter()) {
+        for (mut op, prepare_result) in batch.into_iter().zip(res.into_iter()) {
             match prepare_result {
                 PendingOperationResult::Success => {
                     debug!(?op, "Operation prepared");
                     metrics.ops_prepared.inc();
+                    op.set_stage(PendingMessageStage::Submit);
                     // TODO: push multiple messages at once
                     submit_queue.push(op).await;
                 }
@@ -226,6 +301,7 @@ async fn prepare_task(
                     metrics.ops_dropped.inc();
                 }
                 PendingOperationResult::Confirm => {
+                    op.set_stage(PendingMessageStage::Confirm);
                     confirm_queue.push(op).await;
                 }
             }
@@ -240,6 +316,7 @@ async fn prepare_task(
 #[instrument(skip_all, fields(%domain))]
 async fn submit_task(
     domain: HyperlaneDomain,
+    prepare_queue: OpQueue,
     mut submit_queue: OpQueue,
     mut confirm_queue: OpQueue,
     max_batch_size: u32,
@@ -247,7 +324,8 @@ async fn submit_task(
 ) {
     let recv_limit = max_batch_size as usize;
     loop {
-        let mut batch = submit_queue.pop_many(recv_limit).await;
+        let batch = submit_queue.pop_many(recv_limit).await;
+        let mut batch = filter_submittable(batch, &prepare_queue, &confirm_queue, &metrics).await;
 
         match batch.len().cmp(&1) {
             std::cmp::Ordering::Less => {
@@ -268,6 +346,39 @@ async fn submit_task(
     }
 }
 
+/// Check that the operations may still be submitted, moving the ones that may
+/// not to the queue they belong in, and return the rest.
+async fn filter_submittable(
+    batch: Vec<QueueOperation>,
+    prepare_queue: &OpQueue,
+    confirm_queue: &OpQueue,
+    metrics: &SerialSubmitterMetrics,
+) -> Vec<QueueOperation> {
+    let mut submittable = Vec::with_capacity(batch.len());
+    for mut op in batch {
+        match op.check_submittable().await {
+            PendingOperationResult::Success => submittable.push(op),
+            PendingOperationResult::NotReady => {
+                op.set_stage(PendingMessageStage::Prepare);
+                prepare_queue.push(op).await;
+            }
+            PendingOperationResult::Reprepare => {
+                metrics.ops_failed.inc();
+                op.set_stage(PendingMessageStage::Prepare);
+                prepare_queue.push(op).await;
+            }
+            PendingOperationResult::Drop => {
+                metrics.ops_dropped.inc();
+            }
+            PendingOperationResult::Confirm => {
+                op.set_stage(PendingMessageStage::Confirm);
+                confirm_queue.push(op).await;
+            }
+        }
+    }
+    submittable
+}
+
 #[instrument(skip(confirm_queue, metrics), ret, level = "debug")]
 async fn submit_single_operation(
     mut op: QueueOperation,
@@ -277,6 +388,7 @@ async fn submit_single_operation(
     let destination = op.destination_domain().clone();
     op.submit().await;
     debug!(?op, "Operation submitted");
+    op.set_stage(PendingMessageStage::Confirm);
     op.set_next_attempt_after(CONFIRM_DELAY);
     confirm_queue.push(op).await;
     metrics.ops_submitted.inc();
@@ -298,6 +410,7 @@ async fn confirm_task(
     prepare_queue: OpQueue,
     mut confirm_queue: OpQueue,
     max_batch_size: u32,
+    dry_run: bool,
     metrics: SerialSubmitterMetrics,
 ) {
     let recv_limit = max_batch_size as usize;
@@ -317,6 +430,7 @@ async fn confirm_task(
                 domain.clone(),
                 prepare_queue.clone(),
                 confirm_queue.clone(),
+                dry_run,
                 metrics.clone(),
             )
         });
@@ -339,6 +453,7 @@ async fn confirm_operation(
     domain: HyperlaneDomain,
     prepare_queue: OpQueue,
     confirm_queue: OpQueue,
+    dry_run: bool,
     metrics: SerialSubmitterMetrics,
 ) -> PendingOperationResult {
     trace!(?op, "Confirming operation");
@@ -348,7 +463,10 @@ async fn confirm_operation(
     match operation_result {
         PendingOperationResult::Success => {
             debug!(?op, "Operation confirmed");
-            metrics.ops_confirmed.inc();
+            // Nothing was delivered in a dry run
+            if !dry_run {
+                metrics.ops_confirmed.inc();
+            }
         }
         PendingOperationResult::NotReady | PendingOperationResult::Confirm => {
             // TODO: push multiple messages at once
@@ -356,6 +474,7 @@ async fn confirm_operation(
         }
         PendingOperationResult::Reprepare => {
             metrics.ops_failed.inc();
+            op.set_stage(PendingMessageStage::Prepare);
             prepare_queue.push(op).await;
         }
         PendingOperationResult::Drop => {
@@ -373,6 +492,7 @@ pub struct SerialSubmitterMetrics {
     ops_confirmed: IntCounter,
     ops_failed: IntCounter,
     ops_dropped: IntCounter,
+    ops_throttled: IntCounterVec,
 }
 
 impl SerialSubmitterMetrics {
@@ -395,6 +515,7 @@ impl SerialSubmitterMetrics {
             ops_dropped: metrics
                 .operations_processed_count()
                 .with_label_values(&["dropped", destination]),
+            ops_throttled: metrics.operations_throttled_count(),
         }
     }
 }
@@ -410,11 +531,15 @@ impl OperationBatch {
     async fn submit(self, confirm_queue: &mut OpQueue, metrics: &SerialSubmitterMetrics) {
         match self.try_submit_as_batch(metrics).await {
             Ok(outcome) => {
-                // TODO: use the `tx_outcome` with the total gas expenditure
-                // We'll need to proportionally set `used_gas` based on the tx_outcome, so it can be updated in the confirm step
-                // which means we need to add a `set_transaction_outcome` fn to `PendingOperation`
                 info!(outcome=?outcome, batch_size=self.operations.len(), batch=?self.operations, "Submitted transaction batch");
-                for mut op in self.operations {
+                let gas_used_shares = split_evenly(outcome.gas_used, self.operations.len());
+                for (mut op, gas_used) in self.operations.into_iter().zip(gas_used_shares) {
+                    // Each operation is charged its share of the batch transaction
+                    op.set_submission_outcome(TxOutcome {
+                        gas_used,
+                        ..outcome.clone()
+                    });
+                    op.set_stage(PendingMessageStage::Confirm);
                     op.set_next_attempt_after(CONFIRM_DELAY);
                     confirm_queue.push(op).await;
                 }
@@ -457,3 +582,107 @@ impl OperationBatch {
         }
     }
 }
+
+/// Split `total` into `parts` shares that differ by at most one and add up to
+/// `total`.
+fn split_evenly(total: U256, parts: usize) -> Vec<U256> {
+    if parts == 0 {
+        return vec![];
+    }
+    let parts_u256 = U256::from(parts);
+    let (share, remainder) = (total / parts_u256, (total % parts_u256).as_usize());
+    (0..parts)
+        .map(|i| if i < remainder { share + 1 } else { share })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use hyperlane_base::db::{test_utils, HyperlaneRocksDB};
+    use hyperlane_core::MpmcChannel;
+    use hyperlane_test::mocks::MockMailboxContract;
+    use prometheus::Registry;
+
+    use super::*;
+    use crate::msg::{
+        op_queue::test::dummy_metrics_and_label,
+        pending_message::{test::dummy_prepared_message, MessageContext},
+        processor::test::{dummy_domain, dummy_message_context},
+    };
+
+    #[test]
+    fn test_split_evenly() {
+        assert_eq!(
+            split_evenly(U256::from(10), 3),
+            vec![U256::from(4), U256::from(3), U256::from(3)]
+        );
+        assert!(split_evenly(U256::from(10), 0).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_batch_is_never_submitted_or_confirmed() {
+        test_utils::run_test_db(|db| async move {
+            let origin_domain = dummy_domain(0, "dummy_origin_domain");
+            let destination_domain = dummy_domain(1, "dummy_destination_domain");
+            let db = HyperlaneRocksDB::new(&origin_domain, db);
+            // There are no `process` or `delivered` expectations, so sending or
+            // confirming a transaction panics
+            let mut mailbox = MockMailboxContract::default();
+            mailbox
+                .expect__domain()
+                .return_const(destination_domain.clone());
+            let ctx = Arc::new(MessageContext {
+                dry_run: true,
+                ..dummy_message_context(&origin_domain, &destination_domain, &db, mailbox)
+            });
+            let batch = (0..2)
+                .map(|nonce| {
+                    Box::new(dummy_prepared_message(
+                        &destination_domain,
+                        nonce,
+                        ctx.clone(),
+                    )) as QueueOperation
+                })
+                .collect();
+
+            let core_metrics = CoreMetrics::new("test", 9090, Registry::new()).unwrap();
+            let metrics = SerialSubmitterMetrics::new(&core_metrics, &destination_domain);
+            let (queue_metrics, queue_metrics_label) = dummy_metrics_and_label();
+            let retry_channel = MpmcChannel::new(1);
+            let prepare_queue = OpQueue::new(
+                queue_metrics.clone(),
+                queue_metrics_label.clone(),
+                retry_channel.receiver(),
+            );
+            let mut confirm_queue =
+                OpQueue::new(queue_metrics, queue_metrics_label, retry_channel.receiver());
+
+            // Batching fails in a dry run, so each message falls back to `submit`
+            OperationBatch::new(batch, destination_domain.clone())
+                .submit(&mut confirm_queue, &metrics)
+                .await;
+            assert_eq!(ctx.metrics.dry_run_messages.get(), 2);
+
+            let ops = confirm_queue.pop_many(2).await;
+            assert_eq!(ops.len(), 2);
+            for mut op in ops {
+                // Don't wait for the confirmation delay
+                op.reset_attempts();
+                let result = confirm_operation(
+                    op,
+                    destination_domain.clone(),
+                    prepare_queue.clone(),
+                    confirm_queue.clone(),
+                    true,
+                    metrics.clone(),
+                )
+                .await;
+                assert!(matches!(result, PendingOperationResult::Success));
+            }
+            assert_eq!(metrics.ops_confirmed.get(), 0);
+        })
+        .await;
+    }
+}
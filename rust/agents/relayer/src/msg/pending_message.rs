@@ -1,7 +1,7 @@
 use std::{
     fmt::{Debug, Formatter},
     sync::Arc,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use async_trait::async_trait;
@@ -9,16 +9,22 @@ use derive_new::new;
 use eyre::Result;
 use hyperlane_base::{db::HyperlaneRocksDB, CoreMetrics};
 use hyperlane_core::{
-    BatchItem, ChainCommunicationError, ChainResult, HyperlaneChain, HyperlaneDomain,
-    HyperlaneMessage, Mailbox, MessageSubmissionData, TryBatchAs, TxOutcome, H256, U256,
+    metrics::agent::u256_as_scaled_f64, utils::bytes_to_hex, BatchItem, ChainCommunicationError,
+    ChainResult, DeadLetteredMessage, FixedPointNumber, HyperlaneChain, HyperlaneDomain,
+    HyperlaneMessage, Mailbox, MessageSubmissionData, PendingMessageStage, PendingMessageState,
+    RecipientPauseKey, TryBatchAs, TxCostEstimate, TxOutcome, H256, U256,
 };
-use prometheus::{IntCounter, IntGauge};
+use prometheus::{Counter, IntCounter, IntGauge};
+use tokio::sync::RwLock;
 use tracing::{debug, error, info, instrument, trace, warn};
 
+use crate::settings::{reloader::ReloadableConf, DeadLetterConf};
+
 use super::{
     gas_payment::GasPaymentEnforcer,
     metadata::{BaseMetadataBuilder, MessageMetadataBuilder, MetadataBuilder},
     pending_operation::*,
+    profitability::ProfitabilityTracker,
 };
 
 pub const CONFIRM_DELAY: Duration = if cfg!(any(test, feature = "test-utils")) {
@@ -29,6 +35,14 @@ pub const CONFIRM_DELAY: Duration = if cfg!(any(test, feature = "test-utils")) {
     Duration::from_secs(60)
 };
 
+/// How long to wait before checking again whether an operator has resumed
+/// delivery of a paused message
+pub const OPERATOR_PAUSE_RECHECK_DELAY: Duration = Duration::from_secs(30);
+
+/// How long to wait before checking again whether a queued message that the
+/// reloaded message filters reject is allowed by them
+pub const FILTERED_RECHECK_DELAY: Duration = Duration::from_secs(30);
+
 /// The message context contains the links needed to submit a message. Each
 /// instance is for a unique origin -> destination pairing.
 pub struct MessageContext {
@@ -42,10 +56,18 @@ pub struct MessageContext {
     /// Used to determine if messages from the origin have made sufficient gas
     /// payments.
     pub origin_gas_payment_enforcer: Arc<GasPaymentEnforcer>,
+    /// Message filters, which can change while a message is queued.
+    pub reloadable_conf: Arc<RwLock<ReloadableConf>>,
     /// Hard limit on transaction gas when submitting a transaction to the
     /// destination.
     pub transaction_gas_limit: Option<U256>,
     pub metrics: MessageSubmissionMetrics,
+    /// Correlates the gas payments and expenditures of every route.
+    pub profitability: Arc<ProfitabilityTracker>,
+    /// When to give up on delivering a message.
+    pub dead_letter: DeadLetterConf,
+    /// If true, messages are prepared but never submitted.
+    pub dry_run: bool,
 }
 
 /// A message that the submitter can and should try to submit.
@@ -55,6 +77,8 @@ pub struct PendingMessage {
     ctx: Arc<MessageContext>,
     app_context: Option<String>,
     #[new(default)]
+    stage: PendingMessageStage,
+    #[new(default)]
     submitted: bool,
     #[new(default)]
     submission_data: Option<Box<MessageSubmissionData>>,
@@ -66,6 +90,15 @@ pub struct PendingMessage {
     next_attempt_after: Option<Instant>,
     #[new(default)]
     submission_outcome: Option<TxOutcome>,
+    #[new(default)]
+    last_error: Option<String>,
+    /// Unix timestamp in seconds of when the relayer first saw this message
+    #[new(default)]
+    first_seen_at: Option<u64>,
+    /// The cost estimate of the last successful prepare, reported in dry-run
+    /// mode
+    #[new(default)]
+    tx_cost_estimate: Option<TxCostEstimate>,
 }
 
 impl Debug for PendingMessage {
@@ -100,6 +133,10 @@ impl Eq for PendingMessage {}
 
 impl TryBatchAs<HyperlaneMessage> for PendingMessage {
     fn try_batch(&self) -> ChainResult<BatchItem<HyperlaneMessage>> {
+        if self.ctx.dry_run {
+            // Fall back to `submit`, which never sends a transaction in dry-run mode
+            return Err(ChainCommunicationError::BatchingFailed);
+        }
         match self.submission_data.as_ref() {
             None => {
                 warn!("Cannot batch message without submission data, returning BatchingFailed");
@@ -132,19 +169,69 @@ impl PendingOperation for PendingMessage {
         self.ctx.destination_mailbox.domain()
     }
 
+    fn sender_address(&self) -> &H256 {
+        &self.message.sender
+    }
+
+    fn recipient_address(&self) -> &H256 {
+        &self.message.recipient
+    }
+
+    fn nonce(&self) -> u32 {
+        self.message.nonce
+    }
+
+    fn body(&self) -> &[u8] {
+        &self.message.body
+    }
+
     fn app_context(&self) -> Option<String> {
         self.app_context.clone()
     }
 
+    fn stage(&self) -> PendingMessageStage {
+        self.stage
+    }
+
+    fn set_stage(&mut self, stage: PendingMessageStage) {
+        self.stage = stage;
+        self.persist_state();
+    }
+
     #[instrument(skip(self), ret, fields(id=?self.id()), level = "debug")]
     async fn prepare(&mut self) -> PendingOperationResult {
-        make_op_try!(|| self.on_reprepare());
+        make_op_try!(|reason| self.on_reprepare(reason));
 
         if !self.is_ready() {
             trace!("Message is not ready to be submitted yet");
             return PendingOperationResult::NotReady;
         }
 
+        // The filters may have been reloaded since the message was queued
+        if !self.is_allowed_by_filters().await {
+            return self.on_filtered();
+        }
+
+        // Operators can drop a message, or pause delivery to its destination or
+        // recipient, through the relayer server.
+        if op_try!(
+            self.is_dropped_by_operator(),
+            "checking if message was dropped by an operator"
+        ) {
+            info!("Dropping message because it was dropped by an operator");
+            return self.on_drop();
+        }
+        if op_try!(
+            self.is_paused_by_operator(),
+            "checking if message delivery was paused by an operator"
+        ) {
+            debug!("Message delivery is paused by an operator");
+            // Move the message to the back of the queue so it doesn't block
+            // other messages, without counting this as a retry
+            self.set_next_attempt_after(OPERATOR_PAUSE_RECHECK_DELAY);
+            return PendingOperationResult::NotReady;
+        }
+
         // If the message has already been processed, e.g. due to another relayer having
         // already processed, then mark it as already-processed, and move on to
         // the next tick.
@@ -162,6 +249,15 @@ impl PendingOperation for PendingMessage {
             return PendingOperationResult::Confirm;
         }
 
+        // Paused and already delivered messages are never dead-lettered
+        if let Some(reason) = self.exceeded_retry_budget() {
+            op_try!(
+                critical: self.dead_letter(reason),
+                "moving message to the dead-letter store"
+            );
+            return self.on_drop();
+        }
+
         let provider = self.ctx.destination_mailbox.provider();
 
         // We cannot deliver to an address that is not a contract so check and drop if it isn't.
@@ -174,7 +270,7 @@ impl PendingOperation for PendingMessage {
                 recipient=?self.message.recipient,
                 "Dropping message because recipient is not a contract"
             );
-            return PendingOperationResult::Drop;
+            return self.on_drop();
         }
 
         let ism_address = op_try!(
@@ -202,7 +298,7 @@ impl PendingOperation for PendingMessage {
             "building metadata"
         ) else {
             info!("Could not fetch metadata");
-            return self.on_reprepare();
+            return self.on_reprepare("Could not fetch metadata");
         };
 
         // Estimate transaction costs for the process call. If there are issues, it's
@@ -226,7 +322,7 @@ impl PendingOperation for PendingMessage {
             "checking if message meets gas payment requirement"
         ) else {
             warn!(?tx_cost_estimate, "Gas payment requirement not met yet");
-            return self.on_reprepare();
+            return self.on_reprepare("Gas payment requirement not met");
         };
 
         // Go ahead and attempt processing of message to destination chain.
@@ -239,7 +335,7 @@ impl PendingOperation for PendingMessage {
         if let Some(max_limit) = self.ctx.transaction_gas_limit {
             if gas_limit > max_limit {
                 info!("Message delivery estimated gas exceeds max gas limit");
-                return self.on_reprepare();
+                return self.on_reprepare("Estimated gas exceeds max gas limit");
             }
         }
 
@@ -247,6 +343,36 @@ impl PendingOperation for PendingMessage {
             metadata,
             gas_limit,
         }));
+        self.tx_cost_estimate = Some(tx_cost_estimate);
+        PendingOperationResult::Success
+    }
+
+    #[instrument(skip(self), ret, fields(id=?self.id()), level = "debug")]
+    async fn check_submittable(&mut self) -> PendingOperationResult {
+        make_op_try!(|reason| self.on_reprepare(reason));
+
+        // The filters may have been reloaded, or the operator may have dropped
+        // the message or paused its delivery, while it was waiting to be
+        // submitted
+        if !self.is_allowed_by_filters().await {
+            return self.on_filtered();
+        }
+        if op_try!(
+            self.is_dropped_by_operator(),
+            "checking if message was dropped by an operator"
+        ) {
+            info!("Dropping message because it was dropped by an operator");
+            return self.on_drop();
+        }
+        if op_try!(
+            self.is_paused_by_operator(),
+            "checking if message delivery was paused by an operator"
+        ) {
+            debug!("Message delivery is paused by an operator, not submitting");
+            self.submission_data = None;
+            self.set_next_attempt_after(OPERATOR_PAUSE_RECHECK_DELAY);
+            return PendingOperationResult::NotReady;
+        }
         PendingOperationResult::Success
     }
 
@@ -262,6 +388,11 @@ impl PendingOperation for PendingMessage {
             .take()
             .expect("Pending message must be prepared before it can be submitted");
 
+        if self.ctx.dry_run {
+            self.record_dry_run(&state);
+            return;
+        }
+
         // We use the estimated gas limit from the prior call to
         // `process_estimate_costs` to avoid a second gas estimation.
         let tx_outcome = self
@@ -275,6 +406,7 @@ impl PendingOperation for PendingMessage {
             }
             Err(e) => {
                 error!(error=?e, "Error when processing message");
+                self.last_error = Some(format!("Error when processing message: {e:?}"));
             }
         }
     }
@@ -284,10 +416,11 @@ impl PendingOperation for PendingMessage {
     }
 
     async fn confirm(&mut self) -> PendingOperationResult {
-        make_op_try!(|| {
+        make_op_try!(|reason| {
             // Provider error; just try again later
             // Note: this means that we are using `NotReady` for a retryable error case
             self.inc_attempts();
+            self.last_error = Some(reason);
             PendingOperationResult::NotReady
         });
 
@@ -295,6 +428,13 @@ impl PendingOperation for PendingMessage {
             return PendingOperationResult::NotReady;
         }
 
+        if self.ctx.dry_run {
+            // Nothing was submitted, so there is nothing to confirm
+            debug!("Dry run, skipping delivery confirmation");
+            self.clear_state();
+            return PendingOperationResult::Success;
+        }
+
         let is_delivered = op_try!(
             self.ctx
                 .destination_mailbox
@@ -307,27 +447,21 @@ impl PendingOperation for PendingMessage {
                 critical: self.record_message_process_success(),
                 "recording message process success"
             );
+            self.record_profitability(true).await;
+            self.clear_state();
             info!(
                 submission=?self.submission_outcome,
                 "Message successfully processed"
             );
             PendingOperationResult::Success
         } else {
-            if let Some(outcome) = &self.submission_outcome {
-                if let Err(e) = self
-                    .ctx
-                    .origin_gas_payment_enforcer
-                    .record_tx_outcome(&self.message, outcome.clone())
-                {
-                    error!(error=?e, "Error when recording tx outcome");
-                }
-            }
+            self.record_profitability(false).await;
             warn!(
                 tx_outcome=?self.submission_outcome,
                 message_id=?self.message.id(),
                 "Transaction attempting to process message either reverted or was reorged"
             );
-            self.on_reprepare()
+            self.on_reprepare("Transaction either reverted or was reorged")
         }
     }
 
@@ -337,12 +471,21 @@ impl PendingOperation for PendingMessage {
 
     fn set_next_attempt_after(&mut self, delay: Duration) {
         self.next_attempt_after = Some(Instant::now() + delay);
+        self.persist_state();
     }
 
     fn reset_attempts(&mut self) {
         self.reset_attempts();
     }
 
+    fn num_retries(&self) -> u32 {
+        self.num_retries
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.last_error.clone()
+    }
+
     #[cfg(test)]
     fn set_retries(&mut self, retries: u32) {
         self.set_retries(retries);
@@ -350,7 +493,10 @@ impl PendingOperation for PendingMessage {
 }
 
 impl PendingMessage {
-    /// Constructor that tries reading the retry count from the HyperlaneDB in order to recompute the `next_attempt_after`.
+    /// Constructor that tries reading the retry count and the state of the
+    /// message from the HyperlaneDB, so the message resumes from the stage it
+    /// was in and keeps its `next_attempt_after`. If there is no persisted
+    /// state, `next_attempt_after` is recomputed from the retry count.
     /// In case of failure, behaves like `Self::new(...)`.
     pub fn from_persisted_retries(
         message: HyperlaneMessage,
@@ -373,15 +519,197 @@ impl PendingMessage {
                 trace!(message_id = ?pm.message.id(), result = ?r, "Failed to read retry count from HyperlaneDB for message.")
             }
         }
+        match pm
+            .ctx
+            .origin_db
+            .retrieve_pending_message_first_seen_by_message_id(&pm.message.id())
+        {
+            Ok(Some(first_seen_at)) => pm.first_seen_at = Some(first_seen_at),
+            Ok(None) => {
+                let now = unix_timestamp_now();
+                pm.first_seen_at = Some(now);
+                if let Err(e) = pm
+                    .ctx
+                    .origin_db
+                    .store_pending_message_first_seen_by_message_id(&pm.message.id(), &now)
+                {
+                    warn!(message_id = ?pm.message.id(), err = %e, "Persisting when the message was first seen failed");
+                }
+            }
+            r => {
+                trace!(message_id = ?pm.message.id(), result = ?r, "Failed to read when the message was first seen from HyperlaneDB.")
+            }
+        }
+        match pm
+            .ctx
+            .origin_db
+            .retrieve_pending_message_state_by_message_id(&pm.message.id())
+        {
+            Ok(Some(state)) => pm.restore_state(state),
+            r => {
+                trace!(message_id = ?pm.message.id(), result = ?r, "Failed to read state from HyperlaneDB for message.")
+            }
+        }
         pm
     }
 
-    fn on_reprepare(&mut self) -> PendingOperationResult {
-        self.inc_attempts();
+    fn restore_state(&mut self, state: PendingMessageState) {
+        self.stage = match state.stage {
+            // A prepared message can only be submitted with its submission data
+            PendingMessageStage::Submit if state.submission_data.is_none() => {
+                PendingMessageStage::Prepare
+            }
+            stage => stage,
+        };
+        self.submission_data = state.submission_data.map(Box::new);
+        self.submission_outcome = state.submission_outcome;
+        self.submitted = state.submitted;
+        if let Some(next_attempt_after) = state.next_attempt_after {
+            let delay = Duration::from_secs(next_attempt_after).saturating_sub(
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default(),
+            );
+            self.next_attempt_after = Some(Instant::now() + delay);
+        }
+        debug!(message_id = ?self.message.id(), stage = ?self.stage, tx_outcome = ?self.submission_outcome, "Restored pending message state");
+    }
+
+    /// Persist the stage of the message and the data needed to resume from it,
+    /// so a restart doesn't submit the message again or reset its backoff.
+    fn persist_state(&self) {
+        let now = Instant::now();
+        let next_attempt_after = self.next_attempt_after.map(|next_attempt_after| {
+            (SystemTime::now() + next_attempt_after.saturating_duration_since(now))
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs()
+        });
+        let state = PendingMessageState {
+            stage: self.stage,
+            submission_data: self.submission_data.as_deref().cloned(),
+            submission_outcome: self.submission_outcome.clone(),
+            submitted: self.submitted,
+            next_attempt_after,
+        };
+        if let Err(e) = self
+            .ctx
+            .origin_db
+            .store_pending_message_state_by_message_id(&self.message.id(), &state)
+        {
+            warn!(message_id = ?self.message.id(), err = %e, "Persisting the state failed for message");
+        }
+    }
+
+    /// Forget the persisted state once the message won't be attempted again
+    fn clear_state(&self) {
+        if let Err(e) = self
+            .ctx
+            .origin_db
+            .remove_pending_message_state(&self.message.id())
+        {
+            warn!(message_id = ?self.message.id(), err = %e, "Removing the persisted state failed for message");
+        }
+    }
+
+    fn on_reprepare(&mut self, reason: impl Into<String>) -> PendingOperationResult {
+        // Reset before `inc_attempts` persists the state
         self.submitted = false;
+        self.inc_attempts();
+        self.last_error = Some(reason.into());
         PendingOperationResult::Reprepare
     }
 
+    /// Park a queued message that the reloaded message filters reject, so it
+    /// is delivered if a later reload allows it again
+    fn on_filtered(&mut self) -> PendingOperationResult {
+        info!("Message no longer passes the message filters, holding it back");
+        self.submission_data = None;
+        self.last_error = Some("Message no longer passes the message filters".to_owned());
+        // Not counted as a retry, so the message isn't dead-lettered while held back
+        self.set_next_attempt_after(FILTERED_RECHECK_DELAY);
+        PendingOperationResult::NotReady
+    }
+
+    fn on_drop(&self) -> PendingOperationResult {
+        self.clear_state();
+        PendingOperationResult::Drop
+    }
+
+    async fn is_allowed_by_filters(&self) -> bool {
+        self.ctx
+            .reloadable_conf
+            .read()
+            .await
+            .message_filters
+            .allows(&self.message)
+    }
+
+    fn is_dropped_by_operator(&self) -> Result<bool> {
+        Ok(self
+            .ctx
+            .origin_db
+            .retrieve_operator_dropped_by_message_id(&self.message.id())?
+            .unwrap_or(false))
+    }
+
+    fn is_paused_by_operator(&self) -> Result<bool> {
+        let db = &self.ctx.origin_db;
+        let destination_paused = db
+            .retrieve_operator_paused_by_destination_domain(&self.message.destination)?
+            .unwrap_or(false);
+        let recipient_paused = db
+            .retrieve_operator_paused_by_recipient(&RecipientPauseKey {
+                destination: self.message.destination,
+                recipient: self.message.recipient,
+            })?
+            .unwrap_or(false);
+        Ok(destination_paused || recipient_paused)
+    }
+
+    /// Returns why the message should be given up on, if it exceeded the max
+    /// retries or max age.
+    fn exceeded_retry_budget(&self) -> Option<String> {
+        let DeadLetterConf {
+            max_retries,
+            max_age,
+        } = self.ctx.dead_letter;
+        if let Some(max_retries) = max_retries.filter(|max| self.num_retries >= *max) {
+            return Some(format!("Exceeded max retries of {max_retries}"));
+        }
+        let age = self.first_seen_at.map(|first_seen_at| {
+            Duration::from_secs(unix_timestamp_now().saturating_sub(first_seen_at))
+        });
+        match (age, max_age) {
+            (Some(age), Some(max_age)) if age >= max_age => {
+                Some(format!("Exceeded max age of {}s", max_age.as_secs()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Move the message to the dead-letter store, from which operators can
+    /// requeue it through the relayer server.
+    fn dead_letter(&self, reason: String) -> Result<()> {
+        let reason = match &self.last_error {
+            Some(last_error) => format!("{reason}; last error: {last_error}"),
+            None => reason,
+        };
+        warn!(num_retries = self.num_retries, %reason, "Giving up on message, moving it to the dead-letter store");
+        self.ctx
+            .origin_db
+            .store_dead_lettered_message_by_message_id(
+                &self.message.id(),
+                &DeadLetteredMessage {
+                    num_retries: self.num_retries,
+                    dead_lettered_at: unix_timestamp_now(),
+                    reason,
+                },
+            )?;
+        self.ctx.metrics.messages_dead_lettered.inc();
+        Ok(())
+    }
+
     fn is_ready(&self) -> bool {
         self.next_attempt_after
             .map(|a| Instant::now() >= a)
@@ -404,10 +732,98 @@ impl PendingMessage {
         Ok(())
     }
 
+    /// Report the transaction that would have been submitted, instead of
+    /// submitting it.
+    fn record_dry_run(&self, state: &MessageSubmissionData) {
+        let estimated_cost = self
+            .tx_cost_estimate
+            .as_ref()
+            .map(|estimate| {
+                FixedPointNumber::try_from(state.gas_limit).and_then(|gas_limit| {
+                    TryInto::<U256>::try_into(gas_limit * estimate.gas_price.clone())
+                })
+            })
+            .transpose();
+        let estimated_cost = match estimated_cost {
+            Ok(estimated_cost) => estimated_cost,
+            Err(e) => {
+                warn!(error=?e, "Error when estimating the cost of the dry run");
+                None
+            }
+        };
+        info!(
+            metadata = %bytes_to_hex(&state.metadata),
+            gas_limit = ?state.gas_limit,
+            tx_cost_estimate = ?self.tx_cost_estimate,
+            ?estimated_cost,
+            "Dry run, not submitting message"
+        );
+
+        self.ctx.metrics.dry_run_messages.inc();
+        if let Some(estimated_cost) = estimated_cost {
+            self.ctx
+                .metrics
+                .dry_run_estimated_cost
+                .inc_by(u256_as_scaled_f64(
+                    estimated_cost,
+                    self.ctx.destination_mailbox.domain().domain_protocol(),
+                ));
+        }
+    }
+
+    /// Record the tokens spent by the submitted transaction, if we submitted
+    /// one, and the payment for the message if it was delivered.
+    async fn record_profitability(&mut self, is_delivered: bool) {
+        let origin = self.ctx.origin_db.domain();
+        let destination = self.ctx.destination_mailbox.domain();
+        let app_context = self.app_context.as_deref();
+        let enforcer = &self.ctx.origin_gas_payment_enforcer;
+        // Each submission is only recorded once, even if the message is
+        // retried afterwards
+        if let Some(outcome) = self.submission_outcome.take() {
+            if !is_delivered {
+                if let Err(e) = enforcer.record_tx_outcome(&self.message, outcome.clone()) {
+                    error!(error=?e, "Error when recording tx outcome");
+                }
+            }
+            match GasPaymentEnforcer::tokens_used(&outcome) {
+                Ok(tokens_used) => {
+                    let value = enforcer
+                        .value_of(&self.message, destination.id(), tokens_used)
+                        .await;
+                    self.ctx.profitability.record_expenditure(
+                        origin,
+                        destination,
+                        app_context,
+                        tokens_used,
+                        value,
+                    )
+                }
+                Err(e) => error!(error=?e, "Error when computing tokens used"),
+            }
+        }
+        if is_delivered {
+            match enforcer.total_payment(&self.message) {
+                Ok(payment) => {
+                    let value = enforcer.value_of(&self.message, origin.id(), payment).await;
+                    self.ctx.profitability.record_delivery(
+                        origin,
+                        destination,
+                        app_context,
+                        payment,
+                        value,
+                    )
+                }
+                Err(e) => error!(error=?e, "Error when retrieving gas payment"),
+            }
+        }
+    }
+
     fn reset_attempts(&mut self) {
         self.set_retries(0);
         self.next_attempt_after = None;
         self.last_attempted_at = Instant::now();
+        self.persist_state();
     }
 
     fn inc_attempts(&mut self) {
@@ -415,6 +831,7 @@ impl PendingMessage {
         self.last_attempted_at = Instant::now();
         self.next_attempt_after = PendingMessage::calculate_msg_backoff(self.num_retries)
             .map(|dur| self.last_attempted_at + dur);
+        self.persist_state();
     }
 
     fn set_retries(&mut self, retries: u32) {
@@ -452,11 +869,21 @@ impl PendingMessage {
     }
 }
 
+pub(crate) fn unix_timestamp_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
 #[derive(Debug)]
 pub struct MessageSubmissionMetrics {
     // Fields are public for testing purposes
     pub last_known_nonce: IntGauge,
     pub messages_processed: IntCounter,
+    pub messages_dead_lettered: IntCounter,
+    pub dry_run_messages: IntCounter,
+    pub dry_run_estimated_cost: Counter,
 }
 
 impl MessageSubmissionMetrics {
@@ -476,6 +903,15 @@ impl MessageSubmissionMetrics {
             messages_processed: metrics
                 .messages_processed_count()
                 .with_label_values(&[origin, destination]),
+            messages_dead_lettered: metrics
+                .messages_dead_lettered_count()
+                .with_label_values(&[origin, destination]),
+            dry_run_messages: metrics
+                .dry_run_messages_count()
+                .with_label_values(&[origin, destination]),
+            dry_run_estimated_cost: metrics
+                .dry_run_estimated_cost()
+                .with_label_values(&[origin, destination]),
         }
     }
 
@@ -487,3 +923,163 @@ impl MessageSubmissionMetrics {
             .set(std::cmp::max(self.last_known_nonce.get(), msg.nonce as i64));
     }
 }
+
+#[cfg(test)]
+pub(crate) mod test {
+    use hyperlane_base::db::test_utils;
+    use hyperlane_test::mocks::MockMailboxContract;
+
+    use super::*;
+    use crate::{
+        msg::processor::test::{dummy_domain, dummy_hyperlane_message, dummy_message_context},
+        settings::MessageFilters,
+    };
+
+    /// A message that is ready to be submitted with 100k gas at 1 gwei
+    pub(crate) fn dummy_prepared_message(
+        destination: &HyperlaneDomain,
+        nonce: u32,
+        ctx: Arc<MessageContext>,
+    ) -> PendingMessage {
+        let mut pm = PendingMessage::new(dummy_hyperlane_message(destination, nonce), ctx, None);
+        pm.submission_data = Some(Box::new(MessageSubmissionData {
+            metadata: vec![],
+            gas_limit: U256::from(100_000),
+        }));
+        pm.tx_cost_estimate = Some(TxCostEstimate {
+            gas_limit: U256::from(100_000),
+            gas_price: FixedPointNumber::from(1_000_000_000u64),
+            ..Default::default()
+        });
+        pm
+    }
+
+    #[tokio::test]
+    async fn test_state_is_restored_until_the_message_is_delivered() {
+        test_utils::run_test_db(|db| async move {
+            let origin_domain = dummy_domain(0, "dummy_origin_domain");
+            let destination_domain = dummy_domain(1, "dummy_destination_domain");
+            let db = HyperlaneRocksDB::new(&origin_domain, db);
+            let mut mailbox = MockMailboxContract::default();
+            mailbox
+                .expect__domain()
+                .return_const(destination_domain.clone());
+            mailbox.expect__delivered().returning(|_| Ok(true));
+            let ctx = Arc::new(dummy_message_context(
+                &origin_domain,
+                &destination_domain,
+                &db,
+                mailbox,
+            ));
+            let message = dummy_hyperlane_message(&destination_domain, 0);
+
+            // The message was already delivered, e.g. by another relayer
+            let mut pm = PendingMessage::from_persisted_retries(message.clone(), ctx.clone(), None);
+            pm.submitted = true;
+            pm.set_stage(PendingMessageStage::Confirm);
+
+            // Simulate a restart
+            let mut restored =
+                PendingMessage::from_persisted_retries(message.clone(), ctx.clone(), None);
+            assert!(restored.submitted);
+            assert_eq!(restored.stage, PendingMessageStage::Confirm);
+
+            // Once the message is confirmed, its state is forgotten
+            assert!(matches!(
+                restored.confirm().await,
+                PendingOperationResult::Success
+            ));
+            assert!(db
+                .retrieve_pending_message_state_by_message_id(&message.id())
+                .unwrap()
+                .is_none());
+            let fresh = PendingMessage::from_persisted_retries(message, ctx, None);
+            assert!(!fresh.submitted);
+            assert_eq!(fresh.stage, PendingMessageStage::Prepare);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_filtered_message_is_held_back_until_filters_allow_it() {
+        test_utils::run_test_db(|db| async move {
+            let origin_domain = dummy_domain(0, "dummy_origin_domain");
+            let destination_domain = dummy_domain(1, "dummy_destination_domain");
+            let db = HyperlaneRocksDB::new(&origin_domain, db);
+            let mut mailbox = MockMailboxContract::default();
+            mailbox.expect__delivered().returning(|_| Ok(true));
+            let ctx = Arc::new(dummy_message_context(
+                &origin_domain,
+                &destination_domain,
+                &db,
+                mailbox,
+            ));
+            let message = dummy_hyperlane_message(&destination_domain, 0);
+            let mut pm = PendingMessage::from_persisted_retries(message.clone(), ctx.clone(), None);
+
+            let blacklist =
+                serde_json::from_str(&format!(r#"[{{"senderaddress": "{:?}"}}]"#, message.sender))
+                    .unwrap();
+            *ctx.reloadable_conf.write().await = ReloadableConf::new(
+                MessageFilters {
+                    blacklist,
+                    ..Default::default()
+                },
+                vec![],
+            );
+            assert!(matches!(
+                pm.prepare().await,
+                PendingOperationResult::NotReady
+            ));
+            assert!(pm.next_attempt_after().is_some());
+            assert_eq!(pm.num_retries(), 0);
+
+            // A later reload allows the message again
+            *ctx.reloadable_conf.write().await = ReloadableConf::default();
+            pm.reset_attempts();
+            assert!(matches!(
+                pm.prepare().await,
+                PendingOperationResult::Confirm
+            ));
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_records_the_transaction_instead_of_submitting() {
+        test_utils::run_test_db(|db| async move {
+            let origin_domain = dummy_domain(0, "dummy_origin_domain");
+            let destination_domain = dummy_domain(1, "dummy_destination_domain");
+            let db = HyperlaneRocksDB::new(&origin_domain, db);
+            // There are no `process` or `delivered` expectations, so sending or
+            // confirming a transaction panics
+            let mut mailbox = MockMailboxContract::default();
+            mailbox
+                .expect__domain()
+                .return_const(destination_domain.clone());
+            let ctx = Arc::new(MessageContext {
+                dry_run: true,
+                ..dummy_message_context(&origin_domain, &destination_domain, &db, mailbox)
+            });
+            let mut pm = dummy_prepared_message(&destination_domain, 0, ctx.clone());
+
+            // Batches are submitted without calling `submit`
+            assert!(matches!(
+                pm.try_batch(),
+                Err(ChainCommunicationError::BatchingFailed)
+            ));
+
+            pm.submit().await;
+            assert!(pm.submission_outcome.is_none());
+            assert_eq!(ctx.metrics.dry_run_messages.get(), 1);
+            assert!((ctx.metrics.dry_run_estimated_cost.get() - 0.0001).abs() < 1e-12);
+
+            assert!(matches!(
+                pm.confirm().await,
+                PendingOperationResult::Success
+            ));
+            assert_eq!(ctx.metrics.messages_processed.get(), 0);
+        })
+        .await;
+    }
+}
@@ -5,7 +5,9 @@ use std::{
 };
 
 use async_trait::async_trait;
-use hyperlane_core::{HyperlaneDomain, HyperlaneMessage, TryBatchAs, TxOutcome, H256};
+use hyperlane_core::{
+    HyperlaneDomain, HyperlaneMessage, PendingMessageStage, TryBatchAs, TxOutcome, H256,
+};
 
 use super::op_queue::QueueOperation;
 
@@ -46,6 +48,18 @@ pub trait PendingOperation: Send + Sync + Debug + TryBatchAs<HyperlaneMessage> {
     /// The domain this operation will take place on.
     fn destination_domain(&self) -> &HyperlaneDomain;
 
+    /// The sender of this operation on the origin domain.
+    fn sender_address(&self) -> &H256;
+
+    /// The recipient of this operation on the destination domain.
+    fn recipient_address(&self) -> &H256;
+
+    /// The nonce of this operation on the origin domain.
+    fn nonce(&self) -> u32;
+
+    /// The body of the message this operation delivers.
+    fn body(&self) -> &[u8];
+
     /// Label to use for metrics granularity.
     fn app_context(&self) -> Option<String>;
 
@@ -56,11 +70,26 @@ pub trait PendingOperation: Send + Sync + Debug + TryBatchAs<HyperlaneMessage> {
         (destination, app_context)
     }
 
+    /// The stage of the submission pipeline this operation is in. Operations
+    /// restored after a restart are put back into the queue of this stage.
+    fn stage(&self) -> PendingMessageStage;
+
+    /// Record that this operation moved to another stage of the submission
+    /// pipeline.
+    fn set_stage(&mut self, stage: PendingMessageStage);
+
     /// Prepare to submit this operation. This will be called before every
     /// submission and will usually have a very short gap between it and the
     /// submit call.
     async fn prepare(&mut self) -> PendingOperationResult;
 
+    /// Check that this operation may still be submitted. Operations can wait
+    /// in the submit queue after being prepared, so conditions that can change
+    /// in the meantime are checked again right before submission. Returns
+    /// `Success` if the operation can be submitted, `NotReady` if it should go
+    /// back to the prepare queue and `Drop` if it should be dropped.
+    async fn check_submittable(&mut self) -> PendingOperationResult;
+
     /// Submit this operation to the blockchain
     async fn submit(&mut self);
 
@@ -85,6 +114,13 @@ pub trait PendingOperation: Send + Sync + Debug + TryBatchAs<HyperlaneMessage> {
     /// retried immediately.
     fn reset_attempts(&mut self);
 
+    /// Get the number of times this operation has been retried.
+    fn num_retries(&self) -> u32;
+
+    /// Get a description of the last error encountered while processing this
+    /// operation, if any.
+    fn last_error(&self) -> Option<String>;
+
     #[cfg(test)]
     /// Set the number of times this operation has been retried.
     fn set_retries(&mut self, retries: u32);
@@ -152,7 +188,8 @@ pub enum PendingOperationResult {
     Confirm,
 }
 
-/// create a `op_try!` macro for the `on_retry` handler.
+/// create a `op_try!` macro for the `on_retry` handler. The handler is passed a
+/// description of the error that caused the retry.
 macro_rules! make_op_try {
     ($on_retry:expr) => {
         /// Handle a result and either return early with retry or a critical failure on
@@ -164,7 +201,7 @@ macro_rules! make_op_try {
                                     Err(e) => {
                                         error!(error=?e, concat!("Critical error when ", $ctx));
                                         #[allow(clippy::redundant_closure_call)]
-                                        return $on_retry();
+                                        return $on_retry(format!(concat!("Critical error when ", $ctx, ": {:?}"), e));
                                     }
                                 }
                             };
@@ -174,7 +211,7 @@ macro_rules! make_op_try {
                                     Err(e) => {
                                         warn!(error=?e, concat!("Error when ", $ctx));
                                         #[allow(clippy::redundant_closure_call)]
-                                        return $on_retry();
+                                        return $on_retry(format!(concat!("Error when ", $ctx, ": {:?}"), e));
                                     }
                                 }
                             };
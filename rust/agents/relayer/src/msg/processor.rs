@@ -11,11 +11,14 @@ use eyre::Result;
 use hyperlane_base::{db::HyperlaneRocksDB, CoreMetrics};
 use hyperlane_core::{HyperlaneDomain, HyperlaneMessage};
 use prometheus::IntGauge;
-use tokio::sync::mpsc::UnboundedSender;
-use tracing::{debug, trace};
+use tokio::sync::{
+    mpsc::{UnboundedReceiver, UnboundedSender},
+    RwLock,
+};
+use tracing::{debug, info, trace};
 
 use super::{metadata::AppContextClassifier, op_queue::QueueOperation, pending_message::*};
-use crate::{processor::ProcessorExt, settings::matching_list::MatchingList};
+use crate::{processor::ProcessorExt, settings::reloader::ReloadableConf};
 
 /// Finds unprocessed messages from an origin and submits then through a channel
 /// for to the appropriate destination.
@@ -23,25 +26,27 @@ use crate::{processor::ProcessorExt, settings::matching_list::MatchingList};
 #[derive(new)]
 pub struct MessageProcessor {
     db: HyperlaneRocksDB,
-    whitelist: Arc<MatchingList>,
-    blacklist: Arc<MatchingList>,
+    /// Whitelist, blacklist and app contexts, which can be reloaded at runtime
+    reloadable_conf: Arc<RwLock<ReloadableConf>>,
     metrics: MessageProcessorMetrics,
     /// channel for each destination chain to send operations (i.e. message
     /// submissions) to
     send_channels: HashMap<u32, UnboundedSender<QueueOperation>>,
     /// Needed context to send a message for each destination chain
     destination_ctxs: HashMap<u32, Arc<MessageContext>>,
-    metric_app_contexts: Vec<(MatchingList, String)>,
     #[new(default)]
     message_nonce: u32,
+    /// Dead-lettered messages that an operator asked to retry
+    #[new(default)]
+    requeued: Option<UnboundedReceiver<HyperlaneMessage>>,
 }
 
 impl Debug for MessageProcessor {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "MessageProcessor {{ whitelist: {:?}, blacklist: {:?}, message_nonce: {:?} }}",
-            self.whitelist, self.blacklist, self.message_nonce
+            "MessageProcessor {{ reloadable_conf: {:?}, message_nonce: {:?} }}",
+            self.reloadable_conf, self.message_nonce
         )
     }
 }
@@ -56,6 +61,16 @@ impl ProcessorExt for MessageProcessor {
     /// One round of processing, extracted from infinite work loop for
     /// testing purposes.
     async fn tick(&mut self) -> Result<()> {
+        // Requeued messages were already checked when they were first processed
+        if let Some(msg) = self
+            .requeued
+            .as_mut()
+            .and_then(|requeued| requeued.try_recv().ok())
+        {
+            info!(?msg, "Requeueing dead-lettered message");
+            return self.send_to_submitter(msg).await;
+        }
+
         // Forever, scan HyperlaneRocksDB looking for new messages to send. When criteria are
         // satisfied or the message is disqualified, push the message onto
         // self.tx_msg and then continue the scan at the next highest
@@ -65,16 +80,43 @@ impl ProcessorExt for MessageProcessor {
             debug!(?msg, "Processor working on message");
             let destination = msg.destination;
 
-            // Skip if not whitelisted.
-            if !self.whitelist.msg_matches(&msg, true) {
-                debug!(?msg, whitelist=?self.whitelist, "Message not whitelisted, skipping");
+            {
+                let conf = self.reloadable_conf.read().await;
+                let message_filters = &conf.message_filters;
+
+                // Skip if not whitelisted.
+                if !message_filters.whitelist.msg_matches(&msg, true) {
+                    debug!(?msg, whitelist=?message_filters.whitelist, "Message not whitelisted, skipping");
+                    self.message_nonce += 1;
+                    return Ok(());
+                }
+
+                // Skip if the message is blacklisted
+                if message_filters.blacklist.msg_matches(&msg, false) {
+                    debug!(?msg, blacklist=?message_filters.blacklist, "Message blacklisted, skipping");
+                    self.message_nonce += 1;
+                    return Ok(());
+                }
+            }
+
+            // Skip if the message was dropped by an operator
+            if self
+                .db
+                .retrieve_operator_dropped_by_message_id(&msg.id())?
+                .unwrap_or(false)
+            {
+                debug!(?msg, "Message dropped by operator, skipping");
                 self.message_nonce += 1;
                 return Ok(());
             }
 
-            // Skip if the message is blacklisted
-            if self.blacklist.msg_matches(&msg, false) {
-                debug!(?msg, blacklist=?self.blacklist, "Message blacklisted, skipping");
+            // Skip if the message was given up on, until an operator requeues it
+            if self
+                .db
+                .retrieve_dead_lettered_message_by_message_id(&msg.id())?
+                .is_some()
+            {
+                debug!(?msg, "Message is dead-lettered, skipping");
                 self.message_nonce += 1;
                 return Ok(());
             }
@@ -93,19 +135,7 @@ impl ProcessorExt for MessageProcessor {
                 return Ok(());
             }
 
-            debug!(%msg, "Sending message to submitter");
-
-            let app_context_classifier =
-                AppContextClassifier::new(self.metric_app_contexts.clone());
-
-            let app_context = app_context_classifier.get_app_context(&msg).await?;
-            // Finally, build the submit arg and dispatch it to the submitter.
-            let pending_msg = PendingMessage::from_persisted_retries(
-                msg,
-                self.destination_ctxs[&destination].clone(),
-                app_context,
-            );
-            self.send_channels[&destination].send(Box::new(pending_msg) as QueueOperation)?;
+            self.send_to_submitter(msg).await?;
             self.message_nonce += 1;
         } else {
             tokio::time::sleep(Duration::from_secs(1)).await;
@@ -115,6 +145,29 @@ impl ProcessorExt for MessageProcessor {
 }
 
 impl MessageProcessor {
+    /// Receive dead-lettered messages to retry from this channel.
+    pub fn with_requeue_receiver(mut self, requeued: UnboundedReceiver<HyperlaneMessage>) -> Self {
+        self.requeued = Some(requeued);
+        self
+    }
+
+    async fn send_to_submitter(&self, msg: HyperlaneMessage) -> Result<()> {
+        debug!(%msg, "Sending message to submitter");
+
+        let app_context_classifier = AppContextClassifier::new(self.reloadable_conf.clone());
+
+        let app_context = app_context_classifier.get_app_context(&msg).await?;
+        let destination = msg.destination;
+        // Finally, build the submit arg and dispatch it to the submitter.
+        let pending_msg = PendingMessage::from_persisted_retries(
+            msg,
+            self.destination_ctxs[&destination].clone(),
+            app_context,
+        );
+        self.send_channels[&destination].send(Box::new(pending_msg) as QueueOperation)?;
+        Ok(())
+    }
+
     fn try_get_unprocessed_message(&mut self) -> Result<Option<HyperlaneMessage>> {
         loop {
             // First, see if we can find the message so we can update the gauge.
@@ -183,7 +236,7 @@ impl MessageProcessorMetrics {
 }
 
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
     use std::time::Instant;
 
     use crate::{
@@ -191,6 +244,7 @@ mod test {
         msg::{
             gas_payment::GasPaymentEnforcer,
             metadata::{BaseMetadataBuilder, IsmAwareAppContextClassifier},
+            profitability::ProfitabilityTracker,
         },
         processor::Processor,
     };
@@ -200,8 +254,9 @@ mod test {
         db::{test_utils, HyperlaneRocksDB},
         settings::{ChainConf, ChainConnectionConf, Settings},
     };
+    use hyperlane_core::PendingMessageStage;
     use hyperlane_test::mocks::{MockMailboxContract, MockValidatorAnnounceContract};
-    use prometheus::{IntCounter, Registry};
+    use prometheus::{Counter, IntCounter, Registry};
     use tokio::{
         sync::{
             mpsc::{self, UnboundedReceiver},
@@ -224,10 +279,14 @@ mod test {
         }
     }
 
-    fn dummy_submission_metrics() -> MessageSubmissionMetrics {
+    pub(crate) fn dummy_submission_metrics() -> MessageSubmissionMetrics {
         MessageSubmissionMetrics {
             last_known_nonce: IntGauge::new("last_known_nonce_gauge", "help string").unwrap(),
             messages_processed: IntCounter::new("message_processed_gauge", "help string").unwrap(),
+            messages_dead_lettered: IntCounter::new("messages_dead_lettered", "help string")
+                .unwrap(),
+            dry_run_messages: IntCounter::new("dry_run_messages", "help string").unwrap(),
+            dry_run_estimated_cost: Counter::new("dry_run_estimated_cost", "help string").unwrap(),
         }
     }
 
@@ -274,41 +333,69 @@ mod test {
             Arc::new(core_metrics),
             db.clone(),
             5,
-            IsmAwareAppContextClassifier::new(Arc::new(MockMailboxContract::default()), vec![]),
+            IsmAwareAppContextClassifier::new(
+                Arc::new(MockMailboxContract::default()),
+                Default::default(),
+            ),
+            Default::default(),
         )
     }
 
-    fn dummy_message_processor(
+    pub(crate) fn dummy_message_context(
         origin_domain: &HyperlaneDomain,
         destination_domain: &HyperlaneDomain,
         db: &HyperlaneRocksDB,
-    ) -> (MessageProcessor, UnboundedReceiver<QueueOperation>) {
+        destination_mailbox: MockMailboxContract,
+    ) -> MessageContext {
         let base_metadata_builder = dummy_metadata_builder(origin_domain, destination_domain, db);
-        let message_context = Arc::new(MessageContext {
-            destination_mailbox: Arc::new(MockMailboxContract::default()),
+        MessageContext {
+            destination_mailbox: Arc::new(destination_mailbox),
             origin_db: db.clone(),
             metadata_builder: Arc::new(base_metadata_builder),
-            origin_gas_payment_enforcer: Arc::new(GasPaymentEnforcer::new([], db.clone())),
+            origin_gas_payment_enforcer: Arc::new(GasPaymentEnforcer::new(
+                Default::default(),
+                db.clone(),
+            )),
+            reloadable_conf: Default::default(),
             transaction_gas_limit: Default::default(),
             metrics: dummy_submission_metrics(),
-        });
+            profitability: Arc::new(ProfitabilityTracker::new(
+                &CoreMetrics::new("dummy_relayer", 37582, Registry::new()).unwrap(),
+            )),
+            dead_letter: Default::default(),
+            dry_run: false,
+        }
+    }
+
+    fn dummy_message_processor(
+        origin_domain: &HyperlaneDomain,
+        destination_domain: &HyperlaneDomain,
+        db: &HyperlaneRocksDB,
+    ) -> (MessageProcessor, UnboundedReceiver<QueueOperation>) {
+        let message_context = Arc::new(dummy_message_context(
+            origin_domain,
+            destination_domain,
+            db,
+            MockMailboxContract::default(),
+        ));
 
         let (send_channel, receive_channel) = mpsc::unbounded_channel::<QueueOperation>();
         (
             MessageProcessor::new(
                 db.clone(),
                 Default::default(),
-                Default::default(),
                 dummy_processor_metrics(origin_domain.id()),
                 HashMap::from([(destination_domain.id(), send_channel)]),
                 HashMap::from([(destination_domain.id(), message_context)]),
-                vec![],
             ),
             receive_channel,
         )
     }
 
-    fn dummy_hyperlane_message(destination: &HyperlaneDomain, nonce: u32) -> HyperlaneMessage {
+    pub(crate) fn dummy_hyperlane_message(
+        destination: &HyperlaneDomain,
+        nonce: u32,
+    ) -> HyperlaneMessage {
         HyperlaneMessage {
             version: Default::default(),
             nonce,
@@ -329,7 +416,7 @@ mod test {
         }
     }
 
-    fn dummy_domain(domain_id: u32, name: &str) -> HyperlaneDomain {
+    pub(crate) fn dummy_domain(domain_id: u32, name: &str) -> HyperlaneDomain {
         let test_domain = HyperlaneDomain::new_test_domain(name);
         HyperlaneDomain::Unknown {
             domain_id,
@@ -440,4 +527,48 @@ mod test {
         })
         .await;
     }
+
+    #[tokio::test]
+    async fn test_pending_message_resumes_from_persisted_stage() {
+        test_utils::run_test_db(|db| async move {
+            let origin_domain = dummy_domain(0, "dummy_origin_domain");
+            let destination_domain = dummy_domain(1, "dummy_destination_domain");
+            let db = HyperlaneRocksDB::new(&origin_domain, db);
+            persist_retried_messages(&[0, 0], &db, &destination_domain);
+
+            let pending_messages =
+                get_first_n_operations_from_processor(&origin_domain, &destination_domain, &db, 2)
+                    .await;
+
+            // Submit the first message, like the `SerialSubmitter` does
+            let mut submitted = pending_messages.into_iter().next().unwrap();
+            submitted.set_stage(PendingMessageStage::Confirm);
+            submitted.set_next_attempt_after(Duration::from_secs(60));
+
+            // Simulate a restart
+            let pending_messages =
+                get_first_n_operations_from_processor(&origin_domain, &destination_domain, &db, 2)
+                    .await;
+
+            let restored = pending_messages
+                .iter()
+                .find(|pm| pm.id() == submitted.id())
+                .unwrap();
+            assert_eq!(restored.stage(), PendingMessageStage::Confirm);
+            let backoff = restored
+                .next_attempt_after()
+                .unwrap()
+                .duration_since(Instant::now())
+                .as_secs();
+            assert!((58..=60).contains(&backoff));
+
+            let untouched = pending_messages
+                .iter()
+                .find(|pm| pm.id() != submitted.id())
+                .unwrap();
+            assert_eq!(untouched.stage(), PendingMessageStage::Prepare);
+            assert!(untouched.next_attempt_after().is_none());
+        })
+        .await;
+    }
 }
@@ -0,0 +1,278 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use hyperlane_base::CoreMetrics;
+use hyperlane_core::{metrics::agent::u256_as_scaled_f64, HyperlaneDomain, U256};
+use prometheus::{CounterVec, GaugeVec};
+use serde::Serialize;
+
+/// The app context used for messages that don't belong to a known app
+const UNKNOWN_APP_CONTEXT: &str = "Unknown";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct RouteKey {
+    origin: HyperlaneDomain,
+    destination: HyperlaneDomain,
+    app_context: String,
+}
+
+#[derive(Debug, Clone)]
+struct RouteTotals {
+    /// Sum of the gas payments of delivered messages, in the smallest unit of
+    /// the origin's native token
+    paid: U256,
+    /// Sum of the tokens spent on every delivery attempt, in the smallest unit
+    /// of the destination's native token
+    spent: U256,
+    /// `paid` in the common currency, with each payment valued when it was
+    /// recorded. `None` if any of them couldn't be valued.
+    paid_value: Option<f64>,
+    /// `spent` in the common currency, with each expenditure valued when it
+    /// was recorded. `None` if any of them couldn't be valued.
+    spent_value: Option<f64>,
+    messages_delivered: u64,
+}
+
+impl Default for RouteTotals {
+    fn default() -> Self {
+        Self {
+            paid: U256::zero(),
+            spent: U256::zero(),
+            paid_value: Some(0.),
+            spent_value: Some(0.),
+            messages_delivered: 0,
+        }
+    }
+}
+
+/// The accumulated gas payments and expenditures of a route since the relayer
+/// started.
+///
+/// `paid` and `spent` are in whole native tokens of the origin and destination
+/// respectively. `net` is only known if both could be converted to the common
+/// currency, using the exchange rates of the gas payment policy of each message.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RouteProfitability {
+    pub origin: String,
+    pub destination: String,
+    pub app_context: String,
+    pub messages_delivered: u64,
+    pub paid: f64,
+    pub spent: f64,
+    /// `paid` in the common currency
+    pub paid_value: Option<f64>,
+    /// `spent` in the common currency
+    pub spent_value: Option<f64>,
+    /// `paid_value` minus `spent_value`
+    pub net: Option<f64>,
+    /// `paid` in the smallest unit of the origin's native token
+    pub paid_raw: U256,
+    /// `spent` in the smallest unit of the destination's native token
+    pub spent_raw: U256,
+}
+
+/// Correlates the gas payments made on the origin with the tokens spent on the
+/// destination delivering the messages they paid for, per route and app
+/// context.
+#[derive(Debug)]
+pub struct ProfitabilityTracker {
+    routes: Mutex<HashMap<RouteKey, RouteTotals>>,
+    paid: CounterVec,
+    spent: CounterVec,
+    net: GaugeVec,
+}
+
+impl ProfitabilityTracker {
+    pub fn new(metrics: &CoreMetrics) -> Self {
+        Self {
+            routes: Mutex::new(HashMap::new()),
+            paid: metrics.route_gas_paid(),
+            spent: metrics.route_gas_spent(),
+            net: metrics.route_gas_net(),
+        }
+    }
+
+    /// Record the tokens spent on an attempt to deliver a message, whether or
+    /// not it succeeded. `value` is their value in the common currency, if it
+    /// is known.
+    pub fn record_expenditure(
+        &self,
+        origin: &HyperlaneDomain,
+        destination: &HyperlaneDomain,
+        app_context: Option<&str>,
+        tokens_used: U256,
+        value: Option<f64>,
+    ) {
+        self.update(origin, destination, app_context, |totals| {
+            totals.spent = totals.spent.saturating_add(tokens_used);
+            totals.spent_value = add_value(totals.spent_value, value);
+        });
+    }
+
+    /// Record the total gas payment of a message once it has been delivered.
+    /// `value` is its value in the common currency, if it is known.
+    pub fn record_delivery(
+        &self,
+        origin: &HyperlaneDomain,
+        destination: &HyperlaneDomain,
+        app_context: Option<&str>,
+        payment: U256,
+        value: Option<f64>,
+    ) {
+        self.update(origin, destination, app_context, |totals| {
+            totals.paid = totals.paid.saturating_add(payment);
+            totals.paid_value = add_value(totals.paid_value, value);
+            totals.messages_delivered += 1;
+        });
+    }
+
+    fn update(
+        &self,
+        origin: &HyperlaneDomain,
+        destination: &HyperlaneDomain,
+        app_context: Option<&str>,
+        f: impl FnOnce(&mut RouteTotals),
+    ) {
+        let key = RouteKey {
+            origin: origin.clone(),
+            destination: destination.clone(),
+            app_context: app_context.unwrap_or(UNKNOWN_APP_CONTEXT).to_owned(),
+        };
+        let mut routes = self.routes.lock().unwrap();
+        let totals = routes.entry(key.clone()).or_default();
+        let before = Self::profitability(&key, totals);
+        f(totals);
+        let after = Self::profitability(&key, totals);
+
+        let labels = [origin.name(), destination.name(), key.app_context.as_str()];
+        self.paid
+            .with_label_values(&labels)
+            .inc_by((after.paid - before.paid).max(0.));
+        self.spent
+            .with_label_values(&labels)
+            .inc_by((after.spent - before.spent).max(0.));
+        match after.net {
+            Some(net) => self.net.with_label_values(&labels).set(net),
+            // Don't report a stale value once the net can't be computed anymore
+            None => {
+                let _ = self.net.remove_label_values(&labels);
+            }
+        }
+    }
+
+    /// The totals of every route that has seen a delivery attempt, optionally
+    /// restricted to an origin and destination domain.
+    pub fn report(&self, origin: Option<u32>, destination: Option<u32>) -> Vec<RouteProfitability> {
+        let routes = self.routes.lock().unwrap();
+        let mut report: Vec<_> = routes
+            .iter()
+            .filter(|(key, _)| origin.map_or(true, |origin| key.origin.id() == origin))
+            .filter(|(key, _)| {
+                destination.map_or(true, |destination| key.destination.id() == destination)
+            })
+            .map(|(key, totals)| Self::profitability(key, totals))
+            .collect();
+        report.sort_by(|a, b| {
+            (&a.origin, &a.destination, &a.app_context).cmp(&(
+                &b.origin,
+                &b.destination,
+                &b.app_context,
+            ))
+        });
+        report
+    }
+
+    fn profitability(key: &RouteKey, totals: &RouteTotals) -> RouteProfitability {
+        let paid = u256_as_scaled_f64(totals.paid, key.origin.domain_protocol());
+        let spent = u256_as_scaled_f64(totals.spent, key.destination.domain_protocol());
+        RouteProfitability {
+            origin: key.origin.name().to_owned(),
+            destination: key.destination.name().to_owned(),
+            app_context: key.app_context.clone(),
+            messages_delivered: totals.messages_delivered,
+            paid,
+            spent,
+            paid_value: totals.paid_value,
+            spent_value: totals.spent_value,
+            net: totals
+                .paid_value
+                .zip(totals.spent_value)
+                .map(|(paid, spent)| paid - spent),
+            paid_raw: totals.paid,
+            spent_raw: totals.spent,
+        }
+    }
+}
+
+fn add_value(total: Option<f64>, value: Option<f64>) -> Option<f64> {
+    total.zip(value).map(|(total, value)| total + value)
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::KnownHyperlaneDomain;
+    use prometheus::Registry;
+
+    use super::*;
+
+    fn tracker() -> ProfitabilityTracker {
+        let metrics = CoreMetrics::new("test", 9090, Registry::new()).unwrap();
+        ProfitabilityTracker::new(&metrics)
+    }
+
+    #[test]
+    fn test_payments_and_expenditures_are_correlated_per_route() {
+        let origin: HyperlaneDomain = KnownHyperlaneDomain::Arbitrum.into();
+        let destination: HyperlaneDomain = KnownHyperlaneDomain::Polygon.into();
+        let tracker = tracker();
+        let one_token = U256::exp10(18);
+
+        // A failed attempt followed by a successful one, where the origin's
+        // native token is worth twice the destination's
+        tracker.record_expenditure(&origin, &destination, Some("app"), one_token, Some(1.));
+        tracker.record_expenditure(&origin, &destination, Some("app"), one_token, Some(1.));
+        tracker.record_delivery(&origin, &destination, Some("app"), one_token * 3, Some(6.));
+        // A different app on the same route
+        tracker.record_expenditure(&origin, &destination, None, one_token, Some(1.));
+
+        let report = tracker.report(None, None);
+        assert_eq!(report.len(), 2);
+        let app = report.iter().find(|r| r.app_context == "app").unwrap();
+        assert_eq!(app.messages_delivered, 1);
+        assert_eq!(app.paid, 3.);
+        assert_eq!(app.spent, 2.);
+        assert_eq!(app.paid_value, Some(6.));
+        assert_eq!(app.spent_value, Some(2.));
+        assert_eq!(app.net, Some(4.));
+        assert_eq!(app.spent_raw, one_token * 2);
+        assert_eq!(
+            tracker
+                .net
+                .with_label_values(&["arbitrum", "polygon", "app"])
+                .get(),
+            4.
+        );
+
+        let unknown = report
+            .iter()
+            .find(|r| r.app_context == UNKNOWN_APP_CONTEXT)
+            .unwrap();
+        assert_eq!(unknown.net, Some(-1.));
+
+        assert!(tracker.report(Some(destination.id()), None).is_empty());
+    }
+
+    #[test]
+    fn test_net_is_unknown_without_exchange_rates() {
+        let origin: HyperlaneDomain = KnownHyperlaneDomain::Arbitrum.into();
+        let destination: HyperlaneDomain = KnownHyperlaneDomain::Polygon.into();
+        let tracker = tracker();
+
+        tracker.record_expenditure(&origin, &destination, None, U256::exp10(18), None);
+        tracker.record_delivery(&origin, &destination, None, U256::exp10(18), Some(2.));
+
+        let report = tracker.report(None, None);
+        assert_eq!(report[0].paid_value, Some(2.));
+        assert_eq!(report[0].spent_value, None);
+        assert_eq!(report[0].net, None);
+    }
+}
@@ -12,11 +12,11 @@ use hyperlane_base::{
     db::{HyperlaneRocksDB, DB},
     metrics::{AgentMetrics, MetricsUpdater},
     settings::ChainConf,
-    BaseAgent, ChainMetrics, ContractSyncMetrics, ContractSyncer, CoreMetrics, HyperlaneAgentCore,
+    BaseAgent, ChainMetrics, CheckpointCache, ContractSyncMetrics, ContractSyncer, CoreMetrics,
+    HyperlaneAgentCore,
 };
 use hyperlane_core::{
-    HyperlaneDomain, HyperlaneMessage, InterchainGasPayment, MerkleTreeInsertion, MpmcChannel,
-    MpmcReceiver, U256,
+    HyperlaneDomain, HyperlaneMessage, InterchainGasPayment, MerkleTreeInsertion, MpmcChannel, U256,
 };
 use tokio::{
     sync::{
@@ -36,9 +36,13 @@ use crate::{
         op_submitter::{SerialSubmitter, SerialSubmitterMetrics},
         pending_message::{MessageContext, MessageSubmissionMetrics},
         processor::{MessageProcessor, MessageProcessorMetrics},
+        profitability::ProfitabilityTracker,
     },
     server::{self as relayer_server, MessageRetryRequest},
-    settings::{matching_list::MatchingList, RelayerSettings},
+    settings::{
+        reloader::{ReloadableConf, SettingsReloader},
+        AppSchedulingConf, RelayerSettings,
+    },
 };
 use crate::{
     merkle_tree::processor::{MerkleTreeProcessor, MerkleTreeProcessorMetrics},
@@ -68,12 +72,17 @@ pub struct Relayer {
     prover_syncs: HashMap<HyperlaneDomain, Arc<RwLock<MerkleTreeBuilder>>>,
     merkle_tree_hook_syncs: HashMap<HyperlaneDomain, Arc<dyn ContractSyncer<MerkleTreeInsertion>>>,
     dbs: HashMap<HyperlaneDomain, HyperlaneRocksDB>,
-    whitelist: Arc<MatchingList>,
-    blacklist: Arc<MatchingList>,
+    /// Message filters and gas payment policies, shared with the message
+    /// processors and contexts so they can be reloaded at runtime
+    reloadable_conf: Arc<RwLock<ReloadableConf>>,
+    /// Gas paid and spent per route, shared by every message context
+    profitability: Arc<ProfitabilityTracker>,
     transaction_gas_limit: Option<U256>,
     skip_transaction_gas_limit_for: HashSet<u32>,
     allow_local_checkpoint_syncers: bool,
-    metric_app_contexts: Vec<(MatchingList, String)>,
+    app_scheduling: Vec<AppSchedulingConf>,
+    /// Prepare messages without ever submitting them
+    dry_run: bool,
     core_metrics: Arc<CoreMetrics>,
     // TODO: decide whether to consolidate `agent_metrics` and `chain_metrics` into a single struct
     // or move them in `core_metrics`, like the validator metrics
@@ -85,11 +94,10 @@ impl Debug for Relayer {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Relayer {{ origin_chains: {:?}, destination_chains: {:?}, whitelist: {:?}, blacklist: {:?}, transaction_gas_limit: {:?}, skip_transaction_gas_limit_for: {:?}, allow_local_checkpoint_syncers: {:?} }}",
+            "Relayer {{ origin_chains: {:?}, destination_chains: {:?}, reloadable_conf: {:?}, transaction_gas_limit: {:?}, skip_transaction_gas_limit_for: {:?}, allow_local_checkpoint_syncers: {:?} }}",
             self.origin_chains,
             self.destination_chains,
-            self.whitelist,
-            self.blacklist,
+            self.reloadable_conf,
             self.transaction_gas_limit,
             self.skip_transaction_gas_limit_for,
             self.allow_local_checkpoint_syncers
@@ -172,19 +180,23 @@ impl BaseAgent for Relayer {
             .map(|(k, v)| (k, v as _))
             .collect();
 
-        let whitelist = Arc::new(settings.whitelist);
-        let blacklist = Arc::new(settings.blacklist);
+        let message_filters = settings.message_filters();
         let skip_transaction_gas_limit_for = settings.skip_transaction_gas_limit_for;
         let transaction_gas_limit = settings.transaction_gas_limit;
 
         info!(
-            %whitelist,
-            %blacklist,
+            whitelist = %message_filters.whitelist,
+            blacklist = %message_filters.blacklist,
             ?transaction_gas_limit,
             ?skip_transaction_gas_limit_for,
             "Whitelist configuration"
         );
 
+        let reloadable_conf = Arc::new(RwLock::new(ReloadableConf::new(
+            message_filters,
+            settings.gas_payment_enforcement.clone(),
+        )));
+
         // provers by origin chain
         let prover_syncs = settings
             .origin_chains
@@ -198,6 +210,10 @@ impl BaseAgent for Relayer {
             .collect::<HashMap<_, _>>();
 
         info!(gas_enforcement_policies=?settings.gas_payment_enforcement, "Gas enforcement configuration");
+        info!(app_scheduling=?settings.app_scheduling, "App scheduling configuration");
+        if settings.dry_run {
+            warn!("Running in dry-run mode, messages will be prepared but never submitted");
+        }
 
         // need one of these per origin chain due to the database scoping even though
         // the config itself is the same
@@ -208,13 +224,22 @@ impl BaseAgent for Relayer {
                 (
                     domain.clone(),
                     Arc::new(GasPaymentEnforcer::new(
-                        settings.gas_payment_enforcement.clone(),
+                        reloadable_conf.clone(),
                         dbs.get(domain).unwrap().clone(),
                     )),
                 )
             })
             .collect();
 
+        let profitability = Arc::new(ProfitabilityTracker::new(&core_metrics));
+
+        // validator checkpoints are cached per origin, and shared by every destination
+        let checkpoint_caches: HashMap<_, _> = settings
+            .origin_chains
+            .iter()
+            .map(|origin| (origin.clone(), Arc::new(CheckpointCache::default())))
+            .collect();
+
         let mut msg_ctxs = HashMap::new();
         let mut destination_chains = HashMap::new();
         for destination in &settings.destination_chains {
@@ -240,8 +265,9 @@ impl BaseAgent for Relayer {
                     5,
                     IsmAwareAppContextClassifier::new(
                         mailboxes[destination].clone(),
-                        settings.metric_app_contexts.clone(),
+                        reloadable_conf.clone(),
                     ),
+                    checkpoint_caches[origin].clone(),
                 );
 
                 msg_ctxs.insert(
@@ -254,8 +280,12 @@ impl BaseAgent for Relayer {
                         origin_db: dbs.get(origin).unwrap().clone(),
                         metadata_builder: Arc::new(metadata_builder),
                         origin_gas_payment_enforcer: gas_payment_enforcers[origin].clone(),
+                        reloadable_conf: reloadable_conf.clone(),
                         transaction_gas_limit,
                         metrics: MessageSubmissionMetrics::new(&core_metrics, origin, destination),
+                        profitability: profitability.clone(),
+                        dead_letter: settings.dead_letter,
+                        dry_run: settings.dry_run,
                     }),
                 );
             }
@@ -271,12 +301,13 @@ impl BaseAgent for Relayer {
             interchain_gas_payment_syncs,
             prover_syncs,
             merkle_tree_hook_syncs,
-            whitelist,
-            blacklist,
+            reloadable_conf,
+            profitability,
             transaction_gas_limit,
             skip_transaction_gas_limit_for,
             allow_local_checkpoint_syncers: settings.allow_local_checkpoint_syncers,
-            metric_app_contexts: settings.metric_app_contexts,
+            app_scheduling: settings.app_scheduling,
+            dry_run: settings.dry_run,
             core_metrics,
             agent_metrics,
             chain_metrics,
@@ -287,39 +318,34 @@ impl BaseAgent for Relayer {
     async fn run(self) {
         let mut tasks = vec![];
 
-        // run server
         let mpmc_channel = MpmcChannel::<MessageRetryRequest>::new(ENDPOINT_MESSAGES_QUEUE_SIZE);
-        let custom_routes = relayer_server::routes(mpmc_channel.sender());
 
-        let server = self
-            .core
-            .settings
-            .server(self.core_metrics.clone())
-            .expect("Failed to create server");
-        let server_task = server
-            .run_with_custom_routes(custom_routes)
-            .instrument(info_span!("Relayer server"));
-        tasks.push(server_task);
-
-        // send channels by destination chain
+        // send channels and op queues by destination chain
         let mut send_channels = HashMap::with_capacity(self.destination_chains.len());
+        let mut op_queues = HashMap::with_capacity(self.destination_chains.len());
         for (dest_domain, dest_conf) in &self.destination_chains {
             let (send_channel, receive_channel) = mpsc::unbounded_channel::<QueueOperation>();
             send_channels.insert(dest_domain.id(), send_channel);
 
-            tasks.push(
-                self.run_destination_submitter(
-                    dest_domain,
-                    receive_channel,
-                    mpmc_channel.receiver(),
-                    // Default to submitting one message at a time if there is no batch config
-                    self.core.settings.chains[dest_domain.name()]
-                        .connection
-                        .operation_batch_config()
-                        .map(|c| c.max_batch_size)
-                        .unwrap_or(1),
-                ),
+            let serial_submitter = SerialSubmitter::new(
+                dest_domain.clone(),
+                receive_channel,
+                mpmc_channel.receiver(),
+                SerialSubmitterMetrics::new(&self.core.metrics, dest_domain),
+                // Default to submitting one message at a time if there is no batch config.
+                // Nothing is submitted in dry-run mode, so there is nothing to batch either.
+                self.core.settings.chains[dest_domain.name()]
+                    .connection
+                    .operation_batch_config()
+                    .filter(|_| !self.dry_run)
+                    .map(|c| c.max_batch_size)
+                    .unwrap_or(1),
+                self.app_scheduling.clone(),
+                self.dry_run,
             );
+            op_queues.insert(dest_domain.id(), serial_submitter.op_queues());
+
+            tasks.push(self.run_destination_submitter(dest_domain, serial_submitter));
 
             let metrics_updater = MetricsUpdater::new(
                 dest_conf,
@@ -333,6 +359,40 @@ impl BaseAgent for Relayer {
             tasks.push(metrics_updater.spawn());
         }
 
+        let settings_reloader = SettingsReloader::new(self.reloadable_conf.clone());
+        #[cfg(unix)]
+        tasks.push(settings_reloader.clone().spawn_sighup_listener());
+
+        // channels for requeueing dead-lettered messages by origin chain
+        let (requeue_senders, mut requeue_receivers): (HashMap<_, _>, HashMap<_, _>) = self
+            .origin_chains
+            .iter()
+            .map(|origin| {
+                let (tx, rx) = mpsc::unbounded_channel::<HyperlaneMessage>();
+                ((origin.id(), tx), (origin.id(), rx))
+            })
+            .unzip();
+
+        // run server
+        let custom_routes = relayer_server::routes(
+            mpmc_channel.sender(),
+            op_queues,
+            self.dbs.values().cloned().collect(),
+            settings_reloader,
+            self.profitability.clone(),
+            requeue_senders,
+        );
+
+        let server = self
+            .core
+            .settings
+            .server(self.core_metrics.clone())
+            .expect("Failed to create server");
+        let server_task = server
+            .run_with_custom_routes(custom_routes)
+            .instrument(info_span!("Relayer server"));
+        tasks.push(server_task);
+
         for origin in &self.origin_chains {
             tasks.push(self.run_message_sync(origin).await);
             tasks.push(self.run_interchain_gas_payment_sync(origin).await);
@@ -341,7 +401,11 @@ impl BaseAgent for Relayer {
 
         // each message process attempts to send messages from a chain
         for origin in &self.origin_chains {
-            tasks.push(self.run_message_processor(origin, send_channels.clone()));
+            tasks.push(self.run_message_processor(
+                origin,
+                send_channels.clone(),
+                requeue_receivers.remove(&origin.id()).unwrap(),
+            ));
             tasks.push(self.run_merkle_tree_processor(origin));
         }
 
@@ -398,6 +462,7 @@ impl Relayer {
         &self,
         origin: &HyperlaneDomain,
         send_channels: HashMap<u32, UnboundedSender<QueueOperation>>,
+        requeue_receiver: UnboundedReceiver<HyperlaneMessage>,
     ) -> Instrumented<JoinHandle<()>> {
         let metrics = MessageProcessorMetrics::new(
             &self.core.metrics,
@@ -422,13 +487,12 @@ impl Relayer {
 
         let message_processor = MessageProcessor::new(
             self.dbs.get(origin).unwrap().clone(),
-            self.whitelist.clone(),
-            self.blacklist.clone(),
+            self.reloadable_conf.clone(),
             metrics,
             send_channels,
             destination_ctxs,
-            self.metric_app_contexts.clone(),
-        );
+        )
+        .with_requeue_receiver(requeue_receiver);
 
         let span = info_span!("MessageProcessor", origin=%message_processor.domain());
         let processor = Processor::new(Box::new(message_processor));
@@ -449,22 +513,12 @@ impl Relayer {
         processor.spawn().instrument(span)
     }
 
-    #[allow(clippy::too_many_arguments)]
-    #[tracing::instrument(skip(self, receiver))]
+    #[tracing::instrument(skip(self, serial_submitter))]
     fn run_destination_submitter(
         &self,
         destination: &HyperlaneDomain,
-        receiver: UnboundedReceiver<QueueOperation>,
-        retry_receiver_channel: MpmcReceiver<MessageRetryRequest>,
-        batch_size: u32,
+        serial_submitter: SerialSubmitter,
     ) -> Instrumented<JoinHandle<()>> {
-        let serial_submitter = SerialSubmitter::new(
-            destination.clone(),
-            receiver,
-            retry_receiver_channel,
-            SerialSubmitterMetrics::new(&self.core.metrics, destination),
-            batch_size,
-        );
         let span = info_span!("SerialSubmitter", destination=%destination);
         let destination = destination.clone();
         tokio::spawn(async move {
@@ -0,0 +1,247 @@
+use std::{collections::HashMap, str::FromStr};
+
+use axum::{
+    extract::{Query, State},
+    routing, Json, Router,
+};
+use derive_new::new;
+use eyre::{eyre, Result};
+use hyperlane_base::db::HyperlaneRocksDB;
+use hyperlane_core::{HyperlaneMessage, PendingMessageState, H256};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::info;
+
+use crate::msg::pending_message::unix_timestamp_now;
+
+const DEAD_LETTER_API_BASE: &str = "/dead_letter";
+
+/// Lists the messages the relayer gave up on after exceeding their retry
+/// budget, and lets operators requeue them for delivery.
+#[derive(new, Clone)]
+pub struct DeadLetterApi {
+    /// The databases of every origin chain
+    dbs: Vec<HyperlaneRocksDB>,
+    /// The requeue channel of each origin's message processor, keyed by
+    /// origin domain id
+    requeue_senders: HashMap<u32, UnboundedSender<HyperlaneMessage>>,
+}
+
+#[derive(Deserialize)]
+struct RawListRequest {
+    origin_domain: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct RawRequeueRequest {
+    message_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeadLetteredMessageEntry {
+    pub message_id: H256,
+    pub origin: u32,
+    pub destination: u32,
+    pub nonce: u32,
+    pub sender: H256,
+    pub recipient: H256,
+    pub num_retries: u32,
+    pub dead_lettered_at: u64,
+    pub reason: String,
+}
+
+fn list_entries(db: &HyperlaneRocksDB) -> Result<Vec<DeadLetteredMessageEntry>> {
+    let mut entries = vec![];
+    for (message_id, dead_lettered) in db.retrieve_dead_lettered_messages()? {
+        let Some(message) = db.retrieve_message_by_message_id(&message_id)? else {
+            continue;
+        };
+        entries.push(DeadLetteredMessageEntry {
+            message_id,
+            origin: message.origin,
+            destination: message.destination,
+            nonce: message.nonce,
+            sender: message.sender,
+            recipient: message.recipient,
+            num_retries: dead_lettered.num_retries,
+            dead_lettered_at: dead_lettered.dead_lettered_at,
+            reason: dead_lettered.reason,
+        });
+    }
+    Ok(entries)
+}
+
+async fn list(
+    State(api): State<DeadLetterApi>,
+    Query(request): Query<RawListRequest>,
+) -> Result<Json<Vec<DeadLetteredMessageEntry>>, String> {
+    let mut entries = vec![];
+    for db in &api.dbs {
+        if request
+            .origin_domain
+            .map_or(false, |origin| origin != db.domain().id())
+        {
+            continue;
+        }
+        // Technically it's bad practice to print the error message to the user, but
+        // this endpoint is for debugging purposes only.
+        entries.extend(
+            list_entries(db)
+                .map_err(|err| format!("Failed to list dead-lettered messages: {}", err))?,
+        );
+    }
+    entries.sort_by_key(|entry| (entry.origin, entry.nonce));
+    Ok(Json(entries))
+}
+
+fn requeue_message(api: &DeadLetterApi, message_id: H256) -> Result<bool> {
+    for db in &api.dbs {
+        if db
+            .retrieve_dead_lettered_message_by_message_id(&message_id)?
+            .is_none()
+        {
+            continue;
+        }
+        let message = db
+            .retrieve_message_by_message_id(&message_id)?
+            .ok_or_else(|| eyre!("Dead-lettered message not found in the database"))?;
+        let sender = api
+            .requeue_senders
+            .get(&message.origin)
+            .ok_or_else(|| eyre!("No message processor for origin {}", message.origin))?;
+
+        // Give the message a fresh retry budget
+        db.store_pending_message_retry_count_by_message_id(&message_id, &0)?;
+        db.store_pending_message_first_seen_by_message_id(&message_id, &unix_timestamp_now())?;
+        db.store_pending_message_state_by_message_id(&message_id, &PendingMessageState::default())?;
+        db.remove_dead_lettered_message(&message_id)?;
+
+        sender.send(message)?;
+        info!(?message_id, "Requeued dead-lettered message");
+        return Ok(true);
+    }
+    Ok(false)
+}
+
+async fn requeue(
+    State(api): State<DeadLetterApi>,
+    Query(request): Query<RawRequeueRequest>,
+) -> String {
+    let message_id = match H256::from_str(&request.message_id) {
+        Ok(message_id) => message_id,
+        Err(err) => {
+            return format!("Failed to parse message id: {}", err);
+        }
+    };
+
+    match requeue_message(&api, message_id) {
+        Ok(true) => "Requeued message".to_string(),
+        Ok(false) => "Message is not dead-lettered".to_string(),
+        Err(err) => format!("Failed to requeue message: {}", err),
+    }
+}
+
+impl DeadLetterApi {
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/list", routing::get(list))
+            .route("/requeue", routing::get(requeue))
+            .with_state(self.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (DEAD_LETTER_API_BASE, self.router())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use ethers::utils::hex::ToHex;
+    use hyperlane_base::db::{test_utils, DB};
+    use hyperlane_core::{DeadLetteredMessage, HyperlaneDomain, KnownHyperlaneDomain};
+    use std::net::SocketAddr;
+    use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+    fn setup_test_server(
+        db: DB,
+    ) -> (
+        SocketAddr,
+        HyperlaneRocksDB,
+        UnboundedReceiver<HyperlaneMessage>,
+    ) {
+        let origin: HyperlaneDomain = KnownHyperlaneDomain::Arbitrum.into();
+        let db = HyperlaneRocksDB::new(&origin, db);
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        let dead_letter_api =
+            DeadLetterApi::new(vec![db.clone()], HashMap::from([(origin.id(), tx)]));
+        let (path, router) = dead_letter_api.get_route();
+        let app = Router::new().nest(path, router);
+
+        // Running the app in the background using a test server
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        (addr, db, rx)
+    }
+
+    #[tokio::test]
+    async fn test_list_and_requeue_dead_lettered_message() {
+        test_utils::run_test_db(|db| async move {
+            let (addr, db, mut rx) = setup_test_server(db);
+            let message = HyperlaneMessage {
+                origin: KnownHyperlaneDomain::Arbitrum as u32,
+                destination: KnownHyperlaneDomain::Optimism as u32,
+                ..Default::default()
+            };
+            let message_id = message.id();
+            db.store_message(&message, 0).unwrap();
+            db.store_pending_message_retry_count_by_message_id(&message_id, &10)
+                .unwrap();
+            db.store_dead_lettered_message_by_message_id(
+                &message_id,
+                &DeadLetteredMessage {
+                    num_retries: 10,
+                    dead_lettered_at: 1,
+                    reason: "Exceeded max retries of 10".to_owned(),
+                },
+            )
+            .unwrap();
+
+            let response = reqwest::get(format!("http://{}{}/list", addr, DEAD_LETTER_API_BASE))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let entries: Vec<DeadLetteredMessageEntry> = response.json().await.unwrap();
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].message_id, message_id);
+            assert_eq!(entries[0].num_retries, 10);
+
+            let response = reqwest::get(format!(
+                "http://{}{}/requeue?message_id={}",
+                addr,
+                DEAD_LETTER_API_BASE,
+                message_id.encode_hex::<String>()
+            ))
+            .await
+            .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            assert_eq!(rx.recv().await.unwrap(), message);
+            assert!(db
+                .retrieve_dead_lettered_message_by_message_id(&message_id)
+                .unwrap()
+                .is_none());
+            assert_eq!(
+                db.retrieve_pending_message_retry_count_by_message_id(&message_id)
+                    .unwrap(),
+                Some(0)
+            );
+        })
+        .await;
+    }
+}
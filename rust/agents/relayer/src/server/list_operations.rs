@@ -0,0 +1,252 @@
+use std::{collections::HashMap, time::Instant};
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    routing, Json, Router,
+};
+use derive_new::new;
+use hyperlane_core::H256;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    msg::op_queue::{OpQueue, QueueOperation},
+    settings::matching_list::MatchingList,
+};
+
+const LIST_OPERATIONS_API_BASE: &str = "/list_operations";
+
+/// Read-only view over the prepare, submit and confirm queues of every
+/// destination's `SerialSubmitter`.
+#[derive(new, Clone)]
+pub struct ListOperationsApi {
+    /// The op queues of each destination, keyed by destination domain id
+    op_queues: HashMap<u32, Vec<OpQueue>>,
+}
+
+#[derive(Deserialize)]
+struct RawListOperationsRequest {
+    destination_domain: Option<u32>,
+    /// One of `prepare`, `submit` or `confirm`
+    queue: Option<String>,
+    /// A JSON-encoded `MatchingList`
+    matching_list: Option<String>,
+}
+
+/// An operation sitting in one of the relayer's op queues
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListedOperation {
+    pub id: H256,
+    pub queue: String,
+    pub origin_domain: u32,
+    pub destination_domain: u32,
+    pub sender_address: H256,
+    pub recipient_address: H256,
+    pub nonce: u32,
+    pub num_retries: u32,
+    /// Seconds until the operation will next be attempted. `None` if it can
+    /// be attempted right away.
+    pub next_attempt_after_secs: Option<u64>,
+    pub app_context: Option<String>,
+    pub last_error: Option<String>,
+}
+
+impl ListedOperation {
+    fn new(op: &QueueOperation, queue: &str, now: Instant) -> Self {
+        Self {
+            id: op.id(),
+            queue: queue.to_owned(),
+            origin_domain: op.origin_domain_id(),
+            destination_domain: op.destination_domain().id(),
+            sender_address: *op.sender_address(),
+            recipient_address: *op.recipient_address(),
+            nonce: op.nonce(),
+            num_retries: op.num_retries(),
+            next_attempt_after_secs: op
+                .next_attempt_after()
+                .map(|a| a.saturating_duration_since(now).as_secs()),
+            app_context: op.app_context(),
+            last_error: op.last_error(),
+        }
+    }
+}
+
+fn queue_matches(label: &str, requested: &Option<String>) -> bool {
+    match requested {
+        Some(requested) => label.trim_end_matches("_queue") == requested.trim_end_matches("_queue"),
+        None => true,
+    }
+}
+
+async fn list_operations(
+    State(op_queues): State<HashMap<u32, Vec<OpQueue>>>,
+    Query(request): Query<RawListOperationsRequest>,
+) -> Result<Json<Vec<ListedOperation>>, (StatusCode, String)> {
+    let matching_list = match request.matching_list {
+        Some(raw) => serde_json::from_str::<MatchingList>(&raw).map_err(|err| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Failed to parse matching list: {}", err),
+            )
+        })?,
+        None => MatchingList::default(),
+    };
+
+    let now = Instant::now();
+    let mut listed = vec![];
+    for (destination, queues) in &op_queues {
+        if matches!(request.destination_domain, Some(d) if d != *destination) {
+            continue;
+        }
+        for queue in queues {
+            if !queue_matches(queue.label(), &request.queue) {
+                continue;
+            }
+            let queued = queue.queue().lock().await;
+            // Sort so operations are listed in the order they will be popped
+            let mut ops = queued.iter().collect::<Vec<_>>();
+            ops.sort();
+            listed.extend(
+                ops.into_iter()
+                    .filter(|op| matching_list.op_matches(op, true))
+                    .map(|op| ListedOperation::new(op, queue.label(), now)),
+            );
+        }
+    }
+    Ok(Json(listed))
+}
+
+impl ListOperationsApi {
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/", routing::get(list_operations))
+            .with_state(self.op_queues.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (LIST_OPERATIONS_API_BASE, self.router())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::op_queue::test::{dummy_metrics_and_label, MockPendingOperation};
+    use crate::server::MessageRetryRequest;
+    use hyperlane_core::{HyperlaneDomain, KnownHyperlaneDomain, MpmcChannel};
+    use std::net::SocketAddr;
+
+    async fn setup_test_server(ops: Vec<QueueOperation>) -> SocketAddr {
+        let (metrics, _) = dummy_metrics_and_label();
+        let mpmc_channel = MpmcChannel::<MessageRetryRequest>::new(100);
+        let prepare_queue = OpQueue::new(
+            metrics.clone(),
+            "prepare_queue".to_string(),
+            mpmc_channel.receiver(),
+        );
+        let confirm_queue = OpQueue::new(
+            metrics,
+            "confirm_queue".to_string(),
+            mpmc_channel.receiver(),
+        );
+        for op in ops {
+            prepare_queue.push(op).await;
+        }
+        let destination: HyperlaneDomain = KnownHyperlaneDomain::Ethereum.into();
+        let list_operations_api = ListOperationsApi::new(HashMap::from([(
+            destination.id(),
+            vec![prepare_queue, confirm_queue],
+        )]));
+        let (path, router) = list_operations_api.get_route();
+        let app = Router::new().nest(path, router);
+
+        // Running the app in the background using a test server
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    fn mock_ops(count: u64) -> Vec<QueueOperation> {
+        let destination: HyperlaneDomain = KnownHyperlaneDomain::Ethereum.into();
+        (1..=count)
+            .map(|seconds_to_next_attempt| {
+                Box::new(MockPendingOperation::new(
+                    seconds_to_next_attempt,
+                    destination.clone(),
+                )) as QueueOperation
+            })
+            .collect()
+    }
+
+    async fn list(addr: SocketAddr, query: &[(&str, String)]) -> Vec<ListedOperation> {
+        let response = reqwest::Client::new()
+            .get(format!("http://{}{}", addr, LIST_OPERATIONS_API_BASE))
+            .query(query)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        response.json().await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_list_all_operations() {
+        let ops = mock_ops(3);
+        let op_ids: Vec<_> = ops.iter().map(|op| op.id()).collect();
+        let addr = setup_test_server(ops).await;
+
+        let listed = list(addr, &[]).await;
+
+        // Operations are listed in the order they would be popped from the queue
+        assert_eq!(listed.iter().map(|op| op.id).collect::<Vec<_>>(), op_ids);
+        assert!(listed.iter().all(|op| op.queue == "prepare_queue"));
+    }
+
+    #[tokio::test]
+    async fn test_list_operations_by_queue_and_domain() {
+        let addr = setup_test_server(mock_ops(2)).await;
+        let ethereum = KnownHyperlaneDomain::Ethereum as u32;
+        let injective = KnownHyperlaneDomain::Injective as u32;
+
+        assert_eq!(list(addr, &[("queue", "prepare".into())]).await.len(), 2);
+        assert!(list(addr, &[("queue", "confirm".into())]).await.is_empty());
+        assert_eq!(
+            list(addr, &[("destination_domain", ethereum.to_string())])
+                .await
+                .len(),
+            2
+        );
+        assert!(list(addr, &[("destination_domain", injective.to_string())])
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_list_operations_by_matching_list() {
+        let ops = mock_ops(3);
+        let recipient = *ops[1].recipient_address();
+        let addr = setup_test_server(ops).await;
+
+        let matching_list = format!(r#"[{{"recipientaddress": "{recipient:?}"}}]"#);
+        let listed = list(addr, &[("matching_list", matching_list)]).await;
+
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].recipient_address, recipient);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_matching_list() {
+        let addr = setup_test_server(mock_ops(1)).await;
+
+        let response = reqwest::get(format!(
+            "http://{}{}?matching_list=not-json",
+            addr, LIST_OPERATIONS_API_BASE
+        ))
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
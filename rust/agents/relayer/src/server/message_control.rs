@@ -0,0 +1,318 @@
+use std::{collections::HashMap, str::FromStr};
+
+use axum::{
+    extract::{Query, State},
+    routing, Router,
+};
+use derive_new::new;
+use eyre::Result;
+use hyperlane_base::db::HyperlaneRocksDB;
+use hyperlane_core::{utils::hex_or_base58_to_h256, RecipientPauseKey, H256};
+use serde::Deserialize;
+use tracing::info;
+
+use crate::msg::op_queue::OpQueue;
+
+const MESSAGE_CONTROL_API_BASE: &str = "/message_control";
+
+/// Lets operators permanently drop a message, or pause and resume delivery to a
+/// destination domain or to a recipient on a destination domain. Decisions are
+/// persisted in the database of every origin chain, so they survive restarts.
+#[derive(new, Clone)]
+pub struct MessageControlApi {
+    /// The databases of every origin chain
+    dbs: Vec<HyperlaneRocksDB>,
+    /// The op queues of each destination, keyed by destination domain id
+    op_queues: HashMap<u32, Vec<OpQueue>>,
+}
+
+#[derive(Deserialize)]
+struct RawDropMessageRequest {
+    message_id: String,
+}
+
+/// Pauses a whole destination domain, or only `recipient_address` on it when
+/// that is set
+#[derive(Deserialize)]
+struct RawPauseRequest {
+    destination_domain: Option<u32>,
+    recipient_address: Option<String>,
+}
+
+async fn drop_message(
+    State(api): State<MessageControlApi>,
+    Query(request): Query<RawDropMessageRequest>,
+) -> String {
+    let message_id = match H256::from_str(&request.message_id) {
+        Ok(message_id) => message_id,
+        // Technically it's bad practice to print the error message to the user, but
+        // this endpoint is for debugging purposes only.
+        Err(err) => {
+            return format!("Failed to parse message id: {}", err);
+        }
+    };
+
+    if let Err(err) = api
+        .dbs
+        .iter()
+        .try_for_each(|db| db.store_operator_dropped_by_message_id(&message_id, &true))
+    {
+        return format!("Failed to persist dropped message: {}", err);
+    }
+
+    let mut removed = false;
+    for queue in api.op_queues.values().flatten() {
+        if let Some(op) = queue.remove(message_id).await {
+            info!(operation = %op, queue_label = %queue.label(), "Dropped OpQueue operation");
+            removed = true;
+        }
+    }
+
+    if removed {
+        "Dropped message and removed it from the queue".to_string()
+    } else {
+        "Dropped message".to_string()
+    }
+}
+
+fn set_paused(dbs: &[HyperlaneRocksDB], request: RawPauseRequest, paused: bool) -> String {
+    let recipient_address = match request
+        .recipient_address
+        .as_deref()
+        .map(hex_or_base58_to_h256)
+        .transpose()
+    {
+        Ok(recipient_address) => recipient_address,
+        Err(err) => {
+            return format!("Failed to parse recipient address: {}", err);
+        }
+    };
+
+    let Some(destination_domain) = request.destination_domain else {
+        return "No pause target found. Please provide a destination_domain, and optionally a recipient_address on it.".to_string();
+    };
+
+    let result: Result<()> = dbs.iter().try_for_each(|db| {
+        match recipient_address {
+            Some(recipient) => db.store_operator_paused_by_recipient(
+                &RecipientPauseKey {
+                    destination: destination_domain,
+                    recipient,
+                },
+                &paused,
+            )?,
+            None => db.store_operator_paused_by_destination_domain(&destination_domain, &paused)?,
+        }
+        Ok(())
+    });
+    if let Err(err) = result {
+        return format!("Failed to persist pause state: {}", err);
+    }
+
+    info!(
+        destination_domain,
+        ?recipient_address,
+        paused,
+        "Updated message delivery pause state"
+    );
+    if paused {
+        "Paused message delivery".to_string()
+    } else {
+        "Resumed message delivery".to_string()
+    }
+}
+
+async fn pause(
+    State(api): State<MessageControlApi>,
+    Query(request): Query<RawPauseRequest>,
+) -> String {
+    set_paused(&api.dbs, request, true)
+}
+
+async fn resume(
+    State(api): State<MessageControlApi>,
+    Query(request): Query<RawPauseRequest>,
+) -> String {
+    set_paused(&api.dbs, request, false)
+}
+
+impl MessageControlApi {
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/drop", routing::get(drop_message))
+            .route("/pause", routing::get(pause))
+            .route("/resume", routing::get(resume))
+            .with_state(self.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (MESSAGE_CONTROL_API_BASE, self.router())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        msg::op_queue::{
+            test::{dummy_metrics_and_label, MockPendingOperation},
+            QueueOperation,
+        },
+        server::MessageRetryRequest,
+    };
+    use axum::http::StatusCode;
+    use ethers::utils::hex::ToHex;
+    use hyperlane_base::db::{test_utils, DB};
+    use hyperlane_core::{HyperlaneDomain, KnownHyperlaneDomain, MpmcChannel};
+    use std::net::SocketAddr;
+
+    fn setup_test_server(db: DB) -> (SocketAddr, HyperlaneRocksDB, OpQueue) {
+        let destination: HyperlaneDomain = KnownHyperlaneDomain::Ethereum.into();
+        let origin: HyperlaneDomain = KnownHyperlaneDomain::Injective.into();
+        let db = HyperlaneRocksDB::new(&origin, db);
+
+        let (metrics, queue_metrics_label) = dummy_metrics_and_label();
+        let mpmc_channel = MpmcChannel::<MessageRetryRequest>::new(100);
+        let op_queue = OpQueue::new(metrics, queue_metrics_label, mpmc_channel.receiver());
+
+        let message_control_api = MessageControlApi::new(
+            vec![db.clone()],
+            HashMap::from([(destination.id(), vec![op_queue.clone()])]),
+        );
+        let (path, router) = message_control_api.get_route();
+        let app = Router::new().nest(path, router);
+
+        // Running the app in the background using a test server
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        (addr, db, op_queue)
+    }
+
+    async fn get(addr: SocketAddr, path_and_query: &str) -> String {
+        let response = reqwest::get(format!(
+            "http://{}{}{}",
+            addr, MESSAGE_CONTROL_API_BASE, path_and_query
+        ))
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        response.text().await.unwrap()
+    }
+
+    fn recipient_paused(db: &HyperlaneRocksDB, destination: u32, recipient: H256) -> Option<bool> {
+        db.retrieve_operator_paused_by_recipient(&RecipientPauseKey {
+            destination,
+            recipient,
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_drop_message() {
+        test_utils::run_test_db(|db| async move {
+            let (addr, db, op_queue) = setup_test_server(db);
+            let destination: HyperlaneDomain = KnownHyperlaneDomain::Ethereum.into();
+            let op = Box::new(MockPendingOperation::new(1, destination)) as QueueOperation;
+            let message_id = op.id();
+            op_queue.push(op).await;
+
+            get(
+                addr,
+                &format!("/drop?message_id={}", message_id.encode_hex::<String>()),
+            )
+            .await;
+
+            assert_eq!(
+                db.retrieve_operator_dropped_by_message_id(&message_id)
+                    .unwrap(),
+                Some(true)
+            );
+            assert!(op_queue.queue().lock().await.is_empty());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume() {
+        test_utils::run_test_db(|db| async move {
+            let (addr, db, _) = setup_test_server(db);
+            let recipient = H256::random();
+            let destination_domain = 42;
+
+            get(
+                addr,
+                &format!("/pause?destination_domain={}", destination_domain),
+            )
+            .await;
+            get(
+                addr,
+                &format!(
+                    "/pause?destination_domain={}&recipient_address={:?}",
+                    destination_domain + 1,
+                    recipient
+                ),
+            )
+            .await;
+            assert_eq!(
+                db.retrieve_operator_paused_by_destination_domain(&destination_domain)
+                    .unwrap(),
+                Some(true)
+            );
+            assert_eq!(
+                recipient_paused(&db, destination_domain + 1, recipient),
+                Some(true)
+            );
+
+            get(
+                addr,
+                &format!("/resume?destination_domain={}", destination_domain),
+            )
+            .await;
+            assert_eq!(
+                db.retrieve_operator_paused_by_destination_domain(&destination_domain)
+                    .unwrap(),
+                Some(false)
+            );
+            // Only the destination was resumed
+            assert_eq!(
+                recipient_paused(&db, destination_domain + 1, recipient),
+                Some(true)
+            );
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_pause_recipient_is_scoped_to_destination() {
+        test_utils::run_test_db(|db| async move {
+            let (addr, db, _) = setup_test_server(db);
+            let recipient = H256::random();
+
+            get(
+                addr,
+                &format!(
+                    "/pause?destination_domain=42&recipient_address={:?}",
+                    recipient
+                ),
+            )
+            .await;
+            assert_eq!(recipient_paused(&db, 42, recipient), Some(true));
+            // Neither the same address on another destination nor the whole
+            // destination are paused
+            assert_eq!(recipient_paused(&db, 43, recipient), None);
+            assert_eq!(
+                db.retrieve_operator_paused_by_destination_domain(&42)
+                    .unwrap(),
+                None
+            );
+
+            // A recipient can't be paused without a destination
+            let response = get(addr, &format!("/pause?recipient_address={:?}", recipient)).await;
+            assert!(response.starts_with("No pause target found"));
+        })
+        .await;
+    }
+}
@@ -0,0 +1,53 @@
+use axum::Router;
+use hyperlane_base::db::HyperlaneRocksDB;
+use hyperlane_core::HyperlaneMessage;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::{broadcast::Sender, mpsc::UnboundedSender};
+
+use crate::{
+    msg::{op_queue::OpQueue, profitability::ProfitabilityTracker},
+    settings::reloader::SettingsReloader,
+};
+
+pub use dead_letter::*;
+pub use list_operations::*;
+pub use message_control::*;
+pub use message_retry::*;
+pub use profitability::*;
+pub use reload_settings::*;
+
+mod dead_letter;
+mod list_operations;
+mod message_control;
+mod message_retry;
+mod profitability;
+mod reload_settings;
+
+pub const ENDPOINT_MESSAGES_QUEUE_SIZE: usize = 1_000;
+
+/// Returns a vector of agent-specific endpoint routes to be served.
+/// Can be extended with additional routes and feature flags to enable/disable individually.
+pub fn routes(
+    tx: Sender<MessageRetryRequest>,
+    op_queues: HashMap<u32, Vec<OpQueue>>,
+    dbs: Vec<HyperlaneRocksDB>,
+    settings_reloader: SettingsReloader,
+    profitability: Arc<ProfitabilityTracker>,
+    requeue_senders: HashMap<u32, UnboundedSender<HyperlaneMessage>>,
+) -> Vec<(&'static str, Router)> {
+    let message_retry_api = MessageRetryApi::new(tx);
+    let list_operations_api = ListOperationsApi::new(op_queues.clone());
+    let message_control_api = MessageControlApi::new(dbs.clone(), op_queues);
+    let reload_settings_api = ReloadSettingsApi::new(settings_reloader);
+    let profitability_api = ProfitabilityApi::new(profitability);
+    let dead_letter_api = DeadLetterApi::new(dbs, requeue_senders);
+
+    vec![
+        message_retry_api.get_route(),
+        list_operations_api.get_route(),
+        message_control_api.get_route(),
+        reload_settings_api.get_route(),
+        profitability_api.get_route(),
+        dead_letter_api.get_route(),
+    ]
+}
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Query, State},
+    routing, Json, Router,
+};
+use derive_new::new;
+use serde::Deserialize;
+
+use crate::msg::profitability::{ProfitabilityTracker, RouteProfitability};
+
+const PROFITABILITY_API_BASE: &str = "/profitability";
+
+/// Reports the gas paid for and spent on delivering messages per origin,
+/// destination and app context since the relayer started.
+#[derive(new, Clone)]
+pub struct ProfitabilityApi {
+    tracker: Arc<ProfitabilityTracker>,
+}
+
+#[derive(Deserialize)]
+struct RawProfitabilityRequest {
+    origin_domain: Option<u32>,
+    destination_domain: Option<u32>,
+}
+
+async fn profitability(
+    State(tracker): State<Arc<ProfitabilityTracker>>,
+    Query(request): Query<RawProfitabilityRequest>,
+) -> Json<Vec<RouteProfitability>> {
+    Json(tracker.report(request.origin_domain, request.destination_domain))
+}
+
+impl ProfitabilityApi {
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/", routing::get(profitability))
+            .with_state(self.tracker.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (PROFITABILITY_API_BASE, self.router())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use hyperlane_base::CoreMetrics;
+    use hyperlane_core::{HyperlaneDomain, KnownHyperlaneDomain, U256};
+    use prometheus::Registry;
+    use std::net::SocketAddr;
+
+    fn setup_test_server(tracker: Arc<ProfitabilityTracker>) -> SocketAddr {
+        let (path, router) = ProfitabilityApi::new(tracker).get_route();
+        let app = Router::new().nest(path, router);
+
+        // Running the app in the background using a test server
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_profitability_report() {
+        let metrics = CoreMetrics::new("test", 9090, Registry::new()).unwrap();
+        let tracker = Arc::new(ProfitabilityTracker::new(&metrics));
+        let origin: HyperlaneDomain = KnownHyperlaneDomain::Arbitrum.into();
+        let destination: HyperlaneDomain = KnownHyperlaneDomain::Optimism.into();
+        tracker.record_expenditure(&origin, &destination, Some("app"), U256::from(1), None);
+        tracker.record_delivery(&origin, &destination, Some("app"), U256::from(3), None);
+        let addr = setup_test_server(tracker);
+
+        let response = reqwest::Client::new()
+            .get(format!("http://{}{}", addr, PROFITABILITY_API_BASE))
+            .query(&[("destination_domain", destination.id())])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let report: serde_json::Value = response.json().await.unwrap();
+        let routes = report.as_array().unwrap();
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0]["origin"], "arbitrum");
+        assert_eq!(routes[0]["app_context"], "app");
+        assert_eq!(routes[0]["messages_delivered"], 1);
+    }
+}
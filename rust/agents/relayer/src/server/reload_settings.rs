@@ -0,0 +1,37 @@
+use axum::{extract::State, routing, Router};
+use derive_new::new;
+
+use crate::settings::reloader::SettingsReloader;
+
+const RELOAD_SETTINGS_API_BASE: &str = "/reload_settings";
+
+/// Reloads the relayer's whitelist, blacklist, metric app contexts and gas
+/// payment policies from config without restarting.
+#[derive(new, Clone)]
+pub struct ReloadSettingsApi {
+    reloader: SettingsReloader,
+}
+
+async fn reload_settings(State(reloader): State<SettingsReloader>) -> String {
+    match reloader.reload().await {
+        Ok(()) => "Reloaded relayer settings".to_string(),
+        // Technically it's bad practice to print the error message to the user, but
+        // this endpoint is for debugging purposes only.
+        Err(err) => format!(
+            "Failed to reload relayer settings, keeping previous config: {}",
+            err
+        ),
+    }
+}
+
+impl ReloadSettingsApi {
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/", routing::get(reload_settings))
+            .with_state(self.reloader.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (RELOAD_SETTINGS_API_BASE, self.router())
+    }
+}
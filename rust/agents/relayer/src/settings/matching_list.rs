@@ -8,12 +8,17 @@ use std::{
     marker::PhantomData,
 };
 
-use hyperlane_core::{config::StrOrInt, utils::hex_or_base58_to_h256, HyperlaneMessage, H256};
+use ethers::utils::hex;
+use hyperlane_core::{
+    config::StrOrInt, utils::hex_or_base58_to_h256, HyperlaneMessage, H256, U256,
+};
 use serde::{
     de::{Error, SeqAccess, Visitor},
     Deserialize, Deserializer,
 };
 
+use crate::msg::op_queue::QueueOperation;
+
 /// Defines a set of patterns for determining if a message should or should not
 /// be relayed. This is useful for determine if a message matches a given set or
 /// rules.
@@ -22,6 +27,21 @@ use serde::{
 /// - wildcard "*"
 /// - single value in decimal or hex (must start with `0x`) format
 /// - list of values in decimal or hex format
+///
+/// Rules can additionally filter on the message itself:
+/// - `messageId`: a message id or list of message ids
+/// - `nonce`: an inclusive `{"min", "max"}` range, either bound may be omitted
+/// - `bodyPrefix`: a hex string or list of hex strings the body must start with
+/// - `functionSelector`: a 4 byte hex string or list of them the body must start
+///   with
+/// - `bodyLength`: an inclusive `{"min", "max"}` range on the body length in bytes
+/// - `bodyUint`: an inclusive `{"offset", "min", "max"}` range on the big endian
+///   32 byte word starting at `offset` in the body, e.g. a warp route amount
+/// - `not`: a nested matching list, the rule only matches if none of its rules
+///   match
+///
+/// Queued operations do not expose their message body, so body rules never
+/// match when filtering operations.
 #[derive(Debug, Default, Clone)]
 pub struct MatchingList(Option<Vec<ListElement>>);
 
@@ -46,6 +66,15 @@ impl<T: PartialEq> Filter<T> {
     }
 }
 
+impl Filter<HexBytes> {
+    fn matches_prefix_of(&self, body: &[u8]) -> bool {
+        match self {
+            Filter::Wildcard => true,
+            Filter::Enumerated(list) => list.iter().any(|p| body.starts_with(&p.0)),
+        }
+    }
+}
+
 impl<T: Debug> Display for Filter<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -62,6 +91,89 @@ impl<T: Debug> Display for Filter<T> {
     }
 }
 
+/// Bytes that are configured as a hex string, with or without a `0x` prefix.
+#[derive(Clone, PartialEq)]
+struct HexBytes(Vec<u8>);
+
+impl Debug for HexBytes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(&self.0))
+    }
+}
+
+impl HexBytes {
+    fn parse<E: Error>(s: &str) -> Result<Self, E> {
+        hex::decode(s.strip_prefix("0x").unwrap_or(s))
+            .map(Self)
+            .map_err(to_serde_err)
+    }
+}
+
+/// An inclusive range, where a missing bound is unbounded.
+#[derive(Debug, Clone, PartialEq)]
+struct Range<T> {
+    min: Option<T>,
+    max: Option<T>,
+}
+
+impl<T> Default for Range<T> {
+    fn default() -> Self {
+        Self {
+            min: None,
+            max: None,
+        }
+    }
+}
+
+impl<T: PartialOrd> Range<T> {
+    fn matches(&self, v: &T) -> bool {
+        self.min.as_ref().map_or(true, |min| v >= min)
+            && self.max.as_ref().map_or(true, |max| v <= max)
+    }
+
+    fn is_unbounded(&self) -> bool {
+        self.min.is_none() && self.max.is_none()
+    }
+}
+
+impl<T: Debug> Display for Range<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.min {
+            Some(min) => write!(f, "[{min:?}, ")?,
+            None => write!(f, "[*, ")?,
+        }
+        match &self.max {
+            Some(max) => write!(f, "{max:?}]"),
+            None => write!(f, "*]"),
+        }
+    }
+}
+
+/// A range on the big endian 32 byte word starting at `offset` in the message
+/// body. A body that is too short to contain the word never matches.
+#[derive(Debug, Clone, PartialEq)]
+struct BodyUintRange {
+    offset: usize,
+    range: Range<U256>,
+}
+
+impl BodyUintRange {
+    fn matches(&self, body: &[u8]) -> bool {
+        self.offset
+            .checked_add(32)
+            .and_then(|end| body.get(self.offset..end))
+            .map_or(false, |word| {
+                self.range.matches(&U256::from_big_endian(word))
+            })
+    }
+}
+
+impl Display for BodyUintRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{{offset: {}, range: {}}}", self.offset, self.range)
+    }
+}
+
 struct MatchingListVisitor;
 impl<'de> Visitor<'de> for MatchingListVisitor {
     type Value = MatchingList;
@@ -193,6 +305,39 @@ impl<'de> Visitor<'de> for FilterVisitor<H256> {
     }
 }
 
+impl<'de> Visitor<'de> for FilterVisitor<HexBytes> {
+    type Value = Filter<HexBytes>;
+
+    fn expecting(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(
+            fmt,
+            "Expecting either a wildcard \"*\", hex string, or list of hex strings"
+        )
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(if v == "*" {
+            Self::Value::Wildcard
+        } else {
+            Self::Value::Enumerated(vec![HexBytes::parse(v)?])
+        })
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::new();
+        while let Some(i) = seq.next_element::<String>()? {
+            values.push(HexBytes::parse(&i)?)
+        }
+        Ok(Self::Value::Enumerated(values))
+    }
+}
+
 impl<'de> Deserialize<'de> for MatchingList {
     fn deserialize<D>(d: D) -> Result<Self, D::Error>
     where
@@ -220,6 +365,90 @@ impl<'de> Deserialize<'de> for Filter<H256> {
     }
 }
 
+impl<'de> Deserialize<'de> for Filter<HexBytes> {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        d.deserialize_any(FilterVisitor::<HexBytes>(Default::default()))
+    }
+}
+
+/// Range bounds may be given as integers or as decimal strings.
+#[derive(Deserialize)]
+struct RawRange {
+    min: Option<StrOrInt>,
+    max: Option<StrOrInt>,
+}
+
+impl<'de, T> Deserialize<'de> for Range<T>
+where
+    T: TryFrom<StrOrInt>,
+    T::Error: Display,
+{
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = RawRange::deserialize(d)?;
+        Ok(Self {
+            min: raw.min.map(T::try_from).transpose().map_err(to_serde_err)?,
+            max: raw.max.map(T::try_from).transpose().map_err(to_serde_err)?,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct RawBodyUintRange {
+    offset: StrOrInt,
+    #[serde(flatten)]
+    range: RawRange,
+}
+
+impl<'de> Deserialize<'de> for BodyUintRange {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = RawBodyUintRange::deserialize(d)?;
+        let offset: u32 = raw.offset.try_into().map_err(to_serde_err)?;
+        Ok(Self {
+            offset: offset as usize,
+            range: Range {
+                min: raw.range.min.map(parse_uint).transpose()?,
+                max: raw.range.max.map(parse_uint).transpose()?,
+            },
+        })
+    }
+}
+
+/// Parses a decimal or `0x` prefixed hex value. Unlike `U256::from_str`, strings
+/// without a prefix are treated as decimal since these are usually token amounts.
+fn parse_uint<E: Error>(v: StrOrInt) -> Result<U256, E> {
+    match v {
+        StrOrInt::Str(s) => match s.strip_prefix("0x") {
+            Some(hex) => U256::from_str_radix(hex, 16).map_err(to_serde_err),
+            None => U256::from_dec_str(&s).map_err(to_serde_err),
+        },
+        StrOrInt::Int(i) => u64::try_from(i).map(U256::from).map_err(to_serde_err),
+    }
+}
+
+fn deserialize_function_selector<'de, D>(d: D) -> Result<Filter<HexBytes>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let filter = Filter::<HexBytes>::deserialize(d)?;
+    if let Filter::Enumerated(selectors) = &filter {
+        if let Some(selector) = selectors.iter().find(|s| s.0.len() != 4) {
+            return Err(D::Error::custom(format!(
+                "Function selector must be 4 bytes, got {selector:?}"
+            )));
+        }
+    }
+    Ok(filter)
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(tag = "type")]
 struct ListElement {
@@ -231,18 +460,80 @@ struct ListElement {
     destination_domain: Filter<u32>,
     #[serde(default, rename = "recipientaddress")]
     recipient_address: Filter<H256>,
+    #[serde(default, rename = "messageid")]
+    message_id: Filter<H256>,
+    #[serde(default)]
+    nonce: Range<u32>,
+    #[serde(default, rename = "bodyprefix")]
+    body_prefix: Filter<HexBytes>,
+    #[serde(
+        default,
+        rename = "functionselector",
+        deserialize_with = "deserialize_function_selector"
+    )]
+    function_selector: Filter<HexBytes>,
+    #[serde(default, rename = "bodylength")]
+    body_length: Range<u32>,
+    #[serde(default, rename = "bodyuint")]
+    body_uint: Option<BodyUintRange>,
+    #[serde(default)]
+    not: MatchingList,
+}
+
+impl ListElement {
+    fn matches(&self, info: MatchInfo) -> bool {
+        self.origin_domain.matches(&info.src_domain)
+            && self.sender_address.matches(info.src_addr)
+            && self.destination_domain.matches(&info.dst_domain)
+            && self.recipient_address.matches(info.dst_addr)
+            && self.message_id.matches(&info.id)
+            && self.nonce.matches(&info.nonce)
+            && self.body_prefix.matches_prefix_of(info.body)
+            && self.function_selector.matches_prefix_of(info.body)
+            && (self.body_length.is_unbounded()
+                || u32::try_from(info.body.len())
+                    .map_or(false, |len| self.body_length.matches(&len)))
+            && self
+                .body_uint
+                .as_ref()
+                .map_or(true, |body_uint| body_uint.matches(info.body))
+            && !self.not.matches(info, false)
+    }
 }
 
 impl Display for ListElement {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{originDomain: {}, senderAddress: {}, destinationDomain: {}, recipientAddress: {}}}",
+            "{{originDomain: {}, senderAddress: {}, destinationDomain: {}, recipientAddress: {}",
             self.origin_domain,
             self.sender_address,
             self.destination_domain,
             self.recipient_address
-        )
+        )?;
+        // Only print the message filters that are set to keep the logs readable
+        if self.message_id != Filter::Wildcard {
+            write!(f, ", messageId: {}", self.message_id)?;
+        }
+        if !self.nonce.is_unbounded() {
+            write!(f, ", nonce: {}", self.nonce)?;
+        }
+        if self.body_prefix != Filter::Wildcard {
+            write!(f, ", bodyPrefix: {}", self.body_prefix)?;
+        }
+        if self.function_selector != Filter::Wildcard {
+            write!(f, ", functionSelector: {}", self.function_selector)?;
+        }
+        if !self.body_length.is_unbounded() {
+            write!(f, ", bodyLength: {}", self.body_length)?;
+        }
+        if let Some(body_uint) = &self.body_uint {
+            write!(f, ", bodyUint: {body_uint}")?;
+        }
+        if self.not.0.is_some() {
+            write!(f, ", not: {}", self.not)?;
+        }
+        write!(f, "}}")
     }
 }
 
@@ -252,6 +543,9 @@ struct MatchInfo<'a> {
     src_addr: &'a H256,
     dst_domain: u32,
     dst_addr: &'a H256,
+    id: H256,
+    nonce: u32,
+    body: &'a [u8],
 }
 
 impl<'a> From<&'a HyperlaneMessage> for MatchInfo<'a> {
@@ -261,6 +555,23 @@ impl<'a> From<&'a HyperlaneMessage> for MatchInfo<'a> {
             src_addr: &msg.sender,
             dst_domain: msg.destination,
             dst_addr: &msg.recipient,
+            id: msg.id(),
+            nonce: msg.nonce,
+            body: &msg.body,
+        }
+    }
+}
+
+impl<'a> From<&'a QueueOperation> for MatchInfo<'a> {
+    fn from(op: &'a QueueOperation) -> Self {
+        Self {
+            src_domain: op.origin_domain_id(),
+            src_addr: op.sender_address(),
+            dst_domain: op.destination_domain().id(),
+            dst_addr: op.recipient_address(),
+            id: op.id(),
+            nonce: op.nonce(),
+            body: op.body(),
         }
     }
 }
@@ -272,6 +583,12 @@ impl MatchingList {
         self.matches(msg.into(), default)
     }
 
+    /// Check if an operation matches any of the rules.
+    /// - `default`: What to return if the the matching list is empty.
+    pub fn op_matches(&self, op: &QueueOperation, default: bool) -> bool {
+        self.matches(op.into(), default)
+    }
+
     /// Check if a message matches any of the rules.
     /// - `default`: What to return if the the matching list is empty.
     fn matches(&self, info: MatchInfo, default: bool) -> bool {
@@ -284,12 +601,7 @@ impl MatchingList {
 }
 
 fn matches_any_rule<'a>(mut rules: impl Iterator<Item = &'a ListElement>, info: MatchInfo) -> bool {
-    rules.any(|rule| {
-        rule.origin_domain.matches(&info.src_domain)
-            && rule.sender_address.matches(info.src_addr)
-            && rule.destination_domain.matches(&info.dst_domain)
-            && rule.recipient_address.matches(info.dst_addr)
-    })
+    rules.any(|rule| rule.matches(info))
 }
 
 impl Display for MatchingList {
@@ -316,10 +628,13 @@ fn parse_addr<E: Error>(addr_str: &str) -> Result<H256, E> {
 
 #[cfg(test)]
 mod test {
-    use hyperlane_core::{H160, H256};
+    use hyperlane_core::{HyperlaneMessage, KnownHyperlaneDomain, H160, H256, U256};
 
     use super::{Filter::*, MatchingList};
-    use crate::settings::matching_list::MatchInfo;
+    use crate::{
+        msg::op_queue::{test::MockPendingOperation, QueueOperation},
+        settings::matching_list::MatchInfo,
+    };
 
     #[test]
     fn basic_config() {
@@ -343,7 +658,10 @@ mod test {
                 src_domain: 0,
                 src_addr: &H256::default(),
                 dst_domain: 0,
-                dst_addr: &H256::default()
+                dst_addr: &H256::default(),
+                id: H256::default(),
+                nonce: 0,
+                body: &[],
             },
             false
         ));
@@ -356,7 +674,10 @@ mod test {
                     .unwrap()
                     .into(),
                 dst_domain: 5456,
-                dst_addr: &H256::default()
+                dst_addr: &H256::default(),
+                id: H256::default(),
+                nonce: 0,
+                body: &[],
             },
             false
         ))
@@ -396,7 +717,10 @@ mod test {
                 dst_addr: &"9d4454B023096f34B160D6B654540c56A1F81688"
                     .parse::<H160>()
                     .unwrap()
-                    .into()
+                    .into(),
+                id: H256::default(),
+                nonce: 0,
+                body: &[],
             },
             false
         ));
@@ -409,7 +733,10 @@ mod test {
                     .unwrap()
                     .into(),
                 dst_domain: 5456,
-                dst_addr: &H256::default()
+                dst_addr: &H256::default(),
+                id: H256::default(),
+                nonce: 0,
+                body: &[],
             },
             false
         ));
@@ -441,6 +768,9 @@ mod test {
             src_addr: &H256::default(),
             dst_domain: 0,
             dst_addr: &H256::default(),
+            id: H256::default(),
+            nonce: 0,
+            body: &[],
         };
         // whitelist use
         assert!(MatchingList(None).matches(info, true));
@@ -469,4 +799,111 @@ mod test {
             hyperlane_base::settings::parser::ValueParser::new(Default::default(), &val);
         crate::settings::parse_matching_list(value_parser).unwrap();
     }
+
+    #[test]
+    fn matches_nonce_range_and_message_id() {
+        let msg = HyperlaneMessage {
+            nonce: 10,
+            ..Default::default()
+        };
+        let in_range: MatchingList =
+            serde_json::from_str(r#"[{"nonce": {"min": 5, "max": "10"}}]"#).unwrap();
+        assert!(in_range.msg_matches(&msg, false));
+        let out_of_range: MatchingList =
+            serde_json::from_str(r#"[{"nonce": {"min": 11}}]"#).unwrap();
+        assert!(!out_of_range.msg_matches(&msg, false));
+
+        let by_id: MatchingList =
+            serde_json::from_str(&format!(r#"[{{"messageid": ["{:?}"]}}]"#, msg.id())).unwrap();
+        assert!(by_id.msg_matches(&msg, false));
+        assert!(!by_id.msg_matches(&HyperlaneMessage::default(), false));
+    }
+
+    #[test]
+    fn matches_body_prefix_selector_and_length() {
+        let msg = HyperlaneMessage {
+            body: vec![0xa9, 0x05, 0x9c, 0xbb, 0x01, 0x02],
+            ..Default::default()
+        };
+        let list: MatchingList = serde_json::from_str(
+            r#"[{"functionselector": "0xa9059cbb", "bodyprefix": ["0xdead", "a9059cbb01"], "bodylength": {"max": 6}}]"#,
+        )
+        .unwrap();
+        assert!(list.msg_matches(&msg, false));
+
+        let too_long = HyperlaneMessage {
+            body: [msg.body.clone(), vec![0x03]].concat(),
+            ..Default::default()
+        };
+        assert!(!list.msg_matches(&too_long, false));
+
+        let wrong_selector = HyperlaneMessage {
+            body: vec![0x00, 0x05, 0x9c, 0xbb, 0x01],
+            ..Default::default()
+        };
+        assert!(!list.msg_matches(&wrong_selector, false));
+    }
+
+    #[test]
+    fn rejects_invalid_function_selector() {
+        assert!(
+            serde_json::from_str::<MatchingList>(r#"[{"functionselector": "0xa905"}]"#).is_err()
+        );
+    }
+
+    #[test]
+    fn matches_body_uint() {
+        // Warp route transfer bodies are the recipient followed by the amount
+        let transfer = |amount: u64| {
+            let mut body = H256::random().as_bytes().to_vec();
+            let mut word = [0u8; 32];
+            U256::from(amount).to_big_endian(&mut word);
+            body.extend_from_slice(&word);
+            HyperlaneMessage {
+                body,
+                ..Default::default()
+            }
+        };
+        let list: MatchingList =
+            serde_json::from_str(r#"[{"bodyuint": {"offset": 32, "min": "1000"}}]"#).unwrap();
+        assert!(list.msg_matches(&transfer(1000), false));
+        assert!(!list.msg_matches(&transfer(999), false));
+        // The body is too short to contain the amount
+        assert!(!list.msg_matches(&HyperlaneMessage::default(), false));
+    }
+
+    #[test]
+    fn matches_not() {
+        let list: MatchingList = serde_json::from_str(
+            r#"[{"destinationdomain": 13372, "not": [{"senderaddress": "0x9d4454B023096f34B160D6B654540c56A1F81688"}]}]"#,
+        )
+        .unwrap();
+        let msg = HyperlaneMessage {
+            destination: 13372,
+            ..Default::default()
+        };
+        assert!(list.msg_matches(&msg, false));
+
+        let excluded = HyperlaneMessage {
+            sender: "0x9d4454B023096f34B160D6B654540c56A1F81688"
+                .parse::<H160>()
+                .unwrap()
+                .into(),
+            ..msg
+        };
+        assert!(!list.msg_matches(&excluded, false));
+    }
+
+    #[test]
+    fn matches_negated_body_rules_on_queued_operations() {
+        let list: MatchingList =
+            serde_json::from_str(r#"[{"not": [{"functionselector": "0xa9059cbb"}]}]"#).unwrap();
+        let op = |body: Vec<u8>| {
+            Box::new(
+                MockPendingOperation::new(1, KnownHyperlaneDomain::Ethereum.into()).with_body(body),
+            ) as QueueOperation
+        };
+        assert!(list.op_matches(&op(vec![0x00, 0x05, 0x9c, 0xbb, 0x01]), false));
+        assert!(!list.op_matches(&op(vec![0xa9, 0x05, 0x9c, 0xbb, 0x01]), false));
+    }
 }
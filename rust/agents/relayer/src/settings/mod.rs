@@ -4,7 +4,12 @@
 //! and validations it defines are not applied here, we should mirror them.
 //! ANY CHANGES HERE NEED TO BE REFLECTED IN THE TYPESCRIPT SDK.
 
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
 
 use convert_case::Case;
 use derive_more::{AsMut, AsRef, Deref, DerefMut};
@@ -16,7 +21,9 @@ use hyperlane_base::{
         Settings,
     },
 };
-use hyperlane_core::{cfg_unwrap_all, config::*, HyperlaneDomain, U256};
+use hyperlane_core::{
+    cfg_unwrap_all, config::*, FixedPointNumber, HyperlaneDomain, HyperlaneMessage, U256,
+};
 use itertools::Itertools;
 use serde::Deserialize;
 use serde_json::Value;
@@ -24,6 +31,7 @@ use serde_json::Value;
 use crate::settings::matching_list::MatchingList;
 
 pub mod matching_list;
+pub mod reloader;
 
 /// Settings for `Relayer`
 #[derive(Debug, AsRef, AsMut, Deref, DerefMut)]
@@ -56,6 +64,47 @@ pub struct RelayerSettings {
     pub allow_local_checkpoint_syncers: bool,
     /// App contexts used for metrics.
     pub metric_app_contexts: Vec<(MatchingList, String)>,
+    /// Weights and rate limits used to share each destination's submission
+    /// slot fairly between apps. Empty if operations should be scheduled
+    /// purely by priority.
+    pub app_scheduling: Vec<AppSchedulingConf>,
+    /// When to give up on delivering a message and move it to the dead-letter
+    /// store.
+    pub dead_letter: DeadLetterConf,
+    /// If true, messages are fully prepared but never submitted. What would
+    /// have been submitted is logged and reported in metrics instead.
+    pub dry_run: bool,
+}
+
+impl RelayerSettings {
+    /// The filters that decide which messages are relayed and how they are
+    /// labelled in metrics.
+    pub fn message_filters(&self) -> MessageFilters {
+        MessageFilters {
+            whitelist: self.whitelist.clone(),
+            blacklist: self.blacklist.clone(),
+            metric_app_contexts: self.metric_app_contexts.clone(),
+        }
+    }
+}
+
+/// Matching lists that decide which messages are relayed and how they are
+/// labelled in metrics. These can be swapped out while the relayer is running.
+#[derive(Debug, Clone, Default)]
+pub struct MessageFilters {
+    /// Filter for what messages to relay.
+    pub whitelist: MatchingList,
+    /// Filter for what messages to block.
+    pub blacklist: MatchingList,
+    /// App contexts used for metrics.
+    pub metric_app_contexts: Vec<(MatchingList, String)>,
+}
+
+impl MessageFilters {
+    /// Whether the message passes the whitelist and isn't blacklisted.
+    pub fn allows(&self, message: &HyperlaneMessage) -> bool {
+        self.whitelist.msg_matches(message, true) && !self.blacklist.msg_matches(message, false)
+    }
 }
 
 /// Config for gas payment enforcement
@@ -68,6 +117,40 @@ pub struct GasPaymentEnforcementConf {
     pub matching_list: MatchingList,
 }
 
+/// Config for how the operations of an app are scheduled for submission
+#[derive(Debug, Clone)]
+pub struct AppSchedulingConf {
+    /// The name of the app, used in metrics
+    pub name: String,
+    /// Operations that match this list belong to the app
+    pub matching_list: MatchingList,
+    /// The share of submissions this app gets relative to other apps that have
+    /// operations ready to be submitted
+    pub weight: u32,
+    /// The maximum rate at which operations of this app are submitted to each
+    /// destination
+    pub rate_limit: Option<RateLimitConf>,
+}
+
+/// Config for when a message is given up on. A message is dead-lettered once it
+/// exceeds either limit. By default messages are retried forever.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeadLetterConf {
+    /// The number of retries after which a message is dead-lettered
+    pub max_retries: Option<u32>,
+    /// How long after the relayer first saw a message it is dead-lettered
+    pub max_age: Option<Duration>,
+}
+
+/// Config for a token bucket rate limit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitConf {
+    /// The rate at which tokens are refilled
+    pub max_per_second: f64,
+    /// The maximum number of tokens that can be accumulated
+    pub burst: u32,
+}
+
 /// Config for a GasPaymentEnforcementPolicy
 #[derive(Debug, Clone, Default)]
 pub enum GasPaymentEnforcementPolicy {
@@ -82,6 +165,63 @@ pub enum GasPaymentEnforcementPolicy {
         gas_fraction_numerator: u64,
         gas_fraction_denominator: u64,
     },
+    /// The value of the payment covers the value of the estimated transaction
+    /// cost times a margin, with both converted to a common currency (e.g. USD)
+    /// using the exchange rates of the chains' native tokens.
+    ExchangeRate(ExchangeRateConf),
+}
+
+/// Config for the `ExchangeRate` gas payment enforcement policy
+#[derive(Debug, Clone)]
+pub struct ExchangeRateConf {
+    /// The cost is multiplied by this before being compared to the payment,
+    /// e.g. `1.1` requires the payment to be worth 10% more than the cost.
+    pub margin: FixedPointNumber,
+    /// Exchange rates of each chain's native token, keyed by chain name.
+    pub exchange_rates: HashMap<String, TokenExchangeRate>,
+    /// A local JSON file or an HTTP(S) URL serving exchange rates in the same
+    /// shape as `exchange_rates`. Rates from here take precedence.
+    pub exchange_rates_source: Option<String>,
+    /// How often `exchange_rates_source` is re-read.
+    pub refresh_interval: Duration,
+    /// Chain names by domain id, used to look up the exchange rates for a
+    /// message's origin and destination.
+    pub chain_names: HashMap<u32, String>,
+}
+
+/// The exchange rate of a chain's native token
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenExchangeRate {
+    /// The value of one whole token in the common currency
+    pub price: FixedPointNumber,
+    /// The number of decimals of the token, i.e. one whole token is
+    /// `10^decimals` of its smallest unit.
+    pub decimals: u32,
+}
+
+#[derive(Deserialize)]
+struct RawTokenExchangeRate {
+    price: Value,
+    decimals: Option<u32>,
+}
+
+impl<'de> Deserialize<'de> for TokenExchangeRate {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = RawTokenExchangeRate::deserialize(d)?;
+        // Prices may be given as numbers or as decimal strings
+        let price = match raw.price {
+            Value::String(s) => s,
+            Value::Number(n) => n.to_string(),
+            v => return Err(serde::de::Error::custom(format!("Invalid price `{v}`"))),
+        };
+        Ok(Self {
+            price: FixedPointNumber::from_str(&price).map_err(serde::de::Error::custom)?,
+            decimals: raw.decimals.unwrap_or(18),
+        })
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -167,6 +307,44 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
                                 .unwrap_or(1),
                         })
                     }
+                    Some("exchangeRate") => {
+                        let margin = policy.chain(&mut err)
+                            .get_opt_key("margin")
+                            .parse_f64()
+                            .end()
+                            .map(|margin| FixedPointNumber::from_str(&margin.to_string()))
+                            .transpose()
+                            .context("Invalid `margin` for ExchangeRate gas payment enforcement policy")
+                            .take_err(&mut err, || &policy.cwp + "margin")
+                            .flatten()
+                            .unwrap_or_else(|| FixedPointNumber::from(1));
+                        let exchange_rates = policy.chain(&mut err)
+                            .get_opt_key("exchangeRates")
+                            .parse_value::<HashMap<String, TokenExchangeRate>>("Expected a map of chain names to exchange rates")
+                            .unwrap_or_default();
+                        let exchange_rates_source = policy.chain(&mut err)
+                            .get_opt_key("exchangeRatesSource")
+                            .parse_string()
+                            .end()
+                            .map(str::to_owned);
+                        let refresh_interval = policy.chain(&mut err)
+                            .get_opt_key("refreshInterval")
+                            .parse_u64()
+                            .map(Duration::from_secs)
+                            .unwrap_or(Duration::from_secs(60));
+                        if exchange_rates.is_empty() && exchange_rates_source.is_none() {
+                            err.push(&policy.cwp + "exchange_rates", eyre!("ExchangeRate gas payment enforcement policy requires `exchangeRates` or `exchangeRatesSource`"));
+                        }
+
+                        Some(GasPaymentEnforcementPolicy::ExchangeRate(ExchangeRateConf {
+                            margin,
+                            exchange_rates,
+                            exchange_rates_source,
+                            refresh_interval,
+                            // Filled in once the chain config has been parsed
+                            chain_names: HashMap::new(),
+                        }))
+                    }
                     Some(pt) => Err(eyre!("Unknown gas payment enforcement policy type `{pt}`"))
                         .take_err(&mut err, || cwp + "type"),
                 }.map(|policy| GasPaymentEnforcementConf {
@@ -210,8 +388,26 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             .parse_bool()
             .unwrap_or(false);
 
+        let dry_run = p
+            .chain(&mut err)
+            .get_opt_key("dryRun")
+            .parse_bool()
+            .unwrap_or(false);
+
         cfg_unwrap_all!(cwp, err: [base]);
 
+        let chain_names: HashMap<u32, String> = base
+            .chains
+            .iter()
+            .map(|(name, chain)| (chain.domain.id(), name.clone()))
+            .collect();
+        for conf in &mut gas_payment_enforcement {
+            if let GasPaymentEnforcementPolicy::ExchangeRate(exchange_rate_conf) = &mut conf.policy
+            {
+                exchange_rate_conf.chain_names = chain_names.clone();
+            }
+        }
+
         let skip_transaction_gas_limit_for = skip_transaction_gas_limit_for_names
             .into_iter()
             .filter_map(|chain| {
@@ -260,6 +456,88 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             })
             .unwrap_or_default();
 
+        let (raw_app_scheduling_path, raw_app_scheduling) = p
+            .get_opt_key("appScheduling")
+            .take_config_err_flat(&mut err)
+            .and_then(parse_json_array)
+            .unwrap_or_else(|| (&p.cwp + "app_scheduling", Value::Array(vec![])));
+
+        let app_scheduling_parser = ValueParser::new(raw_app_scheduling_path, &raw_app_scheduling);
+        let app_scheduling = app_scheduling_parser
+            .into_array_iter()
+            .map(|itr| {
+                itr.filter_map(|app| {
+                    let name = app.chain(&mut err).get_key("name").parse_string().end();
+
+                    let matching_list = app
+                        .chain(&mut err)
+                        .get_key("matchingList")
+                        .and_then(parse_matching_list)
+                        .unwrap_or_default();
+
+                    let weight = app
+                        .chain(&mut err)
+                        .get_opt_key("weight")
+                        .parse_u32()
+                        .unwrap_or(1);
+                    if weight == 0 {
+                        err.push(
+                            &app.cwp + "weight",
+                            eyre!("App scheduling `weight` must be greater than 0"),
+                        );
+                    }
+
+                    let rate_limit = app
+                        .chain(&mut err)
+                        .get_opt_key("maxPerSecond")
+                        .parse_f64()
+                        .end()
+                        .and_then(|max_per_second| {
+                            if max_per_second <= 0. {
+                                err.push(
+                                    &app.cwp + "max_per_second",
+                                    eyre!("App scheduling `maxPerSecond` must be greater than 0"),
+                                );
+                                return None;
+                            }
+                            // By default allow bursts of up to one second worth of operations
+                            let burst = app
+                                .chain(&mut err)
+                                .get_opt_key("burst")
+                                .parse_u32()
+                                .unwrap_or_else(|| max_per_second.ceil() as u32)
+                                .max(1);
+                            Some(RateLimitConf {
+                                max_per_second,
+                                burst,
+                            })
+                        });
+
+                    name.map(|name| AppSchedulingConf {
+                        name: name.to_owned(),
+                        matching_list,
+                        weight,
+                        rate_limit,
+                    })
+                })
+                .collect_vec()
+            })
+            .unwrap_or_default();
+
+        let dead_letter = DeadLetterConf {
+            max_retries: p
+                .chain(&mut err)
+                .get_opt_key("maxMessageRetries")
+                .parse_u32()
+                .end(),
+            max_age: p
+                .chain(&mut err)
+                .get_opt_key("maxMessageAge")
+                .parse_u64()
+                .end()
+                .map(Duration::from_secs),
+        };
+
         err.into_result(RelayerSettings {
             base,
             db,
@@ -272,6 +550,9 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             skip_transaction_gas_limit_for,
             allow_local_checkpoint_syncers,
             metric_app_contexts,
+            app_scheduling,
+            dead_letter,
+            dry_run,
         })
     }
 }
@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use derive_new::new;
+use hyperlane_base::LoadableFromSettings;
+use hyperlane_core::config::ConfigResult;
+use tokio::{sync::RwLock, task::JoinHandle};
+use tracing::{error, info, info_span, instrument::Instrumented, Instrument};
+
+use crate::{
+    msg::gas_payment::GasPaymentPolicies,
+    settings::{GasPaymentEnforcementConf, MessageFilters, RelayerSettings},
+};
+
+/// The parts of the relayer config that can be reloaded at runtime. They are
+/// replaced as one value, so a message is never checked against the filters of
+/// one config and the gas payment policies of another.
+#[derive(Debug, Default)]
+pub struct ReloadableConf {
+    /// Whitelist, blacklist and metric app contexts
+    pub message_filters: MessageFilters,
+    /// Shared so the policies can be evaluated without holding the lock
+    pub gas_payment_policies: Arc<GasPaymentPolicies>,
+}
+
+impl ReloadableConf {
+    pub fn new(
+        message_filters: MessageFilters,
+        gas_payment_enforcement: impl IntoIterator<Item = GasPaymentEnforcementConf>,
+    ) -> Self {
+        Self {
+            message_filters,
+            gas_payment_policies: Arc::new(GasPaymentPolicies::new(gas_payment_enforcement)),
+        }
+    }
+}
+
+/// Re-reads the relayer config and swaps the message filters and gas payment
+/// policies into the running relayer. If the new config fails to parse, the
+/// previous config is kept.
+#[derive(new, Clone)]
+pub struct SettingsReloader {
+    /// Shared with every message processor, message context and gas payment
+    /// enforcer
+    conf: Arc<RwLock<ReloadableConf>>,
+}
+
+impl SettingsReloader {
+    /// Load the config from the same sources used at startup and apply it.
+    pub async fn reload(&self) -> ConfigResult<()> {
+        let settings = match RelayerSettings::load() {
+            Ok(settings) => settings,
+            Err(err) => {
+                error!(error = %err, "Failed to reload relayer settings, keeping previous config");
+                return Err(err);
+            }
+        };
+        self.apply(
+            settings.message_filters(),
+            settings.gas_payment_enforcement.clone(),
+        )
+        .await;
+        Ok(())
+    }
+
+    async fn apply(
+        &self,
+        message_filters: MessageFilters,
+        gas_payment_enforcement: Vec<GasPaymentEnforcementConf>,
+    ) {
+        info!(
+            whitelist = %message_filters.whitelist,
+            blacklist = %message_filters.blacklist,
+            gas_enforcement_policies = ?gas_payment_enforcement,
+            "Reloaded relayer settings"
+        );
+        let conf = ReloadableConf::new(message_filters, gas_payment_enforcement);
+        *self.conf.write().await = conf;
+    }
+
+    /// Reload the settings whenever the process receives a SIGHUP.
+    #[cfg(unix)]
+    pub fn spawn_sighup_listener(self) -> Instrumented<JoinHandle<()>> {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        tokio::spawn(async move {
+            let mut hangups = match signal(SignalKind::hangup()) {
+                Ok(hangups) => hangups,
+                Err(err) => {
+                    error!(error = ?err, "Failed to listen for SIGHUP, settings can only be reloaded via the server");
+                    return;
+                }
+            };
+            while hangups.recv().await.is_some() {
+                info!("Received SIGHUP, reloading relayer settings");
+                // Errors are logged in `reload`
+                let _ = self.reload().await;
+            }
+        })
+        .instrument(info_span!("SettingsReloader"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::matching_list::MatchingList;
+    use hyperlane_core::{HyperlaneMessage, H256};
+
+    #[tokio::test]
+    async fn test_apply_swaps_message_filters() {
+        let conf = Arc::new(RwLock::new(ReloadableConf::default()));
+        let reloader = SettingsReloader::new(conf.clone());
+
+        let sender = H256::random();
+        let msg = HyperlaneMessage {
+            sender,
+            ..Default::default()
+        };
+        assert!(conf.read().await.message_filters.allows(&msg));
+
+        let blacklist: MatchingList =
+            serde_json::from_str(&format!(r#"[{{"senderaddress": "{:?}"}}]"#, sender)).unwrap();
+        reloader
+            .apply(
+                MessageFilters {
+                    blacklist,
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await;
+
+        assert!(!conf.read().await.message_filters.allows(&msg));
+    }
+}
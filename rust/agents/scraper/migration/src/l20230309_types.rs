@@ -1,3 +1,4 @@
+use sea_orm::DbBackend;
 use sea_orm_migration::prelude::*;
 
 /// Hashes are to be stored as binary.
@@ -10,5 +11,92 @@ pub const Address: ColumnType = ColumnType::Binary(BlobSize::Tiny);
 /// 256-bit integer as base-10 digits: ceil(log_10(2^256))
 const SIGNIFICANT_DIGITS_IN_256_BIT_INTEGER: u32 = 78;
 /// A type to represent a U256 crypto currency Wei value.
+///
+/// SQLite has no arbitrary precision decimals, so there the scraper stores
+/// these values as blobs of their base-10 digits, which the column's numeric
+/// affinity leaves untouched. Views aggregating them are still floating point
+/// there.
 #[allow(non_upper_case_globals)]
 pub const Wei: ColumnType = ColumnType::Decimal(Some((SIGNIFICANT_DIGITS_IN_256_BIT_INTEGER, 0)));
+
+// The helpers below are used by migrations that were already applied to
+// Postgres databases, so for Postgres they must produce the exact DDL those
+// migrations were written with.
+
+/// An auto incrementing 64-bit primary key. SQLite only auto increments
+/// `INTEGER` primary keys, which are 64 bits wide there anyway.
+pub fn id_column(backend: DbBackend, id: impl IntoIden) -> ColumnDef {
+    let mut col = ColumnDef::new(id);
+    match backend {
+        DbBackend::Sqlite => col.integer(),
+        _ => col.big_integer(),
+    }
+    .not_null()
+    .auto_increment()
+    .primary_key();
+    col
+}
+
+/// Default value for a creation timestamp column.
+pub fn current_timestamp(backend: DbBackend) -> SimpleExpr {
+    match backend {
+        DbBackend::Sqlite => Expr::cust("CURRENT_TIMESTAMP"),
+        _ => "NOW()".into(),
+    }
+}
+
+/// SQL for the time elapsed between two timestamp expressions. SQLite has no
+/// interval type, so there it is the number of seconds instead.
+pub fn timestamp_diff(backend: DbBackend, later: &str, earlier: &str) -> String {
+    match backend {
+        DbBackend::Sqlite => format!("((julianday({later}) - julianday({earlier})) * 86400)"),
+        _ => format!("{later} - {earlier}"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn helpers_keep_postgres_ddl_unchanged() {
+        let original = Table::create()
+            .table(Alias::new("block"))
+            .if_not_exists()
+            .col(
+                ColumnDef::new(Alias::new("id"))
+                    .big_integer()
+                    .not_null()
+                    .auto_increment()
+                    .primary_key(),
+            )
+            .col(
+                ColumnDef::new(Alias::new("time_created"))
+                    .timestamp()
+                    .not_null()
+                    .default("NOW()"),
+            )
+            .to_string(PostgresQueryBuilder);
+        let with_helpers = Table::create()
+            .table(Alias::new("block"))
+            .if_not_exists()
+            .col(&mut id_column(DbBackend::Postgres, Alias::new("id")))
+            .col(
+                ColumnDef::new(Alias::new("time_created"))
+                    .timestamp()
+                    .not_null()
+                    .default(current_timestamp(DbBackend::Postgres)),
+            )
+            .to_string(PostgresQueryBuilder);
+        assert_eq!(with_helpers, original);
+
+        assert_eq!(
+            timestamp_diff(
+                DbBackend::Postgres,
+                r#""dest_block"."timestamp""#,
+                r#""origin_block"."timestamp""#
+            ),
+            r#""dest_block"."timestamp" - "origin_block"."timestamp""#
+        );
+    }
+}
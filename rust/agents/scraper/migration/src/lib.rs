@@ -13,6 +13,9 @@ mod m20230309_000003_create_table_transaction;
 mod m20230309_000004_create_table_delivered_message;
 mod m20230309_000004_create_table_gas_payment;
 mod m20230309_000005_create_table_message;
+mod m20261018_000004_add_cursor_kind;
+mod m20261018_000004_create_table_merkle_tree_insertion;
+mod m20261018_000004_create_table_validator_announcement;
 
 pub struct Migrator;
 
@@ -30,6 +33,11 @@ impl MigratorTrait for Migrator {
             Box::new(m20230309_000004_create_table_gas_payment::Migration),
             Box::new(m20230309_000004_create_table_delivered_message::Migration),
             Box::new(m20230309_000005_create_table_message::Migration),
+            // Added after the initial schema, so these run last to keep the
+            // order of the migrations that were already applied
+            Box::new(m20261018_000004_create_table_merkle_tree_insertion::Migration),
+            Box::new(m20261018_000004_create_table_validator_announcement::Migration),
+            Box::new(m20261018_000004_add_cursor_kind::Migration),
         ]
     }
 }
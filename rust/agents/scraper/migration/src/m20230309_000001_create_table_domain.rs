@@ -3,6 +3,8 @@ use time::OffsetDateTime;
 
 use sea_orm_migration::prelude::*;
 
+use crate::l20230309_types::current_timestamp;
+
 /// List of domain data we want to initialize the database with.
 ///
 /// This needs to be immutable because once we create the tables, we need to
@@ -253,7 +255,7 @@ impl MigrationTrait for Migration {
                         ColumnDef::new(Domain::TimeCreated)
                             .timestamp()
                             .not_null()
-                            .default("NOW()"),
+                            .default(current_timestamp(manager.get_database_backend())),
                     )
                     .col(ColumnDef::new(Domain::TimeUpdated).timestamp().not_null())
                     .col(ColumnDef::new(Domain::Name).text().not_null())
@@ -14,18 +14,12 @@ impl MigrationTrait for Migration {
                 Table::create()
                     .table(Block::Table)
                     .if_not_exists()
-                    .col(
-                        ColumnDef::new(Block::Id)
-                            .big_integer()
-                            .not_null()
-                            .auto_increment()
-                            .primary_key(),
-                    )
+                    .col(&mut id_column(manager.get_database_backend(), Block::Id))
                     .col(
                         ColumnDef::new(Block::TimeCreated)
                             .timestamp()
                             .not_null()
-                            .default("NOW()"),
+                            .default(current_timestamp(manager.get_database_backend())),
                     )
                     .col(ColumnDef::new(Block::Domain).unsigned().not_null())
                     .col(
@@ -1,5 +1,6 @@
 use sea_orm_migration::prelude::*;
 
+use crate::l20230309_types::*;
 use crate::m20230309_000001_create_table_domain::Domain;
 
 #[derive(DeriveMigrationName)]
@@ -13,19 +14,13 @@ impl MigrationTrait for Migration {
                 Table::create()
                     .table(Cursor::Table)
                     .if_not_exists()
-                    .col(
-                        ColumnDef::new(Cursor::Id)
-                            .big_integer()
-                            .not_null()
-                            .auto_increment()
-                            .primary_key(),
-                    )
+                    .col(&mut id_column(manager.get_database_backend(), Cursor::Id))
                     .col(ColumnDef::new(Cursor::Domain).unsigned().not_null())
                     .col(
                         ColumnDef::new(Cursor::TimeCreated)
                             .timestamp()
                             .not_null()
-                            .default("NOW()"),
+                            .default(current_timestamp(manager.get_database_backend())),
                     )
                     .col(ColumnDef::new(Cursor::Height).big_unsigned().not_null())
                     .foreign_key(
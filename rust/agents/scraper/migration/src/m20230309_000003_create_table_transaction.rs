@@ -16,18 +16,15 @@ impl MigrationTrait for Migration {
                 Table::create()
                     .table(Transaction::Table)
                     .if_not_exists()
-                    .col(
-                        ColumnDef::new(Transaction::Id)
-                            .big_integer()
-                            .not_null()
-                            .auto_increment()
-                            .primary_key(),
-                    )
+                    .col(&mut id_column(
+                        manager.get_database_backend(),
+                        Transaction::Id,
+                    ))
                     .col(
                         ColumnDef::new(Transaction::TimeCreated)
                             .timestamp()
                             .not_null()
-                            .default("NOW()"),
+                            .default(current_timestamp(manager.get_database_backend())),
                     )
                     .col(
                         ColumnDef::new_with_type(Transaction::Hash, Hash)
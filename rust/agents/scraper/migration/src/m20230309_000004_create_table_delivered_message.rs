@@ -15,18 +15,15 @@ impl MigrationTrait for Migration {
                 Table::create()
                     .table(DeliveredMessage::Table)
                     .if_not_exists()
-                    .col(
-                        ColumnDef::new(DeliveredMessage::Id)
-                            .big_integer()
-                            .not_null()
-                            .auto_increment()
-                            .primary_key(),
-                    )
+                    .col(&mut id_column(
+                        manager.get_database_backend(),
+                        DeliveredMessage::Id,
+                    ))
                     .col(
                         ColumnDef::new(DeliveredMessage::TimeCreated)
                             .timestamp()
                             .not_null()
-                            .default("NOW()"),
+                            .default(current_timestamp(manager.get_database_backend())),
                     )
                     .col(
                         ColumnDef::new_with_type(DeliveredMessage::MsgId, Hash)
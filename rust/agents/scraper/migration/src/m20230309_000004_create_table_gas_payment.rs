@@ -16,18 +16,15 @@ impl MigrationTrait for Migration {
                 Table::create()
                     .table(GasPayment::Table)
                     .if_not_exists()
-                    .col(
-                        ColumnDef::new(GasPayment::Id)
-                            .big_integer()
-                            .not_null()
-                            .auto_increment()
-                            .primary_key(),
-                    )
+                    .col(&mut id_column(
+                        manager.get_database_backend(),
+                        GasPayment::Id,
+                    ))
                     .col(
                         ColumnDef::new(GasPayment::TimeCreated)
                             .timestamp()
                             .not_null()
-                            .default("NOW()"),
+                            .default(current_timestamp(manager.get_database_backend())),
                     )
                     .col(ColumnDef::new(GasPayment::Domain).unsigned().not_null())
                     .col(ColumnDef::new_with_type(GasPayment::MsgId, Hash).not_null())
@@ -19,18 +19,12 @@ impl MigrationTrait for Migration {
                 Table::create()
                     .table(Message::Table)
                     .if_not_exists()
-                    .col(
-                        ColumnDef::new(Message::Id)
-                            .big_integer()
-                            .not_null()
-                            .auto_increment()
-                            .primary_key(),
-                    )
+                    .col(&mut id_column(manager.get_database_backend(), Message::Id))
                     .col(
                         ColumnDef::new(Message::TimeCreated)
                             .timestamp()
                             .not_null()
-                            .default("NOW()"),
+                            .default(current_timestamp(manager.get_database_backend())),
                     )
                     .col(ColumnDef::new_with_type(Message::MsgId, Hash).not_null())
                     .col(ColumnDef::new(Message::Origin).unsigned().not_null())
@@ -91,6 +85,21 @@ impl MigrationTrait for Migration {
                     .to_owned(),
             )
             .await?;
+
+        let backend = manager.get_database_backend();
+        let origin_timestamp = format!(r#""origin_block"."{}""#, Block::Timestamp.to_string());
+        let dest_timestamp = format!(r#""dest_block"."{}""#, Block::Timestamp.to_string());
+        let delivery_latency = timestamp_diff(backend, &dest_timestamp, &origin_timestamp);
+        let send_scrape_latency = timestamp_diff(
+            backend,
+            &format!(r#""msg"."{}""#, Message::TimeCreated.to_string()),
+            &origin_timestamp,
+        );
+        let delivery_scrape_latency = timestamp_diff(
+            backend,
+            &format!(r#""dmsg"."{}""#, DeliveredMessage::TimeCreated.to_string()),
+            &dest_timestamp,
+        );
         let sql = format!(
             r#"
             CREATE VIEW "{msg_table}_view" AS
@@ -117,9 +126,9 @@ impl MigrationTrait for Migration {
                 "origin_block"."{block_timestamp}" AS "send_occurred_at",
                 "dmsg"."{dmsg_time_created}" AS "delivery_scraped_at",
                 "dest_block"."{block_timestamp}" AS "delivery_occurred_at",
-                "dest_block"."{block_timestamp}" - "origin_block"."{block_timestamp}" AS "delivery_latency",
-                "msg"."{msg_time_created}" - "origin_block"."{block_timestamp}" AS "send_scape_latency",
-                "dmsg"."{dmsg_time_created}" - "dest_block"."{block_timestamp}" AS "delivery_scape_latency",
+                {delivery_latency} AS "delivery_latency",
+                {send_scrape_latency} AS "send_scape_latency",
+                {delivery_scrape_latency} AS "delivery_scape_latency",
 
                 "msg"."{msg_sender}" AS "sender",
                 "msg"."{msg_recipient}" AS "recipient",
@@ -0,0 +1,47 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Cursor::Table)
+                    .add_column(
+                        ColumnDef::new(Cursor::Kind)
+                            .text()
+                            .not_null()
+                            .default(CURSOR_KIND_LOGS),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Cursor::Table)
+                    .drop_column(Cursor::Kind)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Kind of the cursors that existed before kinds were added, which is shared
+/// by every log except validator announcements
+const CURSOR_KIND_LOGS: &str = "logs";
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Cursor {
+    Table,
+    /// What the cursor tracks, so one domain can have several independent
+    /// cursors
+    Kind,
+}
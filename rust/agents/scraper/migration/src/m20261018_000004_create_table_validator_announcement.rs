@@ -0,0 +1,172 @@
+use sea_orm::ConnectionTrait;
+use sea_orm_migration::prelude::*;
+
+use crate::l20230309_types::*;
+use crate::m20230309_000001_create_table_domain::Domain;
+use crate::m20230309_000002_create_table_block::Block;
+use crate::m20230309_000003_create_table_transaction::Transaction;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ValidatorAnnouncement::Table)
+                    .if_not_exists()
+                    .col(&mut id_column(
+                        manager.get_database_backend(),
+                        ValidatorAnnouncement::Id,
+                    ))
+                    .col(
+                        ColumnDef::new(ValidatorAnnouncement::TimeCreated)
+                            .timestamp()
+                            .not_null()
+                            .default(current_timestamp(manager.get_database_backend())),
+                    )
+                    .col(
+                        ColumnDef::new(ValidatorAnnouncement::Domain)
+                            .unsigned()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new_with_type(ValidatorAnnouncement::ValidatorAnnounce, Address)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new_with_type(ValidatorAnnouncement::Validator, Address)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ValidatorAnnouncement::StorageLocation)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ValidatorAnnouncement::TxId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ValidatorAnnouncement::LogIndex)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from_col(ValidatorAnnouncement::Domain)
+                            .to(Domain::Table, Domain::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from_col(ValidatorAnnouncement::TxId)
+                            .to(Transaction::Table, Transaction::Id),
+                    )
+                    .index(
+                        Index::create()
+                            // don't need domain because TxId includes it
+                            .col(ValidatorAnnouncement::TxId)
+                            .col(ValidatorAnnouncement::LogIndex)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .table(ValidatorAnnouncement::Table)
+                    .name("validator_announcement_validator_idx")
+                    .col(ValidatorAnnouncement::Validator)
+                    .index_type(IndexType::Hash)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                r#"
+            CREATE VIEW "{vsl_table}" AS
+            SELECT
+                "va"."{va_domain}" AS "{vsl_domain}",
+                "va"."{va_validator}" AS "{vsl_validator}",
+                "va"."{va_storage_location}" AS "{vsl_storage_location}",
+                MIN("block"."{block_timestamp}") AS "{vsl_first_seen}"
+            FROM "{va_table}" AS "va"
+                INNER JOIN "{txn_table}" AS "txn" ON "txn"."{txn_id}" = "va"."{va_tx_id}"
+                INNER JOIN "{block_table}" AS "block" ON "block"."{block_id}" = "txn"."{txn_block_id}"
+            GROUP BY "va"."{va_domain}", "va"."{va_validator}", "va"."{va_storage_location}"
+            "#,
+                va_table = ValidatorAnnouncement::Table.to_string(),
+                va_domain = ValidatorAnnouncement::Domain.to_string(),
+                va_validator = ValidatorAnnouncement::Validator.to_string(),
+                va_storage_location = ValidatorAnnouncement::StorageLocation.to_string(),
+                va_tx_id = ValidatorAnnouncement::TxId.to_string(),
+                txn_table = Transaction::Table.to_string(),
+                txn_id = Transaction::Id.to_string(),
+                txn_block_id = Transaction::BlockId.to_string(),
+                block_table = Block::Table.to_string(),
+                block_id = Block::Id.to_string(),
+                block_timestamp = Block::Timestamp.to_string(),
+                vsl_table = ValidatorStorageLocation::Table.to_string(),
+                vsl_domain = ValidatorStorageLocation::Domain.to_string(),
+                vsl_validator = ValidatorStorageLocation::Validator.to_string(),
+                vsl_storage_location = ValidatorStorageLocation::StorageLocation.to_string(),
+                vsl_first_seen = ValidatorStorageLocation::FirstSeen.to_string(),
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                r#"DROP VIEW IF EXISTS "{}""#,
+                ValidatorStorageLocation::Table.to_string()
+            ))
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ValidatorAnnouncement::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum ValidatorAnnouncement {
+    Table,
+    /// Unique database ID
+    Id,
+    /// Time of record creation
+    TimeCreated,
+    /// Domain ID of the chain the announcement was made on
+    Domain,
+    /// Address of the validator announce contract
+    ValidatorAnnounce,
+    /// Address of the validator which made the announcement
+    Validator,
+    /// The announced location of signed checkpoints
+    StorageLocation,
+    /// Transaction the announcement was made in
+    TxId,
+    /// Index of the announcement event within the block
+    LogIndex,
+}
+
+/// Every storage location a validator has announced and when it was first
+/// seen on chain
+#[derive(Iden)]
+pub enum ValidatorStorageLocation {
+    Table,
+    Domain,
+    Validator,
+    StorageLocation,
+    FirstSeen,
+}
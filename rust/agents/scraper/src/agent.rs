@@ -1,17 +1,28 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, ops::RangeInclusive, sync::Arc};
 
 use async_trait::async_trait;
 use derive_more::AsRef;
-use futures::future::try_join_all;
+use futures::future::{join_all, try_join_all};
 use hyperlane_base::{
     metrics::AgentMetrics, settings::IndexSettings, BaseAgent, ChainMetrics, ContractSyncMetrics,
     ContractSyncer, CoreMetrics, HyperlaneAgentCore, MetricsUpdater,
 };
-use hyperlane_core::{Delivery, HyperlaneDomain, HyperlaneMessage, InterchainGasPayment};
+use hyperlane_core::{
+    Delivery, HyperlaneDomain, HyperlaneMessage, IndexMode, InterchainGasPayment,
+    MerkleTreeInsertion, ValidatorAnnouncement,
+};
+use prometheus::HistogramVec;
 use tokio::task::JoinHandle;
-use tracing::{info_span, instrument::Instrumented, trace, Instrument};
+use tracing::{error, info, info_span, instrument::Instrumented, trace, Instrument};
 
-use crate::{chain_scraper::HyperlaneSqlDb, db::ScraperDb, settings::ScraperSettings};
+use crate::{
+    chain_scraper::{
+        indexes_validator_announcements, Backfill, ChainIndexers, HyperlaneSqlDb, ReorgWatcher,
+    },
+    db::ScraperDb,
+    server as scraper_server,
+    settings::{BackfillSettings, ScraperSettings},
+};
 
 /// A message explorer scraper agent
 #[derive(Debug, AsRef)]
@@ -25,6 +36,8 @@ pub struct Scraper {
     core_metrics: Arc<CoreMetrics>,
     agent_metrics: AgentMetrics,
     chain_metrics: ChainMetrics,
+    reorg_depth: HistogramVec,
+    db: ScraperDb,
 }
 
 #[derive(Debug)]
@@ -59,6 +72,8 @@ impl BaseAgent for Scraper {
             let db = HyperlaneSqlDb::new(
                 db.clone(),
                 chain_setup.addresses.mailbox,
+                chain_setup.addresses.merkle_tree_hook,
+                chain_setup.addresses.validator_announce,
                 domain.clone(),
                 settings
                     .build_provider(domain, &metrics.clone())
@@ -79,6 +94,13 @@ impl BaseAgent for Scraper {
 
         trace!(domain_count = scrapers.len(), "Created scrapers");
 
+        let reorg_depth = metrics.new_histogram(
+            "scraper_reorg_depth",
+            "Depth in blocks of reorgs that removed already scraped blocks",
+            &["chain"],
+            vec![1., 2., 4., 8., 16., 32., 64., 128., 256.],
+        )?;
+
         Ok(Self {
             core,
             contract_sync_metrics,
@@ -87,6 +109,8 @@ impl BaseAgent for Scraper {
             core_metrics: metrics,
             agent_metrics,
             chain_metrics,
+            reorg_depth,
+            db,
         })
     }
 
@@ -100,7 +124,20 @@ impl BaseAgent for Scraper {
             .settings
             .server(self.core_metrics.clone())
             .expect("Failed to create server");
-        let server_task = server.run().instrument(info_span!("Relayer server"));
+        let server_task = if self.settings.enable_api {
+            server.run_with_custom_routes(scraper_server::routes(self.db.clone()))
+        } else {
+            server.run()
+        }
+        .instrument(info_span!("Scraper server"));
+
+        if let Some(backfill) = self.settings.backfill.clone() {
+            // The server keeps running until the backfill is done and the
+            // agent exits
+            let _server_task = server_task;
+            self.backfill(backfill).await;
+            return;
+        }
         tasks.push(server_task);
 
         for (domain, scraper) in self.scrapers.iter() {
@@ -133,7 +170,7 @@ impl Scraper {
         let index_settings = scraper.index_settings.clone();
         let domain = scraper.domain.clone();
 
-        let mut tasks = Vec::with_capacity(2);
+        let mut tasks = Vec::with_capacity(6);
         tasks.push(
             self.build_message_indexer(
                 domain.clone(),
@@ -156,14 +193,39 @@ impl Scraper {
         );
         tasks.push(
             self.build_interchain_gas_payment_indexer(
-                domain,
+                domain.clone(),
                 self.core_metrics.clone(),
                 self.contract_sync_metrics.clone(),
-                db,
+                db.clone(),
                 index_settings.clone(),
             )
             .await,
         );
+        tasks.push(
+            self.build_merkle_tree_insertion_indexer(
+                domain.clone(),
+                self.core_metrics.clone(),
+                self.contract_sync_metrics.clone(),
+                db.clone(),
+                index_settings.clone(),
+            )
+            .await,
+        );
+        if let Some(task) = self
+            .build_validator_announcement_indexer(
+                domain.clone(),
+                self.core_metrics.clone(),
+                self.contract_sync_metrics.clone(),
+                db.clone(),
+                index_settings.clone(),
+            )
+            .await
+        {
+            tasks.push(task);
+        }
+        if matches!(index_settings.mode, IndexMode::Block) {
+            tasks.push(self.build_reorg_watcher(domain, db, index_settings).await);
+        }
 
         tokio::spawn(async move {
             // If any of the tasks panic, we want to propagate it, so we unwrap
@@ -171,6 +233,26 @@ impl Scraper {
         })
         .instrument(info_span!("Scraper Tasks"))
     }
+
+    /// Scrape the configured range of every backfilled chain and wait until
+    /// they are all done. Live syncing is not started.
+    async fn backfill(&self, settings: BackfillSettings) {
+        let mut tasks = Vec::with_capacity(settings.ranges.len());
+        for (domain, range) in settings.ranges {
+            tasks.push(
+                self.build_backfill(domain, range, settings.concurrency, settings.batch_size)
+                    .await,
+            );
+        }
+        for result in join_all(tasks).await {
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => error!(?err, "Backfill failed"),
+                Err(err) => error!(?err, "Backfill task panicked"),
+            }
+        }
+        info!("Backfill finished");
+    }
 }
 
 impl Scraper {
@@ -250,4 +332,121 @@ impl Scraper {
         tokio::spawn(async move { sync.sync(label, cursor).await })
             .instrument(info_span!("ChainContractSync", chain=%domain.name(), event=label))
     }
+
+    async fn build_merkle_tree_insertion_indexer(
+        &self,
+        domain: HyperlaneDomain,
+        metrics: Arc<CoreMetrics>,
+        contract_sync_metrics: Arc<ContractSyncMetrics>,
+        db: HyperlaneSqlDb,
+        index_settings: IndexSettings,
+    ) -> Instrumented<JoinHandle<()>> {
+        let sync = self
+            .as_ref()
+            .settings
+            .sequenced_contract_sync::<MerkleTreeInsertion, _>(
+                &domain,
+                &metrics.clone(),
+                &contract_sync_metrics.clone(),
+                db.into(),
+            )
+            .await
+            .unwrap();
+
+        let label = "merkle_tree_insertion";
+        let cursor = sync.cursor(index_settings.clone()).await;
+        tokio::spawn(async move { sync.sync(label, cursor).await })
+            .instrument(info_span!("ChainContractSync", chain=%domain.name(), event=label))
+    }
+
+    /// Not every chain emits validator announcements as events, so this
+    /// returns `None` if they can't be indexed on `domain`. Failing to build
+    /// the indexer is logged and also returns `None`, so the other indexers
+    /// keep running.
+    async fn build_validator_announcement_indexer(
+        &self,
+        domain: HyperlaneDomain,
+        metrics: Arc<CoreMetrics>,
+        contract_sync_metrics: Arc<ContractSyncMetrics>,
+        db: HyperlaneSqlDb,
+        index_settings: IndexSettings,
+    ) -> Option<Instrumented<JoinHandle<()>>> {
+        if !indexes_validator_announcements(&domain) {
+            info!(
+                chain=%domain.name(),
+                "Validator announcements are not emitted as events, not indexing them"
+            );
+            return None;
+        }
+        let sync = match self
+            .as_ref()
+            .settings
+            .watermark_contract_sync::<ValidatorAnnouncement, _>(
+                &domain,
+                &metrics.clone(),
+                &contract_sync_metrics.clone(),
+                Arc::new(db),
+            )
+            .await
+        {
+            Ok(sync) => sync,
+            Err(err) => {
+                error!(
+                    ?err,
+                    chain=%domain.name(),
+                    "Failed to build validator announcement indexer"
+                );
+                return None;
+            }
+        };
+
+        let label = "validator_announcement";
+        let cursor = sync.cursor(index_settings.clone()).await;
+        Some(
+            tokio::spawn(async move { sync.sync(label, cursor).await })
+                .instrument(info_span!("ChainContractSync", chain=%domain.name(), event=label)),
+        )
+    }
+
+    async fn build_reorg_watcher(
+        &self,
+        domain: HyperlaneDomain,
+        db: HyperlaneSqlDb,
+        index_settings: IndexSettings,
+    ) -> Instrumented<JoinHandle<()>> {
+        let chain_setup = self.settings.chain_setup(&domain).unwrap();
+        let indexers = ChainIndexers::build(chain_setup, &self.core_metrics)
+            .await
+            .unwrap();
+        let watcher = ReorgWatcher::new(
+            db,
+            indexers,
+            index_settings.chunk_size,
+            self.reorg_depth.with_label_values(&[domain.name()]),
+        );
+        tokio::spawn(watcher.run()).instrument(info_span!("ReorgWatcher", chain=%domain.name()))
+    }
+
+    async fn build_backfill(
+        &self,
+        domain: HyperlaneDomain,
+        range: RangeInclusive<u32>,
+        concurrency: usize,
+        batch_size: usize,
+    ) -> Instrumented<JoinHandle<eyre::Result<()>>> {
+        let scraper = self.scrapers.get(&domain.id()).unwrap();
+        let chain_setup = self.settings.chain_setup(&domain).unwrap();
+        let indexers = ChainIndexers::build(chain_setup, &self.core_metrics)
+            .await
+            .unwrap();
+        let backfill = Backfill::new(
+            scraper.db.clone(),
+            indexers,
+            range,
+            scraper.index_settings.chunk_size,
+            concurrency,
+            batch_size,
+        );
+        tokio::spawn(backfill.run()).instrument(info_span!("Backfill", chain=%domain.name()))
+    }
 }
@@ -0,0 +1,95 @@
+use std::{ops::RangeInclusive, time::Duration};
+
+use derive_new::new;
+use eyre::Result;
+use futures::{stream, StreamExt};
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+use super::{chunk_ranges, indexers::ChainLogs, ChainIndexers, HyperlaneSqlDb};
+
+/// How many times fetching or storing a batch is attempted before giving up
+const MAX_ATTEMPTS: usize = 5;
+
+/// Delay before retrying a failed attempt
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Scrapes a fixed range of a chain's history and returns once it is done.
+///
+/// Chunks are fetched concurrently and the logs of several chunks are stored
+/// together. The cursor of the live scraper is never touched and storing is
+/// idempotent, so a backfill can run next to a live scraper writing to the
+/// same database.
+#[derive(Debug, new)]
+pub struct Backfill {
+    db: HyperlaneSqlDb,
+    indexers: ChainIndexers,
+    /// Block heights for block-indexed chains, sequences otherwise
+    range: RangeInclusive<u32>,
+    chunk_size: u32,
+    /// Number of chunks fetched at once
+    concurrency: usize,
+    /// Number of chunks whose logs are stored together
+    batch_size: usize,
+}
+
+impl Backfill {
+    pub async fn run(self) -> Result<()> {
+        let (from, to) = (*self.range.start(), *self.range.end());
+        info!(from, to, "Starting backfill");
+
+        // `buffered` yields chunks in order, so everything below the end of
+        // the last stored batch has been backfilled
+        let mut batches = stream::iter(chunk_ranges(self.range.clone(), self.chunk_size))
+            .map(|range| self.fetch_chunk(range))
+            .buffered(self.concurrency.max(1))
+            .chunks(self.batch_size.max(1));
+        while let Some(batch) = batches.next().await {
+            let mut logs = ChainLogs::default();
+            let mut height = from;
+            for chunk in batch {
+                let (range, chunk_logs) = chunk?;
+                logs.extend(chunk_logs);
+                height = *range.end();
+            }
+            self.store_batch(&logs).await?;
+            info!(height, to, stored = logs.len(), "Backfilled batch");
+        }
+
+        info!(from, to, "Finished backfill");
+        Ok(())
+    }
+
+    async fn fetch_chunk(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> Result<(RangeInclusive<u32>, ChainLogs)> {
+        let mut attempt = 1;
+        loop {
+            match self.indexers.fetch_logs(range.clone()).await {
+                Ok(logs) => return Ok((range, logs)),
+                Err(err) if attempt < MAX_ATTEMPTS => {
+                    warn!(?err, ?range, attempt, "Failed to fetch logs, retrying");
+                }
+                Err(err) => return Err(err),
+            }
+            attempt += 1;
+            sleep(RETRY_DELAY).await;
+        }
+    }
+
+    async fn store_batch(&self, logs: &ChainLogs) -> Result<()> {
+        let mut attempt = 1;
+        loop {
+            match logs.store(&self.db).await {
+                Ok(()) => return Ok(()),
+                Err(err) if attempt < MAX_ATTEMPTS => {
+                    warn!(?err, attempt, "Failed to store logs, retrying");
+                }
+                Err(err) => return Err(err),
+            }
+            attempt += 1;
+            sleep(RETRY_DELAY).await;
+        }
+    }
+}
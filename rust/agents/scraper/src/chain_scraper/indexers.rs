@@ -0,0 +1,113 @@
+use std::{ops::RangeInclusive, sync::Arc};
+
+use eyre::Result;
+use hyperlane_base::{settings::ChainConf, CoreMetrics};
+use hyperlane_core::{
+    Delivery, HyperlaneMessage, Indexed, InterchainGasPayment, LogMeta, MerkleTreeInsertion,
+    SequenceAwareIndexer, ValidatorAnnouncement,
+};
+
+use super::{indexes_validator_announcements, HyperlaneSqlDb};
+
+/// Indexers for every kind of log the scraper stores, used to index ranges
+/// outside of the contract syncs.
+#[derive(Debug, Clone)]
+pub struct ChainIndexers {
+    message: Arc<dyn SequenceAwareIndexer<HyperlaneMessage>>,
+    delivery: Arc<dyn SequenceAwareIndexer<Delivery>>,
+    igp: Arc<dyn SequenceAwareIndexer<InterchainGasPayment>>,
+    merkle_tree_hook: Arc<dyn SequenceAwareIndexer<MerkleTreeInsertion>>,
+    /// Not every chain emits validator announcements as events
+    validator_announce: Option<Arc<dyn SequenceAwareIndexer<ValidatorAnnouncement>>>,
+}
+
+impl ChainIndexers {
+    pub async fn build(chain_setup: &ChainConf, metrics: &CoreMetrics) -> Result<Self> {
+        Ok(Self {
+            message: chain_setup.build_message_indexer(metrics).await?.into(),
+            delivery: chain_setup.build_delivery_indexer(metrics).await?.into(),
+            igp: chain_setup
+                .build_interchain_gas_payment_indexer(metrics)
+                .await?
+                .into(),
+            merkle_tree_hook: chain_setup
+                .build_merkle_tree_hook_indexer(metrics)
+                .await?
+                .into(),
+            validator_announce: if indexes_validator_announcements(&chain_setup.domain) {
+                Some(
+                    chain_setup
+                        .build_validator_announce_indexer(metrics)
+                        .await?
+                        .into(),
+                )
+            } else {
+                None
+            },
+        })
+    }
+
+    /// Fetch the logs of every kind in `range`. The range is block heights
+    /// for block-indexed chains and sequences otherwise.
+    pub async fn fetch_logs(&self, range: RangeInclusive<u32>) -> Result<ChainLogs> {
+        let validator_announcements = match &self.validator_announce {
+            Some(indexer) => indexer.fetch_logs(range.clone()).await?,
+            None => vec![],
+        };
+        Ok(ChainLogs {
+            messages: self.message.fetch_logs(range.clone()).await?,
+            deliveries: self.delivery.fetch_logs(range.clone()).await?,
+            payments: self.igp.fetch_logs(range.clone()).await?,
+            merkle_tree_insertions: self.merkle_tree_hook.fetch_logs(range).await?,
+            validator_announcements,
+        })
+    }
+}
+
+/// Logs fetched by [`ChainIndexers`] that have not been stored yet
+#[derive(Debug, Default)]
+pub struct ChainLogs {
+    messages: Vec<(Indexed<HyperlaneMessage>, LogMeta)>,
+    deliveries: Vec<(Indexed<Delivery>, LogMeta)>,
+    payments: Vec<(Indexed<InterchainGasPayment>, LogMeta)>,
+    merkle_tree_insertions: Vec<(Indexed<MerkleTreeInsertion>, LogMeta)>,
+    validator_announcements: Vec<(Indexed<ValidatorAnnouncement>, LogMeta)>,
+}
+
+impl ChainLogs {
+    /// Add `other`'s logs so they are written together
+    pub fn extend(&mut self, other: ChainLogs) {
+        self.messages.extend(other.messages);
+        self.deliveries.extend(other.deliveries);
+        self.payments.extend(other.payments);
+        self.merkle_tree_insertions
+            .extend(other.merkle_tree_insertions);
+        self.validator_announcements
+            .extend(other.validator_announcements);
+    }
+
+    /// Total number of logs of every kind
+    pub fn len(&self) -> usize {
+        self.messages.len()
+            + self.deliveries.len()
+            + self.payments.len()
+            + self.merkle_tree_insertions.len()
+            + self.validator_announcements.len()
+    }
+
+    /// Store all the logs in a single database transaction, so either all
+    /// of them are stored or none are. Storing is idempotent, so logs that
+    /// were already stored by another scraper are skipped.
+    pub async fn store(&self, db: &HyperlaneSqlDb) -> Result<()> {
+        let _guard = db.store_lock.read().await;
+        let txn = db.db.begin().await?;
+        db.store_messages(&txn, &self.messages).await?;
+        db.store_deliveries(&txn, &self.deliveries).await?;
+        db.store_payments(&txn, &self.payments).await?;
+        db.store_merkle_tree_insertions(&txn, &self.merkle_tree_insertions)
+            .await?;
+        db.store_validator_announcements(&txn, &self.validator_announcements)
+            .await?;
+        txn.commit().await
+    }
+}
@@ -1,24 +1,35 @@
 //! This module (and children) are responsible for scraping blockchain data and
 //! keeping things updated.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, ops::RangeInclusive, sync::Arc};
 
 use async_trait::async_trait;
-use eyre::Result;
+use eyre::{bail, Result};
 use hyperlane_base::settings::IndexSettings;
 use hyperlane_core::{
-    unwrap_or_none_result, BlockInfo, Delivery, HyperlaneDomain, HyperlaneLogStore,
-    HyperlaneMessage, HyperlaneProvider, HyperlaneSequenceAwareIndexerStoreReader,
-    HyperlaneWatermarkedLogStore, Indexed, InterchainGasPayment, LogMeta, H256,
+    unwrap_or_none_result, BlockInfo, Delivery, HyperlaneDomain, HyperlaneDomainProtocol,
+    HyperlaneLogStore, HyperlaneMessage, HyperlaneProvider,
+    HyperlaneSequenceAwareIndexerStoreReader, HyperlaneWatermarkedLogStore, Indexed,
+    InterchainGasPayment, LogMeta, MerkleTreeInsertion, ValidatorAnnouncement, H256, H512,
 };
 use itertools::Itertools;
-use tracing::trace;
+use sea_orm::ConnectionTrait;
+use tokio::sync::RwLock;
+use tracing::{trace, warn};
+
+pub use backfill::Backfill;
+pub use indexers::ChainIndexers;
+pub use reorg::ReorgWatcher;
 
 use crate::db::{
-    BasicBlock, BlockCursor, ScraperDb, StorableDelivery, StorableMessage, StorablePayment,
-    StorableTxn,
+    BasicBlock, BlockCursor, CursorKind, ScraperDb, StorableDelivery, StorableMerkleTreeInsertion,
+    StorableMessage, StorablePayment, StorableTxn, StorableValidatorAnnouncement,
 };
 
+mod backfill;
+mod indexers;
+mod reorg;
+
 /// Maximum number of records to query at a time. This came about because when a
 /// lot of messages are sent in a short period of time we were ending up with a
 /// lot of data to query from the node provider between points when we would
@@ -30,10 +41,17 @@ const CHUNK_SIZE: usize = 50;
 #[derive(Clone, Debug)]
 pub struct HyperlaneSqlDb {
     mailbox_address: H256,
+    merkle_tree_hook_address: H256,
+    validator_announce_address: H256,
     domain: HyperlaneDomain,
     db: ScraperDb,
     provider: Arc<dyn HyperlaneProvider>,
     cursor: Arc<BlockCursor>,
+    validator_announcement_cursor: Arc<BlockCursor>,
+    /// Held for reading while logs are stored and for writing while reorged
+    /// blocks are deleted, so a store never links rows to a block that is
+    /// being deleted.
+    store_lock: Arc<RwLock<()>>,
 }
 
 #[allow(unused)]
@@ -41,20 +59,34 @@ impl HyperlaneSqlDb {
     pub async fn new(
         db: ScraperDb,
         mailbox_address: H256,
+        merkle_tree_hook_address: H256,
+        validator_announce_address: H256,
         domain: HyperlaneDomain,
         provider: Arc<dyn HyperlaneProvider>,
         index_settings: &IndexSettings,
     ) -> Result<Self> {
         let cursor = Arc::new(
-            db.block_cursor(domain.id(), index_settings.from as u64)
+            db.block_cursor(domain.id(), CursorKind::Logs, index_settings.from as u64)
                 .await?,
         );
+        let validator_announcement_cursor = Arc::new(
+            db.block_cursor(
+                domain.id(),
+                CursorKind::ValidatorAnnouncements,
+                index_settings.from as u64,
+            )
+            .await?,
+        );
         Ok(Self {
             db,
             domain,
             provider,
             mailbox_address,
+            merkle_tree_hook_address,
+            validator_announce_address,
             cursor,
+            validator_announcement_cursor,
+            store_lock: Default::default(),
         })
     }
 
@@ -62,6 +94,52 @@ impl HyperlaneSqlDb {
         &self.domain
     }
 
+    /// The height up to which logs have been indexed
+    pub async fn cursor_height(&self) -> u64 {
+        self.cursor.height().await
+    }
+
+    /// Checks the stored blocks within `depth` blocks of the cursor against
+    /// the chain. Blocks that are no longer canonical are deleted along with
+    /// everything indexed from them.
+    ///
+    /// Returns the height of the lowest block that was reorged out, if any.
+    pub async fn remove_reorged_blocks(&self, depth: u64) -> Result<Option<u64>> {
+        let min_height = self.cursor_height().await.saturating_sub(depth);
+        let stored = self
+            .db
+            .get_blocks_since(self.domain.id(), min_height)
+            .await?;
+
+        let mut orphaned = Vec::new();
+        for block in stored {
+            // Every block above the fork point is orphaned, so there is no
+            // need to fetch them once one mismatch has been found
+            if orphaned.is_empty() {
+                let canonical = self.provider.get_block_by_height(block.height).await?;
+                if canonical.hash == block.hash {
+                    continue;
+                }
+                warn!(
+                    height = block.height,
+                    stored = ?block.hash,
+                    canonical = ?canonical.hash,
+                    "Found reorged block"
+                );
+            }
+            orphaned.push(block);
+        }
+
+        let Some(fork_height) = orphaned.first().map(|block| block.height) else {
+            return Ok(None);
+        };
+        let _guard = self.store_lock.write().await;
+        self.db
+            .delete_blocks(orphaned.into_iter().map(|block| block.id).collect())
+            .await?;
+        Ok(Some(fork_height))
+    }
+
     pub async fn last_message_nonce(&self) -> Result<Option<u32>> {
         self.db
             .last_message_nonce(self.domain.id(), &self.mailbox_address)
@@ -72,41 +150,38 @@ impl HyperlaneSqlDb {
     /// database. If any are not it will fetch the data and insert them.
     ///
     /// Returns the relevant transaction info.
-    async fn ensure_blocks_and_txns(
+    async fn ensure_blocks_and_txns<C: ConnectionTrait>(
         &self,
+        db: &ScraperDb<C>,
         log_meta: impl Iterator<Item = &LogMeta>,
     ) -> Result<impl Iterator<Item = TxnWithId>> {
-        let block_hash_by_txn_hash: HashMap<H256, H256> = log_meta
-            .map(|meta| {
-                (
-                    meta.transaction_id
-                        .try_into()
-                        .expect("256-bit transaction ids are the maximum supported at this time"),
-                    meta.block_hash,
-                )
-            })
+        let block_by_txn_hash: HashMap<H512, (H256, u64)> = log_meta
+            .map(|meta| (meta.transaction_id, (meta.block_hash, meta.block_number)))
             .collect();
 
         // all blocks we care about
         // hash of block maps to the block id and timestamp
         let blocks: HashMap<_, _> = self
-            .ensure_blocks(block_hash_by_txn_hash.values().copied())
+            .ensure_blocks(db, block_by_txn_hash.values().copied())
             .await?
             .map(|block| (block.hash, block))
             .collect();
         trace!(?blocks, "Ensured blocks");
 
         // all txns we care about
-        let txns_with_ids =
-            self.ensure_txns(block_hash_by_txn_hash.into_iter().map(
-                move |(txn_hash, block_hash)| {
-                    let block_info = *blocks.get(&block_hash).as_ref().unwrap();
-                    TxnWithBlockId {
-                        txn_hash,
-                        block_id: block_info.id,
-                    }
-                },
-            ))
+        let txns_with_ids = self
+            .ensure_txns(
+                db,
+                block_by_txn_hash
+                    .into_iter()
+                    .map(move |(txn_hash, (block_hash, _))| {
+                        let block_info = *blocks.get(&block_hash).as_ref().unwrap();
+                        TxnWithBlockId {
+                            txn_hash,
+                            block_id: block_info.id,
+                        }
+                    }),
+            )
             .await?;
 
         Ok(txns_with_ids.map(move |TxnWithId { hash, id: txn_id }| TxnWithId { hash, id: txn_id }))
@@ -118,17 +193,18 @@ impl HyperlaneSqlDb {
     /// if it is not in the database already:
     ///     Looks up its data with ethers and then returns the database id after
     ///     inserting it into the database.
-    async fn ensure_txns(
+    async fn ensure_txns<C: ConnectionTrait>(
         &self,
+        db: &ScraperDb<C>,
         txns: impl Iterator<Item = TxnWithBlockId>,
     ) -> Result<impl Iterator<Item = TxnWithId>> {
         // mapping of txn hash to (txn_id, block_id).
-        let mut txns: HashMap<H256, (Option<i64>, i64)> = txns
+        let mut txns: HashMap<H512, (Option<i64>, i64)> = txns
             .map(|TxnWithBlockId { txn_hash, block_id }| (txn_hash, (None, block_id)))
             .collect();
 
         let db_txns = if !txns.is_empty() {
-            self.db.get_txn_ids(txns.keys()).await?
+            db.get_txn_ids(txns.keys()).await?
         } else {
             HashMap::new()
         };
@@ -147,9 +223,9 @@ impl HyperlaneSqlDb {
         let mut txns_to_fetch = txns.iter_mut().filter(|(_, id)| id.0.is_none());
 
         let mut txns_to_insert: Vec<StorableTxn> = Vec::with_capacity(CHUNK_SIZE);
-        let mut hashes_to_insert: Vec<&H256> = Vec::with_capacity(CHUNK_SIZE);
+        let mut hashes_to_insert: Vec<&H512> = Vec::with_capacity(CHUNK_SIZE);
 
-        for mut chunk in as_chunks::<(&H256, &mut (Option<i64>, i64))>(txns_to_fetch, CHUNK_SIZE) {
+        for mut chunk in as_chunks::<(&H512, &mut (Option<i64>, i64))>(txns_to_fetch, CHUNK_SIZE) {
             for (hash, (_, block_id)) in chunk.iter() {
                 let info = self.provider.get_txn_by_hash(hash).await?;
                 hashes_to_insert.push(*hash);
@@ -159,8 +235,8 @@ impl HyperlaneSqlDb {
                 });
             }
 
-            self.db.store_txns(txns_to_insert.drain(..)).await?;
-            let ids = self.db.get_txn_ids(hashes_to_insert.drain(..)).await?;
+            db.store_txns(txns_to_insert.drain(..)).await?;
+            let ids = db.get_txn_ids(hashes_to_insert.drain(..)).await?;
 
             for (hash, (txn_id, _block_id)) in chunk.iter_mut() {
                 let _ = txn_id.insert(ids[hash]);
@@ -175,25 +251,29 @@ impl HyperlaneSqlDb {
             }))
     }
 
-    /// Takes a list of block hashes for each block
+    /// Takes a list of block hashes and heights for each block
     /// if it is in the database already:
     ///     Fetches its associated database id
     /// if it is not in the database already:
-    ///     Looks up its data with ethers and then returns the database id after
-    ///     inserting it into the database.
-    async fn ensure_blocks(
+    ///     Looks up its data by height with the provider, checks that the hash
+    ///     matches, and then returns the database id after inserting it into
+    ///     the database.
+    async fn ensure_blocks<C: ConnectionTrait>(
         &self,
-        block_hashes: impl Iterator<Item = H256>,
+        db: &ScraperDb<C>,
+        block_hashes: impl Iterator<Item = (H256, u64)>,
     ) -> Result<impl Iterator<Item = BasicBlock>> {
+        // mapping of block hash to the block height, used to fetch unknown blocks
+        let block_numbers: HashMap<H256, u64> = block_hashes.collect();
         // mapping of block hash to the database id and block timestamp. Optionals are
         // in place because we will find the timestamp first if the block was not
         // already in the db.
         let mut blocks: HashMap<H256, Option<BasicBlock>> =
-            block_hashes.map(|b| (b, None)).collect();
+            block_numbers.keys().map(|b| (*b, None)).collect();
 
         let db_blocks: Vec<BasicBlock> = if !blocks.is_empty() {
             // check database to see which blocks we already know and fetch their IDs
-            self.db.get_block_basic(blocks.keys()).await?
+            db.get_block_basic(blocks.keys()).await?
         } else {
             vec![]
         };
@@ -220,7 +300,20 @@ impl HyperlaneSqlDb {
         for chunk in as_chunks(blocks_to_fetch, CHUNK_SIZE) {
             debug_assert!(!chunk.is_empty());
             for (hash, block_info) in chunk {
-                let info = self.provider.get_block_by_hash(hash).await?;
+                // Not every chain can look up blocks by hash, but all of them
+                // can by height
+                let info = self
+                    .provider
+                    .get_block_by_height(block_numbers[hash])
+                    .await?;
+                if info.hash != *hash {
+                    bail!(
+                        "Block at height {} has hash {:?}, expected {:?}",
+                        info.number,
+                        info.hash,
+                        hash
+                    );
+                }
                 let basic_info_ref = block_info.insert(BasicBlock {
                     id: -1,
                     hash: *hash,
@@ -229,17 +322,15 @@ impl HyperlaneSqlDb {
                 hashes_to_insert.push(hash);
             }
 
-            self.db
-                .store_blocks(
-                    self.domain().id(),
-                    blocks_to_insert
-                        .iter_mut()
-                        .map(|(_, info)| info.take().unwrap()),
-                )
-                .await?;
+            db.store_blocks(
+                self.domain().id(),
+                blocks_to_insert
+                    .iter_mut()
+                    .map(|(_, info)| info.take().unwrap()),
+            )
+            .await?;
 
-            let hashes = self
-                .db
+            let hashes = db
                 .get_block_basic(hashes_to_insert.drain(..))
                 .await?
                 .into_iter()
@@ -266,61 +357,52 @@ impl HyperlaneSqlDb {
     }
 }
 
-#[async_trait]
-impl HyperlaneLogStore<HyperlaneMessage> for HyperlaneSqlDb {
+/// Storing through a [`ScraperDb`] handle lets several kinds of logs be
+/// written in one database transaction. Callers must hold the store lock.
+impl HyperlaneSqlDb {
     /// Store messages from the origin mailbox into the database.
-    async fn store_logs(&self, messages: &[(Indexed<HyperlaneMessage>, LogMeta)]) -> Result<u32> {
+    async fn store_messages<C: ConnectionTrait>(
+        &self,
+        db: &ScraperDb<C>,
+        messages: &[(Indexed<HyperlaneMessage>, LogMeta)],
+    ) -> Result<u32> {
         if messages.is_empty() {
             return Ok(0);
         }
-        let txns: HashMap<H256, TxnWithId> = self
-            .ensure_blocks_and_txns(messages.iter().map(|r| &r.1))
+        let txns: HashMap<H512, TxnWithId> = self
+            .ensure_blocks_and_txns(db, messages.iter().map(|r| &r.1))
             .await?
             .map(|t| (t.hash, t))
             .collect();
         let storable = messages.iter().map(|m| {
-            let txn = txns
-                .get(
-                    &m.1.transaction_id
-                        .try_into()
-                        .expect("256-bit transaction ids are the maximum supported at this time"),
-                )
-                .unwrap();
+            let txn = txns.get(&m.1.transaction_id).unwrap();
             StorableMessage {
                 msg: m.0.inner().clone(),
                 meta: &m.1,
                 txn_id: txn.id,
             }
         });
-        let stored = self
-            .db
+        let stored = db
             .store_dispatched_messages(self.domain().id(), &self.mailbox_address, storable)
             .await?;
         Ok(stored as u32)
     }
-}
 
-#[async_trait]
-impl HyperlaneLogStore<Delivery> for HyperlaneSqlDb {
-    async fn store_logs(&self, deliveries: &[(Indexed<Delivery>, LogMeta)]) -> Result<u32> {
+    async fn store_deliveries<C: ConnectionTrait>(
+        &self,
+        db: &ScraperDb<C>,
+        deliveries: &[(Indexed<Delivery>, LogMeta)],
+    ) -> Result<u32> {
         if deliveries.is_empty() {
             return Ok(0);
         }
-        let txns: HashMap<Delivery, TxnWithId> = self
-            .ensure_blocks_and_txns(deliveries.iter().map(|r| &r.1))
+        let txns: HashMap<H512, TxnWithId> = self
+            .ensure_blocks_and_txns(db, deliveries.iter().map(|r| &r.1))
             .await?
             .map(|t| (t.hash, t))
             .collect();
         let storable = deliveries.iter().map(|(message_id, meta)| {
-            let txn_id = txns
-                .get(
-                    &meta
-                        .transaction_id
-                        .try_into()
-                        .expect("256-bit transaction ids are the maximum supported at this time"),
-                )
-                .unwrap()
-                .id;
+            let txn_id = txns.get(&meta.transaction_id).unwrap().id;
             StorableDelivery {
                 message_id: *message_id.inner(),
                 meta,
@@ -328,38 +410,27 @@ impl HyperlaneLogStore<Delivery> for HyperlaneSqlDb {
             }
         });
 
-        let stored = self
-            .db
+        let stored = db
             .store_deliveries(self.domain().id(), self.mailbox_address, storable)
             .await?;
         Ok(stored as u32)
     }
-}
 
-#[async_trait]
-impl HyperlaneLogStore<InterchainGasPayment> for HyperlaneSqlDb {
-    async fn store_logs(
+    async fn store_payments<C: ConnectionTrait>(
         &self,
+        db: &ScraperDb<C>,
         payments: &[(Indexed<InterchainGasPayment>, LogMeta)],
     ) -> Result<u32> {
         if payments.is_empty() {
             return Ok(0);
         }
-        let txns: HashMap<H256, TxnWithId> = self
-            .ensure_blocks_and_txns(payments.iter().map(|r| &r.1))
+        let txns: HashMap<H512, TxnWithId> = self
+            .ensure_blocks_and_txns(db, payments.iter().map(|r| &r.1))
             .await?
             .map(|t| (t.hash, t))
             .collect();
         let storable = payments.iter().map(|(payment, meta)| {
-            let txn_id = txns
-                .get(
-                    &meta
-                        .transaction_id
-                        .try_into()
-                        .expect("256-bit transaction ids are the maximum supported at this time"),
-                )
-                .unwrap()
-                .id;
+            let txn_id = txns.get(&meta.transaction_id).unwrap().id;
             StorablePayment {
                 payment: payment.inner(),
                 meta,
@@ -367,9 +438,124 @@ impl HyperlaneLogStore<InterchainGasPayment> for HyperlaneSqlDb {
             }
         });
 
-        let stored = self.db.store_payments(self.domain().id(), storable).await?;
+        let stored = db.store_payments(self.domain().id(), storable).await?;
+        Ok(stored as u32)
+    }
+
+    async fn store_merkle_tree_insertions<C: ConnectionTrait>(
+        &self,
+        db: &ScraperDb<C>,
+        insertions: &[(Indexed<MerkleTreeInsertion>, LogMeta)],
+    ) -> Result<u32> {
+        if insertions.is_empty() {
+            return Ok(0);
+        }
+        let txns: HashMap<H512, TxnWithId> = self
+            .ensure_blocks_and_txns(db, insertions.iter().map(|r| &r.1))
+            .await?
+            .map(|t| (t.hash, t))
+            .collect();
+        let storable = insertions.iter().map(|(insertion, meta)| {
+            let txn_id = txns.get(&meta.transaction_id).unwrap().id;
+            StorableMerkleTreeInsertion {
+                insertion: insertion.inner(),
+                meta,
+                txn_id,
+            }
+        });
+
+        let stored = db
+            .store_merkle_tree_insertions(
+                self.domain().id(),
+                &self.merkle_tree_hook_address,
+                storable,
+            )
+            .await?;
         Ok(stored as u32)
     }
+
+    async fn store_validator_announcements<C: ConnectionTrait>(
+        &self,
+        db: &ScraperDb<C>,
+        announcements: &[(Indexed<ValidatorAnnouncement>, LogMeta)],
+    ) -> Result<u32> {
+        if announcements.is_empty() {
+            return Ok(0);
+        }
+        let txns: HashMap<H512, TxnWithId> = self
+            .ensure_blocks_and_txns(db, announcements.iter().map(|r| &r.1))
+            .await?
+            .map(|t| (t.hash, t))
+            .collect();
+        let storable = announcements.iter().map(|(announcement, meta)| {
+            let txn_id = txns.get(&meta.transaction_id).unwrap().id;
+            StorableValidatorAnnouncement {
+                announcement: announcement.inner(),
+                meta,
+                txn_id,
+            }
+        });
+
+        let stored = db
+            .store_validator_announcements(
+                self.domain().id(),
+                &self.validator_announce_address,
+                storable,
+            )
+            .await?;
+        Ok(stored as u32)
+    }
+}
+
+#[async_trait]
+impl HyperlaneLogStore<HyperlaneMessage> for HyperlaneSqlDb {
+    async fn store_logs(&self, messages: &[(Indexed<HyperlaneMessage>, LogMeta)]) -> Result<u32> {
+        let _guard = self.store_lock.read().await;
+        self.store_messages(&self.db, messages).await
+    }
+}
+
+#[async_trait]
+impl HyperlaneLogStore<Delivery> for HyperlaneSqlDb {
+    async fn store_logs(&self, deliveries: &[(Indexed<Delivery>, LogMeta)]) -> Result<u32> {
+        let _guard = self.store_lock.read().await;
+        self.store_deliveries(&self.db, deliveries).await
+    }
+}
+
+#[async_trait]
+impl HyperlaneLogStore<InterchainGasPayment> for HyperlaneSqlDb {
+    async fn store_logs(
+        &self,
+        payments: &[(Indexed<InterchainGasPayment>, LogMeta)],
+    ) -> Result<u32> {
+        let _guard = self.store_lock.read().await;
+        self.store_payments(&self.db, payments).await
+    }
+}
+
+#[async_trait]
+impl HyperlaneLogStore<MerkleTreeInsertion> for HyperlaneSqlDb {
+    async fn store_logs(
+        &self,
+        insertions: &[(Indexed<MerkleTreeInsertion>, LogMeta)],
+    ) -> Result<u32> {
+        let _guard = self.store_lock.read().await;
+        self.store_merkle_tree_insertions(&self.db, insertions)
+            .await
+    }
+}
+
+#[async_trait]
+impl HyperlaneLogStore<ValidatorAnnouncement> for HyperlaneSqlDb {
+    async fn store_logs(
+        &self,
+        announcements: &[(Indexed<ValidatorAnnouncement>, LogMeta)],
+    ) -> Result<u32> {
+        let _guard = self.store_lock.read().await;
+        self.store_validator_announcements(&self.db, announcements)
+            .await
+    }
 }
 
 #[async_trait]
@@ -396,7 +582,47 @@ impl HyperlaneSequenceAwareIndexerStoreReader<HyperlaneMessage> for HyperlaneSql
 }
 
 #[async_trait]
-impl<T> HyperlaneWatermarkedLogStore<T> for HyperlaneSqlDb
+impl HyperlaneSequenceAwareIndexerStoreReader<MerkleTreeInsertion> for HyperlaneSqlDb {
+    /// Gets a merkle tree insertion by its leaf index.
+    async fn retrieve_by_sequence(&self, sequence: u32) -> Result<Option<MerkleTreeInsertion>> {
+        let insertion = self
+            .db
+            .retrieve_merkle_tree_insertion_by_leaf_index(
+                self.domain().id(),
+                &self.merkle_tree_hook_address,
+                sequence,
+            )
+            .await?;
+        Ok(insertion)
+    }
+
+    /// Gets the block number at which the log occurred.
+    async fn retrieve_log_block_number_by_sequence(&self, sequence: u32) -> Result<Option<u64>> {
+        let tx_id = unwrap_or_none_result!(
+            self.db
+                .retrieve_merkle_tree_insertion_tx_id(
+                    self.domain().id(),
+                    &self.merkle_tree_hook_address,
+                    sequence,
+                )
+                .await?
+        );
+        let block_id = unwrap_or_none_result!(self.db.retrieve_block_id(tx_id).await?);
+        Ok(self.db.retrieve_block_number(block_id).await?)
+    }
+}
+
+/// Logs whose indexing progress is tracked by the shared [`CursorKind::Logs`]
+/// cursor
+trait SharesLogsCursor {}
+
+impl SharesLogsCursor for HyperlaneMessage {}
+impl SharesLogsCursor for Delivery {}
+impl SharesLogsCursor for InterchainGasPayment {}
+impl SharesLogsCursor for MerkleTreeInsertion {}
+
+#[async_trait]
+impl<T: SharesLogsCursor> HyperlaneWatermarkedLogStore<T> for HyperlaneSqlDb
 where
     HyperlaneSqlDb: HyperlaneLogStore<T>,
 {
@@ -411,15 +637,35 @@ where
     }
 }
 
+#[async_trait]
+impl HyperlaneWatermarkedLogStore<ValidatorAnnouncement> for HyperlaneSqlDb {
+    /// Gets the block number high watermark
+    async fn retrieve_high_watermark(&self) -> Result<Option<u32>> {
+        Ok(Some(
+            self.validator_announcement_cursor
+                .height()
+                .await
+                .try_into()?,
+        ))
+    }
+    /// Stores the block number high watermark
+    async fn store_high_watermark(&self, block_number: u32) -> Result<()> {
+        self.validator_announcement_cursor
+            .update(block_number.into())
+            .await;
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone)]
 struct TxnWithId {
-    hash: H256,
+    hash: H512,
     id: i64,
 }
 
 #[derive(Debug, Clone)]
 struct TxnWithBlockId {
-    txn_hash: H256,
+    txn_hash: H512,
     block_id: i64,
 }
 
@@ -433,3 +679,235 @@ fn as_chunks<T>(iter: impl Iterator<Item = T>, chunk_size: usize) -> impl Iterat
         .collect_vec()
         .into_iter()
 }
+
+/// Whether validator announcements on `domain` are emitted as events that can
+/// be indexed. Sealevel stores them in accounts instead.
+pub fn indexes_validator_announcements(domain: &HyperlaneDomain) -> bool {
+    !matches!(domain.domain_protocol(), HyperlaneDomainProtocol::Sealevel)
+}
+
+/// Splits `range` into consecutive ranges of at most `chunk_size` items.
+fn chunk_ranges(
+    range: RangeInclusive<u32>,
+    chunk_size: u32,
+) -> impl Iterator<Item = RangeInclusive<u32>> {
+    let (from, to) = range.into_inner();
+    let chunk_size = chunk_size.max(1);
+    let mut next = (from <= to).then_some(from);
+    std::iter::from_fn(move || {
+        let start = next?;
+        let end = start.saturating_add(chunk_size - 1).min(to);
+        next = (end < to).then(|| end + 1);
+        Some(start..=end)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Mutex;
+
+    use hyperlane_core::{
+        ChainInfo, ChainResult, HyperlaneChain, KnownHyperlaneDomain, TxnInfo, TxnReceiptInfo, U256,
+    };
+
+    use super::*;
+    use crate::db::MessageFilter;
+
+    /// Serves blocks whose hashes can be changed to simulate a reorg
+    #[derive(Debug, Clone)]
+    struct TestProvider {
+        domain: HyperlaneDomain,
+        block_hashes: Arc<Mutex<HashMap<u64, H256>>>,
+    }
+
+    impl HyperlaneChain for TestProvider {
+        fn domain(&self) -> &HyperlaneDomain {
+            &self.domain
+        }
+
+        fn provider(&self) -> Box<dyn HyperlaneProvider> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[async_trait]
+    impl HyperlaneProvider for TestProvider {
+        async fn get_block_by_hash(&self, _hash: &H256) -> ChainResult<BlockInfo> {
+            unimplemented!()
+        }
+
+        async fn get_block_by_height(&self, height: u64) -> ChainResult<BlockInfo> {
+            Ok(BlockInfo {
+                hash: self.block_hashes.lock().unwrap()[&height],
+                timestamp: 1_700_000_000 + height,
+                number: height,
+            })
+        }
+
+        async fn get_txn_by_hash(&self, hash: &H512) -> ChainResult<TxnInfo> {
+            Ok(TxnInfo {
+                hash: *hash,
+                gas_limit: U256::from(100_000),
+                max_priority_fee_per_gas: None,
+                max_fee_per_gas: None,
+                gas_price: Some(U256::one()),
+                nonce: 0,
+                sender: H256::zero(),
+                recipient: None,
+                receipt: Some(TxnReceiptInfo {
+                    gas_used: U256::from(50_000),
+                    cumulative_gas_used: U256::from(50_000),
+                    effective_gas_price: Some(U256::one()),
+                }),
+            })
+        }
+
+        async fn is_contract(&self, _address: &H256) -> ChainResult<bool> {
+            unimplemented!()
+        }
+
+        async fn get_balance(&self, _address: String) -> ChainResult<U256> {
+            unimplemented!()
+        }
+
+        async fn get_chain_metrics(&self) -> ChainResult<Option<ChainInfo>> {
+            unimplemented!()
+        }
+    }
+
+    fn dispatch(
+        nonce: u32,
+        block_number: u64,
+        block_hash: H256,
+    ) -> (Indexed<HyperlaneMessage>, LogMeta) {
+        let message = HyperlaneMessage {
+            nonce,
+            origin: KnownHyperlaneDomain::Test1 as u32,
+            destination: KnownHyperlaneDomain::Test2 as u32,
+            ..Default::default()
+        };
+        let meta = LogMeta {
+            address: H256::zero(),
+            block_number,
+            block_hash,
+            transaction_id: H512::from_low_u64_be(block_number),
+            transaction_index: 0,
+            log_index: U256::zero(),
+        };
+        (message.into(), meta)
+    }
+
+    #[tokio::test]
+    async fn test_remove_reorged_blocks() {
+        let domain = HyperlaneDomain::Known(KnownHyperlaneDomain::Test1);
+        let block_hashes: HashMap<u64, H256> = (0..6)
+            .map(|height| (height, H256::from_low_u64_be(height + 1)))
+            .collect();
+        let provider = TestProvider {
+            domain: domain.clone(),
+            block_hashes: Arc::new(Mutex::new(block_hashes.clone())),
+        };
+        let db = HyperlaneSqlDb::new(
+            ScraperDb::connect("sqlite::memory:").await.unwrap(),
+            H256::zero(),
+            H256::zero(),
+            H256::zero(),
+            domain.clone(),
+            Arc::new(provider.clone()),
+            &IndexSettings::default(),
+        )
+        .await
+        .unwrap();
+        db.store_logs(&[
+            dispatch(0, 2, block_hashes[&2]),
+            dispatch(1, 4, block_hashes[&4]),
+        ])
+        .await
+        .unwrap();
+        HyperlaneWatermarkedLogStore::<HyperlaneMessage>::store_high_watermark(&db, 5)
+            .await
+            .unwrap();
+        assert_eq!(db.remove_reorged_blocks(256).await.unwrap(), None);
+
+        // Fork the chain below the second message
+        provider
+            .block_hashes
+            .lock()
+            .unwrap()
+            .extend((3..6).map(|height| (height, H256::from_low_u64_be(height + 100))));
+        assert_eq!(db.remove_reorged_blocks(256).await.unwrap(), Some(4));
+
+        let stored = db.db.get_blocks_since(domain.id(), 0).await.unwrap();
+        assert_eq!(stored.iter().map(|b| b.height).collect_vec(), vec![2]);
+        let message = |nonce| {
+            HyperlaneSequenceAwareIndexerStoreReader::<HyperlaneMessage>::retrieve_by_sequence(
+                &db, nonce,
+            )
+        };
+        assert!(message(0).await.unwrap().is_some());
+        assert!(message(1).await.unwrap().is_none());
+        assert!(db
+            .db
+            .get_txn_ids([H512::from_low_u64_be(4)].iter())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_wei_amounts_are_stored_exactly() {
+        let domain = HyperlaneDomain::Known(KnownHyperlaneDomain::Test1);
+        let provider = TestProvider {
+            domain: domain.clone(),
+            block_hashes: Arc::new(Mutex::new(
+                [(2, H256::from_low_u64_be(3))].into_iter().collect(),
+            )),
+        };
+        let db = HyperlaneSqlDb::new(
+            ScraperDb::connect("sqlite::memory:").await.unwrap(),
+            H256::zero(),
+            H256::zero(),
+            H256::zero(),
+            domain,
+            Arc::new(provider),
+            &IndexSettings::default(),
+        )
+        .await
+        .unwrap();
+        let (message, meta) = dispatch(0, 2, H256::from_low_u64_be(3));
+        let payment = InterchainGasPayment {
+            message_id: message.inner().id(),
+            destination: KnownHyperlaneDomain::Test2 as u32,
+            payment: U256::MAX,
+            // Just above what a double can represent exactly
+            gas_amount: U256::from((1u64 << 53) + 1),
+        };
+        db.store_logs(&[(message, meta.clone())]).await.unwrap();
+        db.store_logs(&[(payment.into(), meta)]).await.unwrap();
+
+        let messages = db
+            .db
+            .query_messages(&MessageFilter::default(), 10)
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), 1);
+        let payments = &messages[0].gas_payments;
+        assert_eq!(payments.len(), 1);
+        assert_eq!(payments[0].payment, U256::MAX.to_string());
+        assert_eq!(payments[0].gas_amount, "9007199254740993");
+    }
+
+    #[test]
+    fn test_chunk_ranges() {
+        assert_eq!(
+            chunk_ranges(10..=34, 10).collect_vec(),
+            vec![10..=19, 20..=29, 30..=34]
+        );
+        assert_eq!(chunk_ranges(5..=5, 10).collect_vec(), vec![5..=5]);
+        assert_eq!(chunk_ranges(5..=4, 10).count(), 0);
+        assert_eq!(
+            chunk_ranges(u32::MAX - 1..=u32::MAX, 0).collect_vec(),
+            vec![u32::MAX - 1..=u32::MAX - 1, u32::MAX..=u32::MAX]
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use derive_new::new;
+use eyre::Result;
+use prometheus::Histogram;
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+use super::{chunk_ranges, ChainIndexers, HyperlaneSqlDb};
+
+/// How far below the cursor stored blocks are checked against the chain
+const REORG_CHECK_DEPTH: u64 = 256;
+
+/// How often to check for reorgs
+const REORG_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Watches for reorgs deeper than the reorg period that left blocks in the
+/// database which are no longer part of the canonical chain. Orphaned blocks
+/// are removed along with the rows indexed from them and the affected range
+/// is indexed again.
+///
+/// This only makes sense for block-indexed chains, since the ranges passed
+/// to the indexers are block heights.
+#[derive(Debug, new)]
+pub struct ReorgWatcher {
+    db: HyperlaneSqlDb,
+    indexers: ChainIndexers,
+    chunk_size: u32,
+    reorg_depth: Histogram,
+}
+
+impl ReorgWatcher {
+    /// Check for reorgs each time the cursor advances
+    pub async fn run(self) {
+        let mut last_checked = None;
+        loop {
+            sleep(REORG_CHECK_INTERVAL).await;
+            let height = self.db.cursor_height().await;
+            if last_checked == Some(height) {
+                continue;
+            }
+            match self.check(height).await {
+                Ok(()) => last_checked = Some(height),
+                Err(err) => warn!(?err, height, "Failed to check for reorgs"),
+            }
+        }
+    }
+
+    async fn check(&self, height: u64) -> Result<()> {
+        let Some(fork_height) = self.db.remove_reorged_blocks(REORG_CHECK_DEPTH).await? else {
+            return Ok(());
+        };
+        let depth = height.saturating_sub(fork_height) + 1;
+        self.reorg_depth.observe(depth as f64);
+        warn!(
+            fork_height,
+            height, depth, "Removed reorged blocks, indexing the range again"
+        );
+
+        let to = u32::try_from(height)?;
+        for range in chunk_ranges(u32::try_from(fork_height)?..=to, self.chunk_size) {
+            self.indexers
+                .fetch_logs(range)
+                .await?
+                .store(&self.db)
+                .await?;
+        }
+        info!(fork_height, height, "Finished indexing reorged range");
+        Ok(())
+    }
+}
@@ -14,3 +14,8 @@ pub fn now() -> TimeDateTime {
     let offset = OffsetDateTime::now_utc();
     TimeDateTime::new(offset.date(), offset.time())
 }
+
+/// Convert from a TimeDateTime object to a unix timestamp in seconds.
+pub fn to_unix_timestamp_s(date_time: TimeDateTime) -> i64 {
+    date_time.assume_utc().unix_timestamp()
+}
@@ -1,7 +1,7 @@
 use eyre::{Context, Result};
 use sea_orm::{
-    prelude::*, ActiveValue::*, DbErr, EntityTrait, FromQueryResult, Insert, QueryResult,
-    QuerySelect,
+    prelude::*, ActiveValue::*, DbErr, EntityTrait, FromQueryResult, Insert, QueryOrder,
+    QueryResult, QuerySelect, TransactionTrait,
 };
 use tracing::{debug, trace};
 
@@ -12,7 +12,10 @@ use crate::conversions::{address_to_bytes, h256_to_bytes};
 use crate::date_time;
 use crate::db::ScraperDb;
 
-use super::generated::block;
+use super::generated::{
+    block, delivered_message, gas_payment, merkle_tree_insertion, message, transaction,
+    validator_announcement,
+};
 
 /// A stripped down block model. This is so we can get just the information
 /// needed if the block is present in the Db already to inject into other
@@ -33,7 +36,17 @@ impl FromQueryResult for BasicBlock {
     }
 }
 
-impl ScraperDb {
+/// A block stored in the database along with its height, used to check that
+/// it is still part of the canonical chain.
+#[derive(Debug, Clone)]
+pub struct StoredBlock {
+    /// the database id of this block
+    pub id: i64,
+    pub hash: H256,
+    pub height: u64,
+}
+
+impl<C: ConnectionTrait> ScraperDb<C> {
     /// Retrieves the block number for a given block database ID
     pub async fn retrieve_block_number(&self, block_id: i64) -> Result<Option<u64>> {
         #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -110,4 +123,90 @@ impl ScraperDb {
             Err(e) => Err(e).context("When inserting blocks"),
         }
     }
+
+    /// Get the blocks stored for `domain` at or above `min_height`, ordered
+    /// by height.
+    pub async fn get_blocks_since(&self, domain: u32, min_height: u64) -> Result<Vec<StoredBlock>> {
+        let blocks = block::Entity::find()
+            .filter(block::Column::Domain.eq(domain as i32))
+            .filter(block::Column::Height.gte(min_height as i64))
+            .order_by_asc(block::Column::Height)
+            .all(&self.0)
+            .await
+            .context("When querying recent blocks")?;
+
+        blocks
+            .into_iter()
+            .map(|block| {
+                Ok(StoredBlock {
+                    id: block.id,
+                    hash: H256::from_slice(&block.hash),
+                    height: block.height.try_into()?,
+                })
+            })
+            .collect()
+    }
+}
+
+impl ScraperDb {
+    /// Delete blocks along with the transactions in them and everything
+    /// that was indexed from those transactions. Used to remove blocks that
+    /// are no longer part of the canonical chain after a reorg.
+    pub async fn delete_blocks(&self, block_ids: Vec<i64>) -> Result<()> {
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
+        enum QueryAs {
+            Id,
+        }
+
+        let db_txn = self.0.begin().await?;
+        let txn_ids = transaction::Entity::find()
+            .filter(transaction::Column::BlockId.is_in(block_ids.clone()))
+            .select_only()
+            .column_as(transaction::Column::Id, QueryAs::Id)
+            .into_values::<i64, QueryAs>()
+            .all(&db_txn)
+            .await?;
+
+        let messages = message::Entity::delete_many()
+            .filter(message::Column::OriginTxId.is_in(txn_ids.clone()))
+            .exec(&db_txn)
+            .await?;
+        let deliveries = delivered_message::Entity::delete_many()
+            .filter(delivered_message::Column::DestinationTxId.is_in(txn_ids.clone()))
+            .exec(&db_txn)
+            .await?;
+        let payments = gas_payment::Entity::delete_many()
+            .filter(gas_payment::Column::TxId.is_in(txn_ids.clone()))
+            .exec(&db_txn)
+            .await?;
+        let insertions = merkle_tree_insertion::Entity::delete_many()
+            .filter(merkle_tree_insertion::Column::TxId.is_in(txn_ids.clone()))
+            .exec(&db_txn)
+            .await?;
+        let announcements = validator_announcement::Entity::delete_many()
+            .filter(validator_announcement::Column::TxId.is_in(txn_ids.clone()))
+            .exec(&db_txn)
+            .await?;
+        transaction::Entity::delete_many()
+            .filter(transaction::Column::Id.is_in(txn_ids.clone()))
+            .exec(&db_txn)
+            .await?;
+        block::Entity::delete_many()
+            .filter(block::Column::Id.is_in(block_ids.clone()))
+            .exec(&db_txn)
+            .await?;
+        db_txn.commit().await.context("When deleting blocks")?;
+
+        debug!(
+            blocks = block_ids.len(),
+            txns = txn_ids.len(),
+            messages = messages.rows_affected,
+            deliveries = deliveries.rows_affected,
+            payments = payments.rows_affected,
+            insertions = insertions.rows_affected,
+            announcements = announcements.rows_affected,
+            "Deleted blocks from database"
+        );
+        Ok(())
+    }
 }
@@ -19,6 +19,25 @@ struct BlockCursorInner {
     last_saved_at: Instant,
 }
 
+/// What a [`BlockCursor`] tracks. A domain has one cursor of each kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorKind {
+    /// Shared by every kind of log except validator announcements
+    Logs,
+    /// Validator announcements, which have their own cursor so that enabling
+    /// them on an existing database still indexes their full history
+    ValidatorAnnouncements,
+}
+
+impl CursorKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Logs => "logs",
+            Self::ValidatorAnnouncements => "validator_announcements",
+        }
+    }
+}
+
 /// A tool to wrap the logic of fetching and updating the cursor position in the
 /// database. We may end up reading the same block range again later but this
 /// prevents us from starting from the beginning after a restart.
@@ -27,11 +46,12 @@ pub struct BlockCursor {
     db: DbConn,
     /// The hyperlane domain this block cursor is for.
     domain: u32,
+    kind: CursorKind,
     inner: RwLock<BlockCursorInner>,
 }
 
 impl BlockCursor {
-    async fn new(db: DbConn, domain: u32, default_height: u64) -> Result<Self> {
+    async fn new(db: DbConn, domain: u32, kind: CursorKind, default_height: u64) -> Result<Self> {
         #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
         enum QueryAs {
             Height,
@@ -39,6 +59,7 @@ impl BlockCursor {
 
         let height = (cursor::Entity::find())
             .filter(cursor::Column::Domain.eq(domain))
+            .filter(cursor::Column::Kind.eq(kind.as_str()))
             .order_by(cursor::Column::Height, Order::Desc)
             .select_only()
             .column_as(cursor::Column::Height, QueryAs::Height)
@@ -51,14 +72,20 @@ impl BlockCursor {
             warn!(
                 height,
                 default_height,
+                ?kind,
                 "Cursor height loaded from the database is lower than the default height!"
             )
         } else {
-            info!(height, "Restored current cursor position from database")
+            info!(
+                height,
+                ?kind,
+                "Restored current cursor position from database"
+            )
         }
         Ok(Self {
             db,
             domain,
+            kind,
             inner: RwLock::new(BlockCursorInner {
                 height,
                 last_saved_at: Instant::now(),
@@ -88,6 +115,7 @@ impl BlockCursor {
                 domain: ActiveValue::Set(self.domain as i32),
                 time_created: ActiveValue::NotSet,
                 height: ActiveValue::Set(height as i64),
+                kind: ActiveValue::Set(self.kind.as_str().to_owned()),
             };
             debug!(?model, "Inserting cursor");
             if let Err(e) = Insert::one(model).exec(&self.db).await {
@@ -100,7 +128,12 @@ impl BlockCursor {
 }
 
 impl ScraperDb {
-    pub async fn block_cursor(&self, domain: u32, default_height: u64) -> Result<BlockCursor> {
-        BlockCursor::new(self.0.clone(), domain, default_height).await
+    pub async fn block_cursor(
+        &self,
+        domain: u32,
+        kind: CursorKind,
+        default_height: u64,
+    ) -> Result<BlockCursor> {
+        BlockCursor::new(self.0.clone(), domain, kind, default_height).await
     }
 }
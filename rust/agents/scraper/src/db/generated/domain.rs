@@ -53,7 +53,9 @@ pub enum Relation {
     Cursor,
     DeliveredMessage,
     GasPayment,
+    MerkleTreeInsertion,
     Message,
+    ValidatorAnnouncement,
 }
 
 impl ColumnTrait for Column {
@@ -79,7 +81,13 @@ impl RelationTrait for Relation {
             Self::Cursor => Entity::has_many(super::cursor::Entity).into(),
             Self::DeliveredMessage => Entity::has_many(super::delivered_message::Entity).into(),
             Self::GasPayment => Entity::has_many(super::gas_payment::Entity).into(),
+            Self::MerkleTreeInsertion => {
+                Entity::has_many(super::merkle_tree_insertion::Entity).into()
+            }
             Self::Message => Entity::has_many(super::message::Entity).into(),
+            Self::ValidatorAnnouncement => {
+                Entity::has_many(super::validator_announcement::Entity).into()
+            }
         }
     }
 }
@@ -108,10 +116,22 @@ impl Related<super::gas_payment::Entity> for Entity {
     }
 }
 
+impl Related<super::merkle_tree_insertion::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::MerkleTreeInsertion.def()
+    }
+}
+
 impl Related<super::message::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Message.def()
     }
 }
 
+impl Related<super::validator_announcement::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ValidatorAnnouncement.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
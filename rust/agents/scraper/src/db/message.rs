@@ -27,7 +27,7 @@ pub struct StorableMessage<'a> {
     pub txn_id: i64,
 }
 
-impl ScraperDb {
+impl<C: ConnectionTrait> ScraperDb<C> {
     /// Get the highest message nonce that is stored in the database.
     #[instrument(skip(self))]
     pub async fn last_message_nonce(
@@ -1,11 +1,18 @@
 pub use block::*;
-pub use block_cursor::BlockCursor;
+pub use block_cursor::{BlockCursor, CursorKind};
 use eyre::Result;
+pub use merkle_tree::*;
 pub use message::*;
+use migration::{Migrator, MigratorTrait};
 pub use payment::*;
-use sea_orm::{Database, DbConn};
+pub use query::*;
+use sea_orm::{
+    sea_query::Value, ActiveModelTrait, ConnectOptions, ConnectionTrait, Database,
+    DatabaseTransaction, DbBackend, DbConn, DbErr, Insert, QueryTrait, TransactionTrait,
+};
 use tracing::instrument;
 pub use txn::*;
+pub use validator_announcement::*;
 
 #[allow(clippy::all)]
 mod generated;
@@ -13,19 +20,132 @@ mod generated;
 // These modules implement additional functionality for the ScraperDb
 mod block;
 mod block_cursor;
+mod merkle_tree;
 mod message;
 mod payment;
+mod query;
 mod txn;
+mod validator_announcement;
 
 /// Database interface to the message explorer database for the scraper. This is
 /// focused on writing data to the database.
+///
+/// Writes go through a connection pool by default, or through a single
+/// database transaction when created with [`ScraperDb::begin`].
 #[derive(Clone, Debug)]
-pub struct ScraperDb(DbConn);
+pub struct ScraperDb<C = DbConn>(C);
 
 impl ScraperDb {
+    /// Connect to a Postgres (`postgresql://`) or SQLite (`sqlite://`)
+    /// database. SQLite databases have no separate setup step, so their schema
+    /// is created or migrated on connect.
     #[instrument]
     pub async fn connect(url: &str) -> Result<Self> {
-        let db = Database::connect(url).await?;
+        let mut options = ConnectOptions::new(url.to_owned());
+        if DbBackend::Sqlite.is_prefix_of(url) {
+            // SQLite only allows a single writer, and every connection to an
+            // in-memory database gets its own database
+            options.max_connections(1);
+        }
+        let db = Database::connect(options).await?;
+        if db.get_database_backend() == DbBackend::Sqlite {
+            Migrator::up(&db, None).await?;
+        }
         Ok(Self(db))
     }
+
+    /// Start a database transaction. Nothing written through it is visible
+    /// to other connections until it is committed, and dropping it without
+    /// committing rolls everything back.
+    pub async fn begin(&self) -> Result<ScraperDb<DatabaseTransaction>> {
+        Ok(ScraperDb(self.0.begin().await?))
+    }
+}
+
+impl ScraperDb<DatabaseTransaction> {
+    /// Commit everything written through this transaction
+    pub async fn commit(self) -> Result<()> {
+        Ok(self.0.commit().await?)
+    }
+}
+
+impl<C: ConnectionTrait> ScraperDb<C> {
+    /// Execute an insert of rows with wei amounts. SQLite would store these
+    /// as floating point numbers, so there they are written as blobs of
+    /// their base-10 digits instead.
+    async fn exec_wei_insert<A: ActiveModelTrait>(&self, insert: Insert<A>) -> Result<(), DbErr> {
+        let backend = self.0.get_database_backend();
+        let mut stmt = insert.build(backend);
+        if backend == DbBackend::Sqlite {
+            for value in stmt
+                .values
+                .iter_mut()
+                .flat_map(|values| values.0.iter_mut())
+            {
+                if let Value::BigDecimal(Some(wei)) = value {
+                    *value = Value::Bytes(Some(Box::new(wei.to_string().into_bytes())));
+                }
+            }
+        }
+        self.0.execute(stmt).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::{BlockInfo, H256};
+    use sea_orm::{ActiveValue::NotSet, Insert, Set};
+
+    use super::*;
+    use crate::date_time;
+
+    #[tokio::test]
+    async fn test_sqlite_store_and_query_blocks() {
+        let db = ScraperDb::connect("sqlite::memory:").await.unwrap();
+        let domain = 13371;
+        let blocks = (0..3).map(|height| BlockInfo {
+            hash: H256::from_low_u64_be(height + 1),
+            timestamp: 1_700_000_000 + height,
+            number: height,
+        });
+        db.store_blocks(domain, blocks.clone()).await.unwrap();
+        // Storing the same blocks again is a no-op
+        db.store_blocks(domain, blocks).await.unwrap();
+
+        let stored = db.get_blocks_since(domain, 1).await.unwrap();
+        assert_eq!(
+            stored.iter().map(|b| b.height).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(stored[0].hash, H256::from_low_u64_be(2));
+
+        let cursor = db.block_cursor(domain, CursorKind::Logs, 5).await.unwrap();
+        assert_eq!(cursor.height().await, 5);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cursor_kinds_are_independent() {
+        let db = ScraperDb::connect("sqlite::memory:").await.unwrap();
+        let domain = 13371;
+        // Rows written before cursors had a kind default to the logs cursor
+        Insert::one(generated::cursor::ActiveModel {
+            id: NotSet,
+            domain: Set(domain as i32),
+            time_created: Set(date_time::now()),
+            height: Set(100),
+            kind: NotSet,
+        })
+        .exec(&db.0)
+        .await
+        .unwrap();
+
+        let logs = db.block_cursor(domain, CursorKind::Logs, 5).await.unwrap();
+        assert_eq!(logs.height().await, 100);
+        let announcements = db
+            .block_cursor(domain, CursorKind::ValidatorAnnouncements, 5)
+            .await
+            .unwrap();
+        assert_eq!(announcements.height().await, 5);
+    }
 }
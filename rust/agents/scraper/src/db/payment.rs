@@ -19,7 +19,7 @@ pub struct StorablePayment<'a> {
     pub txn_id: i64,
 }
 
-impl ScraperDb {
+impl<C: ConnectionTrait> ScraperDb<C> {
     #[instrument(skip_all)]
     pub async fn store_payments(
         &self,
@@ -44,8 +44,8 @@ impl ScraperDb {
         debug_assert!(!models.is_empty());
         trace!(?models, "Writing gas payments to database");
 
-        Insert::many(models)
-            .on_conflict(
+        self.exec_wei_insert(
+            Insert::many(models).on_conflict(
                 OnConflict::columns([
                     // don't need domain because TxId includes it
                     gas_payment::Column::MsgId,
@@ -58,9 +58,9 @@ impl ScraperDb {
                     gas_payment::Column::GasAmount,
                 ])
                 .to_owned(),
-            )
-            .exec(&self.0)
-            .await?;
+            ),
+        )
+        .await?;
         let payment_count_after = self.payments_count(domain).await?;
         let difference = payment_count_after.saturating_sub(payment_count_before);
         if difference > 0 {
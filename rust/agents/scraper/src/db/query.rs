@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+
+use ethers::types::Bytes;
+use eyre::{Context, Result};
+use hyperlane_core::{H256, H512};
+use sea_orm::{
+    prelude::*,
+    sea_query::{Alias, Func, SimpleExpr},
+    FromQueryResult, JoinType, QueryOrder, QuerySelect,
+};
+use serde::Serialize;
+use tracing::instrument;
+
+use crate::conversions::{address_to_bytes, bytes_to_address, h256_to_bytes, h512_to_bytes};
+use crate::date_time;
+use crate::db::ScraperDb;
+
+use super::generated::{block, delivered_message, gas_payment, message, transaction};
+
+/// Filters for querying dispatched messages. Every filter that is set must
+/// match.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MessageFilter {
+    pub message_id: Option<H256>,
+    pub sender: Option<H256>,
+    pub recipient: Option<H256>,
+    pub origin: Option<u32>,
+    pub destination: Option<u32>,
+    /// Hash of the transaction the message was dispatched in
+    pub origin_tx_hash: Option<H512>,
+    /// Earliest dispatch time, as a unix timestamp in seconds (inclusive)
+    pub from_time: Option<u64>,
+    /// Latest dispatch time, as a unix timestamp in seconds (inclusive)
+    pub to_time: Option<u64>,
+    /// Only return messages stored before this database id, used to page
+    /// through results
+    pub before_id: Option<i64>,
+}
+
+/// A dispatched message along with its delivery and gas payments
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MessageView {
+    /// The database id of the message, used as a pagination cursor
+    pub id: i64,
+    pub message_id: H256,
+    pub origin: u32,
+    pub destination: u32,
+    pub nonce: u32,
+    pub sender: H256,
+    pub recipient: H256,
+    pub body: Bytes,
+    pub origin_mailbox: H256,
+    pub origin_tx_hash: Bytes,
+    /// Time of the block the message was dispatched in, as a unix timestamp
+    /// in seconds
+    pub dispatched_at: i64,
+    /// Set once the message has been delivered
+    pub delivery: Option<DeliveryView>,
+    pub gas_payments: Vec<GasPaymentView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeliveryView {
+    pub destination_mailbox: H256,
+    pub destination_tx_hash: Bytes,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GasPaymentView {
+    pub domain: u32,
+    /// Amount of native tokens paid, as a base-10 integer
+    pub payment: String,
+    /// Amount of destination gas paid for, as a base-10 integer
+    pub gas_amount: String,
+    pub tx_hash: Bytes,
+}
+
+/// A gas payment with its wei amounts as base-10 text
+#[derive(Debug, FromQueryResult)]
+struct GasPaymentRow {
+    msg_id: Vec<u8>,
+    domain: i32,
+    payment: String,
+    gas_amount: String,
+    tx_id: i64,
+}
+
+/// The columns of a transaction that message views need
+#[derive(Debug, FromQueryResult)]
+struct TxnRow {
+    id: i64,
+    hash: Vec<u8>,
+    block_id: i64,
+}
+
+/// Read a wei column as base-10 text. Postgres stores wei as numerics and
+/// SQLite as blobs of their digits, and both cast to the same text.
+fn wei_as_text(col: gas_payment::Column) -> SimpleExpr {
+    Func::cast_as(Expr::col(col), Alias::new("TEXT")).into()
+}
+
+impl ScraperDb {
+    /// Find the dispatched messages matching `filter`, newest first, with
+    /// their delivery and gas payments.
+    #[instrument(skip(self))]
+    pub async fn query_messages(
+        &self,
+        filter: &MessageFilter,
+        limit: u64,
+    ) -> Result<Vec<MessageView>> {
+        let mut query = message::Entity::find()
+            .join(JoinType::InnerJoin, message::Relation::Transaction.def())
+            .join(JoinType::InnerJoin, transaction::Relation::Block.def());
+        if let Some(message_id) = &filter.message_id {
+            query = query.filter(message::Column::MsgId.eq(h256_to_bytes(message_id)));
+        }
+        if let Some(sender) = &filter.sender {
+            query = query.filter(message::Column::Sender.eq(address_to_bytes(sender)));
+        }
+        if let Some(recipient) = &filter.recipient {
+            query = query.filter(message::Column::Recipient.eq(address_to_bytes(recipient)));
+        }
+        if let Some(origin) = filter.origin {
+            query = query.filter(message::Column::Origin.eq(origin));
+        }
+        if let Some(destination) = filter.destination {
+            query = query.filter(message::Column::Destination.eq(destination));
+        }
+        if let Some(tx_hash) = &filter.origin_tx_hash {
+            query = query.filter(transaction::Column::Hash.eq(h512_to_bytes(tx_hash)));
+        }
+        if let Some(from_time) = filter.from_time {
+            query = query
+                .filter(block::Column::Timestamp.gte(date_time::from_unix_timestamp_s(from_time)));
+        }
+        if let Some(to_time) = filter.to_time {
+            query = query
+                .filter(block::Column::Timestamp.lte(date_time::from_unix_timestamp_s(to_time)));
+        }
+        if let Some(before_id) = filter.before_id {
+            query = query.filter(message::Column::Id.lt(before_id));
+        }
+        let messages = query
+            .order_by_desc(message::Column::Id)
+            .limit(limit)
+            .all(&self.0)
+            .await
+            .context("When querying messages")?;
+        if messages.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let msg_ids: Vec<Vec<u8>> = messages.iter().map(|m| m.msg_id.clone()).collect();
+        let deliveries = delivered_message::Entity::find()
+            .filter(delivered_message::Column::MsgId.is_in(msg_ids.clone()))
+            .all(&self.0)
+            .await
+            .context("When querying deliveries")?;
+        let payments = gas_payment::Entity::find()
+            .filter(gas_payment::Column::MsgId.is_in(msg_ids))
+            .order_by_asc(gas_payment::Column::Id)
+            .select_only()
+            .column(gas_payment::Column::MsgId)
+            .column(gas_payment::Column::Domain)
+            .column_as(wei_as_text(gas_payment::Column::Payment), "payment")
+            .column_as(wei_as_text(gas_payment::Column::GasAmount), "gas_amount")
+            .column(gas_payment::Column::TxId)
+            .into_model::<GasPaymentRow>()
+            .all(&self.0)
+            .await
+            .context("When querying gas payments")?;
+
+        let txn_ids = messages
+            .iter()
+            .map(|m| m.origin_tx_id)
+            .chain(deliveries.iter().map(|d| d.destination_tx_id))
+            .chain(payments.iter().map(|p| p.tx_id));
+        let txns: HashMap<i64, TxnRow> = transaction::Entity::find()
+            .filter(transaction::Column::Id.is_in(txn_ids))
+            .select_only()
+            .column(transaction::Column::Id)
+            .column(transaction::Column::Hash)
+            .column(transaction::Column::BlockId)
+            .into_model::<TxnRow>()
+            .all(&self.0)
+            .await
+            .context("When querying transactions")?
+            .into_iter()
+            .map(|txn| (txn.id, txn))
+            .collect();
+        let blocks: HashMap<i64, block::Model> = block::Entity::find()
+            .filter(
+                block::Column::Id.is_in(
+                    messages
+                        .iter()
+                        .filter_map(|m| txns.get(&m.origin_tx_id))
+                        .map(|txn| txn.block_id),
+                ),
+            )
+            .all(&self.0)
+            .await
+            .context("When querying blocks")?
+            .into_iter()
+            .map(|block| (block.id, block))
+            .collect();
+
+        let tx_hash = |txn_id: i64| -> Result<Bytes> {
+            txns.get(&txn_id)
+                .map(|txn| txn.hash.clone().into())
+                .ok_or_else(|| eyre::eyre!("Missing transaction {txn_id}"))
+        };
+
+        let mut deliveries: HashMap<Vec<u8>, DeliveryView> = deliveries
+            .into_iter()
+            .map(|delivery| {
+                Ok((
+                    delivery.msg_id,
+                    DeliveryView {
+                        destination_mailbox: bytes_to_address(delivery.destination_mailbox)?,
+                        destination_tx_hash: tx_hash(delivery.destination_tx_id)?,
+                    },
+                ))
+            })
+            .collect::<Result<_>>()?;
+        let mut payments_by_msg_id: HashMap<Vec<u8>, Vec<GasPaymentView>> = HashMap::new();
+        for payment in payments {
+            payments_by_msg_id
+                .entry(payment.msg_id)
+                .or_default()
+                .push(GasPaymentView {
+                    domain: payment.domain as u32,
+                    payment: payment.payment,
+                    gas_amount: payment.gas_amount,
+                    tx_hash: tx_hash(payment.tx_id)?,
+                });
+        }
+
+        messages
+            .into_iter()
+            .map(|message| {
+                let block = txns
+                    .get(&message.origin_tx_id)
+                    .and_then(|txn| blocks.get(&txn.block_id))
+                    .ok_or_else(|| eyre::eyre!("Missing block for message {}", message.id))?;
+                Ok(MessageView {
+                    id: message.id,
+                    message_id: H256::from_slice(&message.msg_id),
+                    origin: message.origin as u32,
+                    destination: message.destination as u32,
+                    nonce: message.nonce as u32,
+                    sender: bytes_to_address(message.sender)?,
+                    recipient: bytes_to_address(message.recipient)?,
+                    body: message.msg_body.unwrap_or_default().into(),
+                    origin_mailbox: bytes_to_address(message.origin_mailbox)?,
+                    origin_tx_hash: tx_hash(message.origin_tx_id)?,
+                    dispatched_at: date_time::to_unix_timestamp_s(block.timestamp),
+                    delivery: deliveries.remove(&message.msg_id),
+                    gas_payments: payments_by_msg_id
+                        .remove(&message.msg_id)
+                        .unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+}
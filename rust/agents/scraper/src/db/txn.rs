@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use derive_more::Deref;
 use eyre::{eyre, Context, Result};
-use hyperlane_core::{TxnInfo, H256};
+use hyperlane_core::{TxnInfo, H512};
 use sea_orm::{
     prelude::*, sea_query::OnConflict, ActiveValue::*, DeriveColumn, EnumIter, Insert, NotSet,
     QuerySelect,
@@ -11,7 +11,7 @@ use tracing::{debug, instrument, trace};
 
 use super::generated::transaction;
 use crate::{
-    conversions::{address_to_bytes, h256_to_bytes, u256_to_decimal},
+    conversions::{address_to_bytes, bytes_to_h512, h512_to_bytes, u256_to_decimal},
     date_time,
     db::ScraperDb,
 };
@@ -23,7 +23,7 @@ pub struct StorableTxn {
     pub block_id: i64,
 }
 
-impl ScraperDb {
+impl<C: ConnectionTrait> ScraperDb<C> {
     pub async fn retrieve_block_id(&self, tx_id: i64) -> Result<Option<i64>> {
         #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
         enum QueryAs {
@@ -43,8 +43,8 @@ impl ScraperDb {
     /// found be excluded from the hashmap.
     pub async fn get_txn_ids(
         &self,
-        hashes: impl Iterator<Item = &H256>,
-    ) -> Result<HashMap<H256, i64>> {
+        hashes: impl Iterator<Item = &H512>,
+    ) -> Result<HashMap<H512, i64>> {
         #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
         enum QueryAs {
             Id,
@@ -53,7 +53,7 @@ impl ScraperDb {
 
         // check database to see which txns we already know and fetch their IDs
         let txns = transaction::Entity::find()
-            .filter(transaction::Column::Hash.is_in(hashes.map(h256_to_bytes)))
+            .filter(transaction::Column::Hash.is_in(hashes.map(h512_to_bytes)))
             .select_only()
             .column_as(transaction::Column::Id, QueryAs::Id)
             .column_as(transaction::Column::Hash, QueryAs::Hash)
@@ -62,7 +62,7 @@ impl ScraperDb {
             .await
             .context("When querying transactions")?
             .into_iter()
-            .map(|(id, hash)| Ok((H256::from_slice(&hash), id)))
+            .map(|(id, hash)| Ok((bytes_to_h512(&hash)?, id)))
             .collect::<Result<HashMap<_, _>>>()?;
 
         trace!(?txns, "Queried transaction info for hashes");
@@ -86,7 +86,7 @@ impl ScraperDb {
                     max_priority_fee_per_gas: Set(txn
                         .max_priority_fee_per_gas
                         .map(u256_to_decimal)),
-                    hash: Unchanged(h256_to_bytes(&txn.hash)),
+                    hash: Unchanged(h512_to_bytes(&txn.hash)),
                     time_created: Set(date_time::now()),
                     gas_used: Set(u256_to_decimal(receipt.gas_used)),
                     gas_price: Set(txn.gas_price.map(u256_to_decimal)),
@@ -104,18 +104,14 @@ impl ScraperDb {
         debug!(txns = models.len(), "Writing txns to database");
         trace!(?models, "Writing txns to database");
 
-        match Insert::many(models)
-            .on_conflict(
+        self.exec_wei_insert(
+            Insert::many(models).on_conflict(
                 OnConflict::column(transaction::Column::Hash)
                     .do_nothing()
                     .to_owned(),
-            )
-            .exec(&self.0)
-            .await
-        {
-            Ok(_) => Ok(()),
-            Err(DbErr::RecordNotInserted) => Ok(()),
-            Err(e) => Err(e).context("When inserting transactions"),
-        }
+            ),
+        )
+        .await
+        .context("When inserting transactions")
     }
 }
@@ -0,0 +1,201 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    routing, Json, Router,
+};
+use derive_new::new;
+use ethers::utils::hex;
+use eyre::{eyre, Result};
+use hyperlane_core::{H256, H512};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    conversions::{bytes_to_address, bytes_to_h512},
+    db::{MessageFilter, MessageView, ScraperDb},
+};
+
+const MESSAGES_API_BASE: &str = "/messages";
+
+const DEFAULT_PAGE_SIZE: u64 = 50;
+const MAX_PAGE_SIZE: u64 = 500;
+
+/// Queries the scraped messages, joined with their delivery and gas payments.
+#[derive(new, Clone)]
+pub struct MessagesApi {
+    db: ScraperDb,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RawMessagesRequest {
+    message_id: Option<String>,
+    sender: Option<String>,
+    recipient: Option<String>,
+    origin: Option<u32>,
+    destination: Option<u32>,
+    tx_hash: Option<String>,
+    from_time: Option<u64>,
+    to_time: Option<u64>,
+    /// The `next_cursor` of the previous page
+    cursor: Option<i64>,
+    limit: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MessagesResponse {
+    pub messages: Vec<MessageView>,
+    /// Pass as `cursor` to get the next page. Not set on the last page.
+    pub next_cursor: Option<i64>,
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>> {
+    Ok(hex::decode(value.trim_start_matches("0x"))?)
+}
+
+fn parse_message_id(value: &str) -> Result<H256> {
+    let bytes = decode_hex(value)?;
+    if bytes.len() != 32 {
+        return Err(eyre!("Invalid message id length"));
+    }
+    Ok(H256::from_slice(&bytes))
+}
+
+fn parse_tx_hash(value: &str) -> Result<H512> {
+    bytes_to_h512(&decode_hex(value)?)
+}
+
+fn parse_request(request: RawMessagesRequest) -> Result<(MessageFilter, u64)> {
+    let filter = MessageFilter {
+        message_id: request
+            .message_id
+            .as_deref()
+            .map(parse_message_id)
+            .transpose()?,
+        sender: request
+            .sender
+            .as_deref()
+            .map(|sender| bytes_to_address(decode_hex(sender)?))
+            .transpose()?,
+        recipient: request
+            .recipient
+            .as_deref()
+            .map(|recipient| bytes_to_address(decode_hex(recipient)?))
+            .transpose()?,
+        origin: request.origin,
+        destination: request.destination,
+        origin_tx_hash: request.tx_hash.as_deref().map(parse_tx_hash).transpose()?,
+        from_time: request.from_time,
+        to_time: request.to_time,
+        before_id: request.cursor,
+    };
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    Ok((filter, limit))
+}
+
+async fn list(
+    State(api): State<MessagesApi>,
+    Query(request): Query<RawMessagesRequest>,
+) -> Result<Json<MessagesResponse>, (StatusCode, String)> {
+    let (filter, limit) = parse_request(request)
+        .map_err(|err| (StatusCode::BAD_REQUEST, format!("Invalid request: {}", err)))?;
+    // Technically it's bad practice to print the error message to the user, but
+    // this endpoint is for debugging purposes only.
+    let messages = api.db.query_messages(&filter, limit).await.map_err(|err| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to query messages: {}", err),
+        )
+    })?;
+    let next_cursor = if messages.len() as u64 == limit {
+        messages.last().map(|message| message.id)
+    } else {
+        None
+    };
+    Ok(Json(MessagesResponse {
+        messages,
+        next_cursor,
+    }))
+}
+
+impl MessagesApi {
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/list", routing::get(list))
+            .with_state(self.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (MESSAGES_API_BASE, self.router())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_request() {
+        let request = RawMessagesRequest {
+            message_id: Some(
+                "0x9b9e7d39b4d3ab73b2ae3e6c5bd4ef6c7d2b9d5b9a3b7ac4a5c1f4a8e7b6c5d4".to_owned(),
+            ),
+            sender: Some("0x3dc2b6e5c3d2a1b0c9d8e7f6a5b4c3d2e1f0a9b8".to_owned()),
+            tx_hash: Some(
+                "a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90".to_owned(),
+            ),
+            origin: Some(1),
+            cursor: Some(100),
+            limit: Some(10_000),
+            ..Default::default()
+        };
+        let (filter, limit) = parse_request(request).unwrap();
+
+        assert_eq!(limit, MAX_PAGE_SIZE);
+        assert_eq!(
+            filter,
+            MessageFilter {
+                message_id: Some(
+                    H256::from_str(
+                        "9b9e7d39b4d3ab73b2ae3e6c5bd4ef6c7d2b9d5b9a3b7ac4a5c1f4a8e7b6c5d4"
+                    )
+                    .unwrap()
+                ),
+                sender: Some(
+                    H256::from_str(
+                        "0000000000000000000000003dc2b6e5c3d2a1b0c9d8e7f6a5b4c3d2e1f0a9b8"
+                    )
+                    .unwrap()
+                ),
+                origin_tx_hash: Some(
+                    H256::from_str(
+                        "a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90"
+                    )
+                    .unwrap()
+                    .into()
+                ),
+                origin: Some(1),
+                before_id: Some(100),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_request_rejects_bad_hashes() {
+        let request = RawMessagesRequest {
+            message_id: Some("0x1234".to_owned()),
+            ..Default::default()
+        };
+        assert!(parse_request(request).is_err());
+
+        let request = RawMessagesRequest {
+            tx_hash: Some("not hex".to_owned()),
+            ..Default::default()
+        };
+        assert!(parse_request(request).is_err());
+    }
+}
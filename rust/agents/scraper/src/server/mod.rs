@@ -0,0 +1,14 @@
+use axum::Router;
+
+use crate::db::ScraperDb;
+
+pub use messages::*;
+
+mod messages;
+
+/// Returns a vector of agent-specific endpoint routes to be served.
+pub fn routes(db: ScraperDb) -> Vec<(&'static str, Router)> {
+    let messages_api = MessagesApi::new(db);
+
+    vec![messages_api.get_route()]
+}
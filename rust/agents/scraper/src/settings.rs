@@ -4,15 +4,15 @@
 //! and validations it defines are not applied here, we should mirror them.
 //! ANY CHANGES HERE NEED TO BE REFLECTED IN THE TYPESCRIPT SDK.
 
-use std::{collections::HashSet, default::Default};
+use std::{collections::HashSet, default::Default, ops::RangeInclusive};
 
 use derive_more::{AsMut, AsRef, Deref, DerefMut};
-use eyre::Context;
+use eyre::{eyre, Context};
 use hyperlane_base::{
     impl_loadable_from_settings,
     settings::{
         parser::{RawAgentConf, ValueParser},
-        Settings,
+        ChainConnectionConf, Settings,
     },
 };
 use hyperlane_core::{cfg_unwrap_all, config::*, HyperlaneDomain};
@@ -30,8 +30,28 @@ pub struct ScraperSettings {
 
     pub db: String,
     pub chains_to_scrape: Vec<HyperlaneDomain>,
+    /// Whether to serve the API for querying scraped messages
+    pub enable_api: bool,
+    /// If set, scrape a fixed range of history and exit instead of following
+    /// the chain head
+    pub backfill: Option<BackfillSettings>,
 }
 
+/// Settings for scraping a fixed range of history
+#[derive(Debug, Clone)]
+pub struct BackfillSettings {
+    /// The inclusive range to scrape on each chain. Block heights for
+    /// block-indexed chains, sequences otherwise.
+    pub ranges: Vec<(HyperlaneDomain, RangeInclusive<u32>)>,
+    /// Number of chunks fetched at once on each chain
+    pub concurrency: usize,
+    /// Number of chunks whose logs are written to the database together
+    pub batch_size: usize,
+}
+
+const DEFAULT_BACKFILL_CONCURRENCY: usize = 8;
+const DEFAULT_BACKFILL_BATCH_SIZE: usize = 10;
+
 #[derive(Debug, Deserialize)]
 #[serde(transparent)]
 struct RawScraperSettings(Value);
@@ -69,6 +89,12 @@ impl FromRawConf<RawScraperSettings> for ScraperSettings {
             .end()
             .map(|v| v.to_owned());
 
+        let enable_api = p
+            .chain(&mut err)
+            .get_opt_key("enableApi")
+            .parse_bool()
+            .unwrap_or(false);
+
         let chains_to_scrape = if let (Some(base), Some(chains)) = (&base, chains_names_to_scrape) {
             chains
                 .into_iter()
@@ -83,12 +109,80 @@ impl FromRawConf<RawScraperSettings> for ScraperSettings {
             Default::default()
         };
 
+        let backfill = p
+            .chain(&mut err)
+            .get_opt_key("backfill")
+            .end()
+            .map(|backfill| parse_backfill(backfill, base.as_ref(), &mut err));
+
         cfg_unwrap_all!(&p.cwp, err: [base, db]);
 
+        let mut base = base;
+        // The scraper stores the block and transaction of every log, which
+        // other agents don't need
+        for chain in base.chains.values_mut() {
+            if let ChainConnectionConf::Sealevel(conf) = &mut chain.connection {
+                conf.index_transaction_details = true;
+            }
+        }
+
         err.into_result(Self {
             base,
             db,
             chains_to_scrape,
+            enable_api,
+            backfill,
+        })
+    }
+}
+
+fn parse_backfill(
+    p: ValueParser,
+    base: Option<&Settings>,
+    err: &mut ConfigParsingError,
+) -> BackfillSettings {
+    let ranges = p
+        .chain(err)
+        .get_key("chains")
+        .into_obj_iter()
+        .map(|chains| chains.collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(name, range)| {
+            let from = range.chain(err).get_key("from").parse_u32().end();
+            let to = range.chain(err).get_key("to").parse_u32().end();
+            let domain = base?
+                .lookup_domain(&name)
+                .context("Backfill chains must also be in `chainsToScrape`")
+                .into_config_result(|| range.cwp.clone())
+                .take_config_err(err);
+            let (domain, from, to) = (domain?, from?, to?);
+            if from > to {
+                Err(eyre!("Backfill range starts after it ends"))
+                    .into_config_result(|| range.cwp.clone())
+                    .take_config_err(err)?;
+            }
+            Some((domain, from..=to))
         })
+        .collect();
+
+    let concurrency = p
+        .chain(err)
+        .get_opt_key("concurrency")
+        .parse_u32()
+        .map(|v| v as usize)
+        .unwrap_or(DEFAULT_BACKFILL_CONCURRENCY);
+
+    let batch_size = p
+        .chain(err)
+        .get_opt_key("batchSize")
+        .parse_u32()
+        .map(|v| v as usize)
+        .unwrap_or(DEFAULT_BACKFILL_BATCH_SIZE);
+
+    BackfillSettings {
+        ranges,
+        concurrency,
+        batch_size,
     }
 }
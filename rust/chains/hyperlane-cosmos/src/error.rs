@@ -47,6 +47,9 @@ pub enum HyperlaneCosmosError {
     /// Fallback providers failed
     #[error("Fallback providers failed. (Errors: {0:?})")]
     FallbackProvidersFailed(Vec<HyperlaneCosmosError>),
+    /// A message id in an event is not 32 bytes long
+    #[error("Invalid message id length: expected 32 bytes, got {0}")]
+    InvalidMessageIdLength(usize),
 }
 
 impl From<HyperlaneCosmosError> for ChainCommunicationError {
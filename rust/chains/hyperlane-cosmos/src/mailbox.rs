@@ -262,16 +262,23 @@ const MESSAGE_ATTRIBUTE_KEY: &str = "message";
 static MESSAGE_ATTRIBUTE_KEY_BASE64: Lazy<String> =
     Lazy::new(|| BASE64.encode(MESSAGE_ATTRIBUTE_KEY));
 
+const MESSAGE_ID_ATTRIBUTE_KEY: &str = "message_id";
+static MESSAGE_ID_ATTRIBUTE_KEY_BASE64: Lazy<String> =
+    Lazy::new(|| BASE64.encode(MESSAGE_ID_ATTRIBUTE_KEY));
+
 /// Struct that retrieves event data for a Cosmos Mailbox contract
 #[derive(Debug, Clone)]
 pub struct CosmosMailboxIndexer {
     mailbox: CosmosMailbox,
     indexer: Box<CosmosWasmIndexer>,
+    delivery_indexer: Box<CosmosWasmIndexer>,
 }
 
 impl CosmosMailboxIndexer {
     /// The message dispatch event type from the CW contract.
     const MESSAGE_DISPATCH_EVENT_TYPE: &str = "mailbox_dispatch";
+    /// The message process event type from the CW contract.
+    const MESSAGE_PROCESS_ID_EVENT_TYPE: &str = "mailbox_process_id";
 
     /// Create a reference to a mailbox at a specific Cosmos address on some
     /// chain
@@ -283,15 +290,22 @@ impl CosmosMailboxIndexer {
     ) -> ChainResult<Self> {
         let mailbox = CosmosMailbox::new(conf.clone(), locator.clone(), signer.clone())?;
         let indexer = CosmosWasmIndexer::new(
+            conf.clone(),
+            locator.clone(),
+            Self::MESSAGE_DISPATCH_EVENT_TYPE.into(),
+            reorg_period,
+        )?;
+        let delivery_indexer = CosmosWasmIndexer::new(
             conf,
             locator,
-            Self::MESSAGE_DISPATCH_EVENT_TYPE.into(),
+            Self::MESSAGE_PROCESS_ID_EVENT_TYPE.into(),
             reorg_period,
         )?;
 
         Ok(Self {
             mailbox,
             indexer: Box::new(indexer),
+            delivery_indexer: Box::new(delivery_indexer),
         })
     }
 
@@ -346,6 +360,59 @@ impl CosmosMailboxIndexer {
 
         Ok(ParsedEvent::new(contract_address, message))
     }
+
+    #[instrument(err)]
+    fn message_delivery_parser(attrs: &Vec<EventAttribute>) -> ChainResult<ParsedEvent<H256>> {
+        let mut contract_address: Option<String> = None;
+        let mut message_id: Option<H256> = None;
+
+        for attr in attrs {
+            let key = attr.key.as_str();
+            let value = attr.value.as_str();
+
+            match key {
+                CONTRACT_ADDRESS_ATTRIBUTE_KEY => {
+                    contract_address = Some(value.to_string());
+                }
+                v if *CONTRACT_ADDRESS_ATTRIBUTE_KEY_BASE64 == v => {
+                    contract_address = Some(String::from_utf8(
+                        BASE64
+                            .decode(value)
+                            .map_err(Into::<HyperlaneCosmosError>::into)?,
+                    )?);
+                }
+
+                MESSAGE_ID_ATTRIBUTE_KEY => {
+                    message_id = Some(Self::parse_message_id(value)?);
+                }
+                v if *MESSAGE_ID_ATTRIBUTE_KEY_BASE64 == v => {
+                    message_id = Some(Self::parse_message_id(&String::from_utf8(
+                        BASE64
+                            .decode(value)
+                            .map_err(Into::<HyperlaneCosmosError>::into)?,
+                    )?)?);
+                }
+
+                _ => {}
+            }
+        }
+
+        let contract_address = contract_address
+            .ok_or_else(|| ChainCommunicationError::from_other_str("missing contract_address"))?;
+        let message_id = message_id
+            .ok_or_else(|| ChainCommunicationError::from_other_str("missing message_id"))?;
+
+        Ok(ParsedEvent::new(contract_address, message_id))
+    }
+
+    /// Parse a hex encoded message id, which must be exactly 32 bytes
+    fn parse_message_id(value: &str) -> ChainResult<H256> {
+        let bytes = hex::decode(value)?;
+        if bytes.len() != H256::len_bytes() {
+            return Err(HyperlaneCosmosError::InvalidMessageIdLength(bytes.len()).into());
+        }
+        Ok(H256::from_slice(&bytes))
+    }
 }
 
 #[async_trait]
@@ -401,8 +468,43 @@ impl Indexer<H256> for CosmosMailboxIndexer {
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
-        // TODO: implement when implementing Cosmos scraping
-        todo!()
+        let logs_futures: Vec<_> = range
+            .map(|block_number| {
+                let self_clone = self.clone();
+                tokio::spawn(async move {
+                    let logs = self_clone
+                        .delivery_indexer
+                        .get_logs_in_block(
+                            block_number,
+                            Self::message_delivery_parser,
+                            "DeliveryCursor",
+                        )
+                        .await;
+                    (logs, block_number)
+                })
+            })
+            .collect();
+
+        // TODO: this can be refactored when we rework indexing, to be part of the block-by-block indexing
+        let result = future::join_all(logs_futures)
+            .await
+            .into_iter()
+            .flatten()
+            .map(|(logs, block_number)| {
+                if let Err(err) = &logs {
+                    warn!(?err, ?block_number, "Failed to fetch logs for block");
+                }
+                logs
+            })
+            // Propagate errors from any of the queries. This will cause the entire range to be retried,
+            // including successful ones, but we don't have a way to handle partial failures in a range for now.
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
+            .map(|(log, meta)| (Indexed::new(log), meta))
+            .collect();
+
+        Ok(result)
     }
 
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
@@ -466,4 +568,42 @@ mod tests {
         );
         assert_parsed_event(&base64_attrs);
     }
+
+    #[test]
+    fn test_message_delivery_parser() {
+        let expected = ParsedEvent::new(
+            "neutron1sjzzd4gwkggy6hrrs8kxxatexzcuz3jecsxm3wqgregkulzj8r7qlnuef4".into(),
+            H256::from_str("9e09dc9d3bc7b3c8ac0ee64d4b3e7a3f0b4f6a6fa0f1d4b2e7d2b5e2a1c3d4e5")
+                .unwrap(),
+        );
+
+        let assert_parsed_event = |attrs: &Vec<EventAttribute>| {
+            let parsed_event = CosmosMailboxIndexer::message_delivery_parser(attrs).unwrap();
+
+            assert_eq!(parsed_event, expected);
+        };
+
+        // Non-base64 version
+        let non_base64_attrs = event_attributes_from_str(
+            r#"[{"key":"_contract_address","value":"neutron1sjzzd4gwkggy6hrrs8kxxatexzcuz3jecsxm3wqgregkulzj8r7qlnuef4","index":true},{"key":"message_id","value":"9e09dc9d3bc7b3c8ac0ee64d4b3e7a3f0b4f6a6fa0f1d4b2e7d2b5e2a1c3d4e5","index":true}]"#,
+        );
+        assert_parsed_event(&non_base64_attrs);
+
+        // Base64 version
+        let base64_attrs = event_attributes_from_str(
+            r#"[{"key":"X2NvbnRyYWN0X2FkZHJlc3M=","value":"bmV1dHJvbjFzanp6ZDRnd2tnZ3k2aHJyczhreHhhdGV4emN1ejNqZWNzeG0zd3FncmVna3Vsemo4cjdxbG51ZWY0","index":true},{"key":"bWVzc2FnZV9pZA==","value":"OWUwOWRjOWQzYmM3YjNjOGFjMGVlNjRkNGIzZTdhM2YwYjRmNmE2ZmEwZjFkNGIyZTdkMmI1ZTJhMWMzZDRlNQ==","index":true}]"#,
+        );
+        assert_parsed_event(&base64_attrs);
+
+        // Message ids that aren't 32 bytes are rejected instead of panicking
+        let short_id_attrs = event_attributes_from_str(
+            r#"[{"key":"_contract_address","value":"neutron1sjzzd4gwkggy6hrrs8kxxatexzcuz3jecsxm3wqgregkulzj8r7qlnuef4","index":true},{"key":"message_id","value":"9e09dc9d","index":true}]"#,
+        );
+        assert!(CosmosMailboxIndexer::message_delivery_parser(&short_id_attrs).is_err());
+        // Base64 of "9e09"
+        let short_base64_id_attrs = event_attributes_from_str(
+            r#"[{"key":"X2NvbnRyYWN0X2FkZHJlc3M=","value":"bmV1dHJvbjFzanp6ZDRnd2tnZ3k2aHJyczhreHhhdGV4emN1ejNqZWNzeG0zd3FncmVna3Vsemo4cjdxbG51ZWY0","index":true},{"key":"bWVzc2FnZV9pZA==","value":"OWUwOQ==","index":true}]"#,
+        );
+        assert!(CosmosMailboxIndexer::message_delivery_parser(&short_base64_id_attrs).is_err());
+    }
 }
@@ -16,13 +16,13 @@ use cosmrs::{
             },
         },
         cosmwasm::wasm::v1::{
-            query_client::QueryClient as WasmQueryClient, MsgExecuteContract,
-            QuerySmartContractStateRequest,
+            query_client::QueryClient as WasmQueryClient, ContractInfo, MsgExecuteContract,
+            QueryContractInfoRequest, QuerySmartContractStateRequest,
         },
         traits::Message,
     },
     tx::{self, Fee, MessageExt, SignDoc, SignerInfo},
-    Any, Coin,
+    AccountId, Any, Coin,
 };
 use derive_new::new;
 use hyperlane_core::{
@@ -31,12 +31,12 @@ use hyperlane_core::{
 };
 use protobuf::Message as _;
 use serde::Serialize;
-use std::fmt::Debug;
+use std::{fmt::Debug, str::FromStr};
 use tonic::{
     transport::{Channel, Endpoint},
     GrpcMethod, IntoRequest,
 };
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
 use url::Url;
 
 use crate::{address::CosmosAddress, CosmosAmount};
@@ -49,6 +49,14 @@ const GAS_ESTIMATE_MULTIPLIER: f64 = 1.25;
 /// The number of blocks in the future in which a transaction will
 /// be valid for.
 const TIMEOUT_BLOCKS: u64 = 1000;
+/// The cosmos-sdk error code for a transaction whose fee is below the
+/// minimum gas price of the node it was sent to.
+const INSUFFICIENT_FEE_CODE: u32 = 13;
+/// How much to raise the fee by, in percent, each time a transaction is
+/// rejected for paying too little.
+const FEE_BUMP_PERCENT: u128 = 50;
+/// The maximum number of times a transaction is resent with a higher fee.
+const MAX_FEE_BUMPS: usize = 3;
 
 #[derive(Debug, Clone, new)]
 struct CosmosChannel {
@@ -135,6 +143,8 @@ pub struct WasmGrpcProvider {
     /// See `<https://docs.rs/tonic/latest/tonic/transport/struct.Channel.html#multiplexing-requests>`
     provider: CosmosFallbackProvider<CosmosChannel>,
     gas_price: CosmosAmount,
+    /// Account that pays for transaction fees through a fee grant, if any.
+    fee_granter: Option<AccountId>,
 }
 
 impl WasmGrpcProvider {
@@ -171,6 +181,14 @@ impl WasmGrpcProvider {
             })
             .transpose()?;
 
+        let fee_granter = conf
+            .get_transaction_overrides()
+            .fee_granter
+            .as_deref()
+            .map(AccountId::from_str)
+            .transpose()
+            .map_err(Into::<HyperlaneCosmosError>::into)?;
+
         Ok(Self {
             domain,
             conf,
@@ -178,6 +196,7 @@ impl WasmGrpcProvider {
             signer,
             provider,
             gas_price,
+            fee_granter,
         })
     }
 
@@ -193,12 +212,22 @@ impl WasmGrpcProvider {
         self.gas_price.amount.clone()
     }
 
+    /// The fee to pay for a transaction with the given gas limit at the
+    /// configured gas price.
+    fn fee_amount(&self, gas_limit: u64) -> ChainResult<u128> {
+        // The fee to pay is the gas limit * the gas price
+        Ok((FixedPointNumber::from(gas_limit) * self.gas_price())
+            .ceil_to_integer()
+            .try_into()?)
+    }
+
     /// Generates an unsigned SignDoc for a transaction and the Coin amount
     /// required to pay for tx fees.
     async fn generate_unsigned_sign_doc_and_fee(
         &self,
         msgs: Vec<cosmrs::Any>,
         gas_limit: u64,
+        fee_amount: u128,
     ) -> ChainResult<(SignDoc, Coin)> {
         // As this function is only used for estimating gas or sending transactions,
         // we can reasonably expect to have a signer.
@@ -215,17 +244,11 @@ impl WasmGrpcProvider {
         );
         let signer_info = SignerInfo::single_direct(Some(signer.public_key), account_info.sequence);
 
-        let amount: u128 = (FixedPointNumber::from(gas_limit) * self.gas_price())
-            .ceil_to_integer()
-            .try_into()?;
-        let fee_coin = Coin::new(
-            // The fee to pay is the gas limit * the gas price
-            amount,
-            self.conf.get_canonical_asset().as_str(),
-        )
-        .map_err(Into::<HyperlaneCosmosError>::into)?;
-        let auth_info =
-            signer_info.auth_info(Fee::from_amount_and_gas(fee_coin.clone(), gas_limit));
+        let fee_coin = Coin::new(fee_amount, self.conf.get_canonical_asset().as_str())
+            .map_err(Into::<HyperlaneCosmosError>::into)?;
+        let mut fee = Fee::from_amount_and_gas(fee_coin.clone(), gas_limit);
+        fee.granter = self.fee_granter.clone();
+        let auth_info = signer_info.auth_info(fee);
 
         let chain_id = self
             .conf
@@ -240,21 +263,16 @@ impl WasmGrpcProvider {
         ))
     }
 
-    /// Generates a raw signed transaction including `msgs`, estimating gas if a limit is not provided,
-    /// and the Coin amount required to pay for tx fees.
+    /// Generates a raw signed transaction including `msgs` that pays `fee_amount`
+    /// for `gas_limit`, and the Coin amount required to pay for tx fees.
     async fn generate_raw_signed_tx_and_fee(
         &self,
         msgs: Vec<cosmrs::Any>,
-        gas_limit: Option<u64>,
+        gas_limit: u64,
+        fee_amount: u128,
     ) -> ChainResult<(Vec<u8>, Coin)> {
-        let gas_limit = if let Some(l) = gas_limit {
-            l
-        } else {
-            self.estimate_gas(msgs.clone()).await?
-        };
-
         let (sign_doc, fee) = self
-            .generate_unsigned_sign_doc_and_fee(msgs, gas_limit)
+            .generate_unsigned_sign_doc_and_fee(msgs, gas_limit, fee_amount)
             .await?;
 
         let signer = self.get_signer()?;
@@ -272,7 +290,7 @@ impl WasmGrpcProvider {
     /// Estimates gas for a transaction containing `msgs`.
     async fn estimate_gas(&self, msgs: Vec<cosmrs::Any>) -> ChainResult<u64> {
         // Get a sign doc with 0 gas, because we plan to simulate
-        let (sign_doc, _) = self.generate_unsigned_sign_doc_and_fee(msgs, 0).await?;
+        let (sign_doc, _) = self.generate_unsigned_sign_doc_and_fee(msgs, 0, 0).await?;
 
         let raw_tx = TxRaw {
             body_bytes: sign_doc.body_bytes,
@@ -313,11 +331,66 @@ impl WasmGrpcProvider {
             })
             .await?;
 
-        let gas_estimate = (gas_used as f64 * GAS_ESTIMATE_MULTIPLIER) as u64;
+        let gas_adjustment = self
+            .conf
+            .get_transaction_overrides()
+            .gas_adjustment
+            .unwrap_or(GAS_ESTIMATE_MULTIPLIER);
+        let gas_estimate = (gas_used as f64 * gas_adjustment) as u64;
 
         Ok(gas_estimate)
     }
 
+    /// Signs a transaction including `msgs` and broadcasts it, returning the
+    /// response of the node's `CheckTx`.
+    async fn sign_and_broadcast(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        gas_limit: u64,
+        fee_amount: u128,
+    ) -> ChainResult<TxResponse> {
+        let (tx_bytes, fee) = self
+            .generate_raw_signed_tx_and_fee(msgs, gas_limit, fee_amount)
+            .await?;
+
+        // Check if the signer has enough funds to pay for the fee so we can get
+        // a more informative error. Fees are paid by the granter if there is one.
+        if self.fee_granter.is_none() {
+            let signer = self.get_signer()?;
+            let signer_balance = self
+                .get_balance(signer.address.clone(), fee.denom.to_string())
+                .await?;
+            let fee_amount: U256 = fee.amount.into();
+            if signer_balance < fee_amount {
+                return Err(ChainCommunicationError::InsufficientFunds {
+                    required: fee_amount,
+                    available: signer_balance,
+                });
+            }
+        }
+
+        self.provider
+            .call(move |provider| {
+                let tx_bytes = tx_bytes.clone();
+                let future = async move {
+                    let mut client = TxServiceClient::new(provider.channel.clone());
+                    let tx_req = BroadcastTxRequest {
+                        tx_bytes,
+                        mode: BroadcastMode::Sync as i32,
+                    };
+                    client
+                        .broadcast_tx(tx_req)
+                        .await
+                        .map_err(Into::<HyperlaneCosmosError>::into)?
+                        .into_inner()
+                        .tx_response
+                        .ok_or_else(|| ChainCommunicationError::from_other_str("Empty tx_response"))
+                };
+                Box::pin(future)
+            })
+            .await
+    }
+
     /// Fetches balance for a given `address` and `denom`
     pub async fn get_balance(&self, address: String, denom: String) -> ChainResult<U256> {
         let response = self
@@ -347,6 +420,29 @@ impl WasmGrpcProvider {
         Ok(U256::from_dec_str(&balance.amount)?)
     }
 
+    /// Fetches the CosmWasm contract info for a given `address`, or `None` if
+    /// there is no contract at that address.
+    pub async fn contract_info(&self, address: String) -> ChainResult<Option<ContractInfo>> {
+        let response = self
+            .provider
+            .call(move |provider| {
+                let address = address.clone();
+                let future = async move {
+                    let mut client = WasmQueryClient::new(provider.channel.clone());
+                    let request = tonic::Request::new(QueryContractInfoRequest { address });
+                    match client.contract_info(request).await {
+                        Ok(response) => Ok(Some(response.into_inner())),
+                        Err(status) if status.code() == tonic::Code::NotFound => Ok(None),
+                        Err(status) => Err(ChainCommunicationError::from_other(status)),
+                    }
+                };
+                Box::pin(future)
+            })
+            .await?;
+
+        Ok(response.and_then(|response| response.contract_info))
+    }
+
     /// Queries an account.
     pub async fn account_query(&self, account: String) -> ChainResult<BaseAccount> {
         // Injective is a special case where their account query requires
@@ -561,44 +657,44 @@ impl WasmProvider for WasmGrpcProvider {
                 None
             }
         });
-        let (tx_bytes, fee) = self.generate_raw_signed_tx_and_fee(msgs, gas_limit).await?;
+        let overrides = self.conf.get_transaction_overrides();
+        let gas_limit = match overrides.gas_limit.or(gas_limit) {
+            Some(limit) => limit,
+            None => self.estimate_gas(msgs.clone()).await?,
+        };
 
-        // Check if the signer has enough funds to pay for the fee so we can get
-        // a more informative error.
-        let signer_balance = self
-            .get_balance(signer.address.clone(), fee.denom.to_string())
-            .await?;
-        let fee_amount: U256 = fee.amount.into();
-        if signer_balance < fee_amount {
-            return Err(ChainCommunicationError::InsufficientFunds {
-                required: fee_amount,
-                available: signer_balance,
-            });
+        let mut fee_amount = self.fee_amount(gas_limit)?;
+        if let Some(max_fee) = overrides.max_fee {
+            if fee_amount > max_fee {
+                return Err(ChainCommunicationError::from_other_str(
+                    "Transaction fee exceeds the configured max fee",
+                ));
+            }
         }
-
-        let tx_res = self
-            .provider
-            .call(move |provider| {
-                let tx_bytes = tx_bytes.clone();
-                let future = async move {
-                    let mut client = TxServiceClient::new(provider.channel.clone());
-                    // We often use U256s to represent gas limits, but Cosmos expects u64s. Try to convert,
-                    // and if it fails, just fallback to None which will result in gas estimation.
-                    let tx_req = BroadcastTxRequest {
-                        tx_bytes,
-                        mode: BroadcastMode::Sync as i32,
-                    };
-                    client
-                        .broadcast_tx(tx_req)
-                        .await
-                        .map_err(Into::<HyperlaneCosmosError>::into)?
-                        .into_inner()
-                        .tx_response
-                        .ok_or_else(|| ChainCommunicationError::from_other_str("Empty tx_response"))
-                };
-                Box::pin(future)
-            })
+        let mut tx_res = self
+            .sign_and_broadcast(msgs.clone(), gas_limit, fee_amount)
             .await?;
+        // Minimum gas prices are set per node and can change at any time, so
+        // retry with a higher fee if the one we paid was too low.
+        for _ in 0..MAX_FEE_BUMPS {
+            if !is_insufficient_fee(&tx_res) {
+                break;
+            }
+            let Some(bumped_fee_amount) = bumped_fee(fee_amount, overrides.max_fee) else {
+                break;
+            };
+            warn!(
+                fee_amount,
+                bumped_fee_amount,
+                raw_log = %tx_res.raw_log,
+                domain = ?self.domain,
+                "Transaction fee too low, retrying with a higher fee"
+            );
+            fee_amount = bumped_fee_amount;
+            tx_res = self
+                .sign_and_broadcast(msgs.clone(), gas_limit, fee_amount)
+                .await?;
+        }
         debug!(tx_result=?tx_res, domain=?self.domain, ?payload, "Wasm transaction sent");
         Ok(tx_res)
     }
@@ -636,3 +732,32 @@ impl BlockNumberGetter for WasmGrpcProvider {
         self.latest_block_height().await
     }
 }
+
+/// Whether a transaction was rejected for paying less than the node's
+/// minimum gas price.
+fn is_insufficient_fee(tx_res: &TxResponse) -> bool {
+    tx_res.codespace == "sdk" && tx_res.code == INSUFFICIENT_FEE_CODE
+}
+
+/// The fee to pay after a transaction paying `fee_amount` was rejected for
+/// paying too little, or None if the fee can't be raised any further.
+fn bumped_fee(fee_amount: u128, max_fee: Option<u128>) -> Option<u128> {
+    // Make sure a zero fee still increases
+    let bumped =
+        (fee_amount.saturating_mul(100 + FEE_BUMP_PERCENT) / 100).max(fee_amount.saturating_add(1));
+    let bumped = max_fee.map_or(bumped, |max_fee| bumped.min(max_fee));
+    (bumped > fee_amount).then_some(bumped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bumped_fee() {
+        assert_eq!(bumped_fee(0, None), Some(1));
+        assert_eq!(bumped_fee(100, None), Some(150));
+        assert_eq!(bumped_fee(100, Some(120)), Some(120));
+        assert_eq!(bumped_fee(120, Some(120)), None);
+    }
+}
@@ -1,11 +1,25 @@
+use std::{str::FromStr, time::Instant};
+
 use async_trait::async_trait;
+use cosmrs::Tx;
+use ethers_prometheus::json_rpc_client::{
+    ChainInfo as PrometheusChainInfo, JsonRpcClientMetrics, NodeInfo, PrometheusJsonRpcClientConfig,
+};
 use hyperlane_core::{
-    BlockInfo, ChainInfo, ChainResult, ContractLocator, HyperlaneChain, HyperlaneDomain,
-    HyperlaneProvider, TxnInfo, H256, U256,
+    rpc_clients::BlockNumberGetter, BlockInfo, ChainCommunicationError, ChainInfo, ChainResult,
+    ContractLocator, HyperlaneChain, HyperlaneDomain, HyperlaneProvider, HyperlaneProviderError,
+    TxnInfo, TxnReceiptInfo, H256, H512, U256,
 };
-use tendermint_rpc::{client::CompatMode, HttpClient};
+use tendermint::{abci::Event, block::Height, hash::Algorithm, Block, Hash};
+use tendermint_rpc::{client::CompatMode, Client, HttpClient};
+use url::Url;
 
-use crate::{ConnectionConf, CosmosAmount, HyperlaneCosmosError, Signer};
+use crate::{
+    address::CosmosAddress,
+    rpc_clients::{CosmosRpcProvider, TendermintRequest},
+    utils::find_event_attribute,
+    ConnectionConf, CosmosAmount, HyperlaneCosmosError, Signer,
+};
 
 use self::grpc::WasmGrpcProvider;
 
@@ -14,13 +28,93 @@ pub mod grpc;
 /// cosmos rpc provider
 pub mod rpc;
 
+/// A Tendermint RPC client connected to a single endpoint
+#[derive(Debug, Clone)]
+pub struct CosmosRpcClient {
+    client: HttpClient,
+    /// The url that this client is connected to.
+    /// Not explicitly used, but useful for debugging.
+    _url: Url,
+    metrics: Option<JsonRpcClientMetrics>,
+    metrics_config: PrometheusJsonRpcClientConfig,
+}
+
+impl CosmosRpcClient {
+    /// Create a client for `url`, recording its requests in `metrics` if set
+    pub fn new(
+        url: Url,
+        chain_name: &str,
+        metrics: Option<JsonRpcClientMetrics>,
+    ) -> ChainResult<Self> {
+        let client = HttpClient::builder(
+            url.as_str()
+                .parse()
+                .map_err(Into::<HyperlaneCosmosError>::into)?,
+        )
+        // Consider supporting different compatibility modes.
+        .compat_mode(CompatMode::latest())
+        .build()
+        .map_err(Into::<HyperlaneCosmosError>::into)?;
+        let metrics_config = PrometheusJsonRpcClientConfig {
+            node: Some(NodeInfo {
+                host: url.host_str().map(str::to_owned),
+            }),
+            chain: Some(PrometheusChainInfo {
+                name: Some(chain_name.to_owned()),
+            }),
+        };
+        Ok(Self {
+            client,
+            _url: url,
+            metrics,
+            metrics_config,
+        })
+    }
+
+    /// Get the underlying Tendermint RPC client
+    pub fn client(&self) -> &HttpClient {
+        &self.client
+    }
+
+    /// Await a request to this endpoint, recording it in the metrics
+    pub async fn instrument<V>(
+        self,
+        method: &str,
+        request: TendermintRequest<V>,
+    ) -> Result<V, tendermint_rpc::Error> {
+        let start = Instant::now();
+        let result = request.await;
+        if let Some(metrics) = &self.metrics {
+            metrics.increment(&self.metrics_config, method, start, result.is_ok());
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl BlockNumberGetter for CosmosRpcClient {
+    async fn get_block_number(&self) -> Result<u64, ChainCommunicationError> {
+        let client = self.client.clone();
+        let response = self
+            .clone()
+            .instrument(
+                "latest_block",
+                Box::pin(async move { client.latest_block().await }),
+            )
+            .await
+            .map_err(Into::<HyperlaneCosmosError>::into)?;
+        Ok(response.block.header.height.value())
+    }
+}
+
 /// Abstraction over a connection to a Cosmos chain
 #[derive(Debug, Clone)]
 pub struct CosmosProvider {
     domain: HyperlaneDomain,
+    connection_conf: ConnectionConf,
     canonical_asset: String,
     grpc_client: WasmGrpcProvider,
-    rpc_client: HttpClient,
+    rpc_client: CosmosRpcProvider,
 }
 
 impl CosmosProvider {
@@ -39,21 +133,19 @@ impl CosmosProvider {
             locator,
             signer,
         )?;
-        let rpc_client = HttpClient::builder(
-            conf.get_rpc_url()
-                .parse()
-                .map_err(Into::<HyperlaneCosmosError>::into)?,
-        )
-        // Consider supporting different compatibility modes.
-        .compat_mode(CompatMode::latest())
-        .build()
-        .map_err(Into::<HyperlaneCosmosError>::into)?;
+        let clients = conf
+            .get_rpc_urls()
+            .into_iter()
+            .map(|url| CosmosRpcClient::new(url, domain.name(), conf.get_rpc_metrics()))
+            .collect::<ChainResult<Vec<_>>>()?;
+        let rpc_client = CosmosRpcProvider::new(clients, conf.get_rpc_consensus_type());
 
         Ok(Self {
             domain,
             rpc_client,
             grpc_client,
             canonical_asset: conf.get_canonical_asset(),
+            connection_conf: conf,
         })
     }
 
@@ -63,9 +155,34 @@ impl CosmosProvider {
     }
 
     /// Get an rpc client
-    pub fn rpc(&self) -> &HttpClient {
+    pub fn rpc(&self) -> &CosmosRpcProvider {
         &self.rpc_client
     }
+
+    fn block_info(hash: Hash, block: Block) -> BlockInfo {
+        BlockInfo {
+            hash: H256::from_slice(hash.as_bytes()),
+            timestamp: block.header.time.unix_timestamp() as u64,
+            number: block.header.height.value(),
+        }
+    }
+
+    /// The bech32 address of the account that signed a transaction, taken
+    /// from the events it emitted. This also works for chains such as
+    /// Injective that use non-standard public keys.
+    fn txn_sender(events: &[Event]) -> ChainResult<H256> {
+        let sender = find_event_attribute(events, "message", "sender").ok_or_else(|| {
+            ChainCommunicationError::from_other_str("Transaction has no message sender")
+        })?;
+        Ok(CosmosAddress::from_str(&sender)?.digest())
+    }
+
+    /// The first contract executed by a transaction, if any
+    fn txn_recipient(events: &[Event]) -> ChainResult<Option<H256>> {
+        find_event_attribute(events, "execute", "_contract_address")
+            .map(|contract| Ok(CosmosAddress::from_str(&contract)?.digest()))
+            .transpose()
+    }
 }
 
 impl HyperlaneChain for CosmosProvider {
@@ -80,17 +197,98 @@ impl HyperlaneChain for CosmosProvider {
 
 #[async_trait]
 impl HyperlaneProvider for CosmosProvider {
-    async fn get_block_by_hash(&self, _hash: &H256) -> ChainResult<BlockInfo> {
-        todo!() // FIXME
+    async fn get_block_by_hash(&self, hash: &H256) -> ChainResult<BlockInfo> {
+        let tendermint_hash = Hash::from_bytes(Algorithm::Sha256, hash.as_bytes())
+            .map_err(Into::<HyperlaneCosmosError>::into)?;
+        let response = self
+            .rpc_client
+            .call("block_by_hash", move |client| {
+                Box::pin(async move { client.block_by_hash(tendermint_hash).await })
+            })
+            .await?;
+        let block = response
+            .block
+            .ok_or(HyperlaneProviderError::CouldNotFindObjectByHash(*hash))?;
+        Ok(Self::block_info(response.block_id.hash, block))
+    }
+
+    async fn get_block_by_height(&self, height: u64) -> ChainResult<BlockInfo> {
+        let tendermint_height =
+            Height::try_from(height).map_err(Into::<HyperlaneCosmosError>::into)?;
+        let response = self
+            .rpc_client
+            .call("block", move |client| {
+                Box::pin(async move { client.block(tendermint_height).await })
+            })
+            .await?;
+        Ok(Self::block_info(response.block_id.hash, response.block))
     }
 
-    async fn get_txn_by_hash(&self, _hash: &H256) -> ChainResult<TxnInfo> {
-        todo!() // FIXME
+    /// Cosmos fees are paid up front for the whole gas limit, so the gas
+    /// price is the fee in the canonical asset divided by the gas limit.
+    async fn get_txn_by_hash(&self, hash: &H512) -> ChainResult<TxnInfo> {
+        let txn_hash: H256 = (*hash).into();
+        let tendermint_hash = Hash::from_bytes(Algorithm::Sha256, txn_hash.as_bytes())
+            .map_err(Into::<HyperlaneCosmosError>::into)?;
+        let response = self
+            .rpc_client
+            .call("tx", move |client| {
+                Box::pin(async move { client.tx(tendermint_hash, false).await })
+            })
+            .await?;
+        let tx = Tx::from_bytes(&response.tx).map_err(Into::<HyperlaneCosmosError>::into)?;
+
+        let gas_limit = U256::from(tx.auth_info.fee.gas_limit);
+        let fee: U256 = tx
+            .auth_info
+            .fee
+            .amount
+            .iter()
+            .filter(|coin| coin.denom.as_ref() == self.canonical_asset)
+            .map(|coin| U256::from(coin.amount))
+            .fold(U256::zero(), |acc, amount| acc + amount);
+        let gas_price = if gas_limit.is_zero() {
+            U256::zero()
+        } else {
+            fee / gas_limit
+        };
+        let nonce = tx
+            .auth_info
+            .signer_infos
+            .first()
+            .map(|signer| signer.sequence)
+            .unwrap_or_default();
+        let gas_used = U256::from(response.tx_result.gas_used.max(0) as u64);
+        let events = &response.tx_result.events;
+
+        Ok(TxnInfo {
+            hash: *hash,
+            gas_limit,
+            max_priority_fee_per_gas: None,
+            max_fee_per_gas: None,
+            gas_price: Some(gas_price),
+            nonce,
+            sender: Self::txn_sender(events)?,
+            recipient: Self::txn_recipient(events)?,
+            receipt: Some(TxnReceiptInfo {
+                gas_used,
+                cumulative_gas_used: gas_used,
+                effective_gas_price: Some(gas_price),
+            }),
+        })
     }
 
-    async fn is_contract(&self, _address: &H256) -> ChainResult<bool> {
-        // FIXME
-        Ok(true)
+    async fn is_contract(&self, address: &H256) -> ChainResult<bool> {
+        let contract_address = CosmosAddress::from_h256(
+            *address,
+            &self.connection_conf.get_bech32_prefix(),
+            self.connection_conf.get_contract_address_bytes(),
+        )?;
+        let contract_info = self
+            .grpc_client
+            .contract_info(contract_address.address())
+            .await?;
+        Ok(contract_info.is_some())
     }
 
     async fn get_balance(&self, address: String) -> ChainResult<U256> {
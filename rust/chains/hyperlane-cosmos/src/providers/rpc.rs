@@ -9,11 +9,11 @@ use tendermint::hash::Algorithm;
 use tendermint::Hash;
 use tendermint_rpc::endpoint::block::Response as BlockResponse;
 use tendermint_rpc::endpoint::block_results::Response as BlockResultsResponse;
-use tendermint_rpc::HttpClient;
 use tracing::{debug, instrument, trace};
 
 use crate::address::CosmosAddress;
-use crate::{ConnectionConf, CosmosProvider, HyperlaneCosmosError};
+use crate::rpc_clients::CosmosRpcProvider;
+use crate::{ConnectionConf, CosmosProvider};
 
 #[async_trait]
 /// Trait for wasm indexer. Use rpc provider
@@ -91,28 +91,30 @@ impl CosmosWasmIndexer {
         })
     }
 
-    async fn get_block(client: HttpClient, block_number: u32) -> ChainResult<BlockResponse> {
-        Ok(client
-            .block(block_number)
+    async fn get_block(
+        provider: CosmosRpcProvider,
+        block_number: u32,
+    ) -> ChainResult<BlockResponse> {
+        provider
+            .call("block", move |client| {
+                Box::pin(async move { client.block(block_number).await })
+            })
             .await
-            .map_err(Into::<HyperlaneCosmosError>::into)?)
     }
 
     async fn get_block_results(
-        client: HttpClient,
+        provider: CosmosRpcProvider,
         block_number: u32,
     ) -> ChainResult<BlockResultsResponse> {
-        Ok(client
-            .block_results(block_number)
+        provider
+            .call("block_results", move |client| {
+                Box::pin(async move { client.block_results(block_number).await })
+            })
             .await
-            .map_err(Into::<HyperlaneCosmosError>::into)?)
     }
 
-    async fn get_latest_block(client: HttpClient) -> ChainResult<BlockResponse> {
-        Ok(client
-            .latest_block()
-            .await
-            .map_err(Into::<HyperlaneCosmosError>::into)?)
+    async fn get_latest_block(provider: CosmosRpcProvider) -> ChainResult<BlockResponse> {
+        provider.latest_block().await
     }
 }
 
@@ -1,3 +1,4 @@
-pub use self::fallback::*;
+pub use self::{fallback::*, rpc_provider::*};
 
 mod fallback;
+mod rpc_provider;
@@ -0,0 +1,162 @@
+use std::{future::Future, pin::Pin};
+
+use hyperlane_core::{
+    rpc_clients::{FallbackProvider, QuorumProvider, RpcConsensusType},
+    ChainResult,
+};
+use serde::Serialize;
+use tendermint::block::Height;
+use tendermint_rpc::{
+    endpoint::block::Response as BlockResponse, error::ErrorDetail, Client, HttpClient,
+};
+
+use crate::{CosmosRpcClient, HyperlaneCosmosError};
+
+use super::CosmosFallbackProvider;
+
+/// Error returned by a node asked for a block it hasn't reached yet
+const NODE_BEHIND_ERROR: &str = "must be less than or equal to the current blockchain height";
+
+/// A request to a single Tendermint RPC endpoint
+pub type TendermintRequest<V> =
+    Pin<Box<dyn Future<Output = Result<V, tendermint_rpc::Error>> + Send>>;
+
+/// Sends Tendermint RPC requests to the configured endpoints
+#[derive(Debug, Clone)]
+pub enum CosmosRpcProvider {
+    /// Each request goes to the highest priority endpoint that is healthy
+    Fallback(CosmosFallbackProvider<CosmosRpcClient>),
+    /// Each request goes to every endpoint and a quorum of them must agree
+    /// on the response
+    Quorum(QuorumProvider<CosmosRpcClient>),
+}
+
+impl CosmosRpcProvider {
+    /// Create a provider over `clients`, in order of priority
+    pub fn new(clients: Vec<CosmosRpcClient>, consensus_type: RpcConsensusType) -> Self {
+        match consensus_type {
+            RpcConsensusType::Fallback => {
+                Self::Fallback(CosmosFallbackProvider::new(FallbackProvider::new(clients)))
+            }
+            RpcConsensusType::Quorum => Self::Quorum(QuorumProvider::new(clients)),
+        }
+    }
+
+    /// Send the request made by `f`, which is labelled `method` in the
+    /// metrics.
+    ///
+    /// Error responses from a healthy node (e.g. for a transaction that
+    /// doesn't exist) would be the same on every endpoint, so they are
+    /// returned as-is rather than retried elsewhere.
+    pub async fn call<V>(
+        &self,
+        method: &'static str,
+        f: impl Fn(HttpClient) -> TendermintRequest<V>,
+    ) -> ChainResult<V>
+    where
+        V: Serialize + Send + 'static,
+    {
+        match self {
+            Self::Fallback(provider) => provider
+                .call(|rpc| {
+                    let request = f(rpc.client().clone());
+                    let future = async move {
+                        match rpc.instrument(method, request).await {
+                            Err(err) if Self::should_fallback(&err) => {
+                                Err(HyperlaneCosmosError::from(err).into())
+                            }
+                            result => Ok(result),
+                        }
+                    };
+                    Box::pin(future)
+                })
+                .await?
+                .map_err(|err| HyperlaneCosmosError::from(err).into()),
+            Self::Quorum(provider) => {
+                provider
+                    .call(|rpc| {
+                        let request = f(rpc.client().clone());
+                        let future = async move {
+                            Ok(rpc
+                                .instrument(method, request)
+                                .await
+                                .map_err(HyperlaneCosmosError::from)?)
+                        };
+                        Box::pin(future)
+                    })
+                    .await
+            }
+        }
+    }
+
+    /// Get the latest block. With a quorum, this is the highest block that a
+    /// quorum of endpoints have reached.
+    pub async fn latest_block(&self) -> ChainResult<BlockResponse> {
+        let Self::Quorum(provider) = self else {
+            return self
+                .call("latest_block", |client| {
+                    Box::pin(async move { client.latest_block().await })
+                })
+                .await;
+        };
+        let heights = provider
+            .call_all(|rpc| {
+                let client = rpc.client().clone();
+                let request: TendermintRequest<_> =
+                    Box::pin(async move { client.latest_block().await });
+                let future = async move {
+                    let response = rpc
+                        .instrument("latest_block", request)
+                        .await
+                        .map_err(HyperlaneCosmosError::from)?;
+                    Ok(response.block.header.height.value())
+                };
+                Box::pin(future)
+            })
+            .await;
+        let height = Height::try_from(provider.agreed_height(heights)?)
+            .map_err(Into::<HyperlaneCosmosError>::into)?;
+        self.call("block", move |client| {
+            Box::pin(async move { client.block(height).await })
+        })
+        .await
+    }
+
+    /// Whether an error means the endpoint, rather than the request, is at fault
+    fn should_fallback(err: &tendermint_rpc::Error) -> bool {
+        match err.detail() {
+            ErrorDetail::Response(detail) => detail
+                .source
+                .data()
+                .map_or(false, |data| data.contains(NODE_BEHIND_ERROR)),
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tendermint_rpc::response_error::{Code, ResponseError};
+
+    use super::*;
+
+    fn response_error(data: &str) -> tendermint_rpc::Error {
+        tendermint_rpc::Error::response(ResponseError::new(
+            Code::InternalError,
+            Some(data.to_owned()),
+        ))
+    }
+
+    #[test]
+    fn test_should_fallback() {
+        assert!(!CosmosRpcProvider::should_fallback(&response_error(
+            "tx (4A1B) not found"
+        )));
+        assert!(CosmosRpcProvider::should_fallback(&response_error(
+            "height 100 must be less than or equal to the current blockchain height 99"
+        )));
+        assert!(CosmosRpcProvider::should_fallback(
+            &tendermint_rpc::Error::io(std::io::ErrorKind::ConnectionRefused.into())
+        ));
+    }
+}
@@ -1,7 +1,11 @@
 use std::str::FromStr;
 
 use derive_new::new;
-use hyperlane_core::{config::OperationBatchConfig, ChainCommunicationError, FixedPointNumber};
+use ethers_prometheus::json_rpc_client::JsonRpcClientMetrics;
+use hyperlane_core::{
+    config::OperationBatchConfig, rpc_clients::RpcConsensusType, ChainCommunicationError,
+    FixedPointNumber,
+};
 use url::Url;
 
 /// Cosmos connection configuration
@@ -9,8 +13,10 @@ use url::Url;
 pub struct ConnectionConf {
     /// The GRPC url to connect to
     grpc_urls: Vec<Url>,
-    /// The RPC url to connect to
-    rpc_url: String,
+    /// The RPC urls to connect to, in order of priority
+    rpc_urls: Vec<Url>,
+    /// How requests are spread over `rpc_urls`
+    rpc_consensus_type: RpcConsensusType,
     /// The chain ID
     chain_id: String,
     /// The human readable address prefix for the chains using bech32.
@@ -27,6 +33,28 @@ pub struct ConnectionConf {
     contract_address_bytes: usize,
     /// Operation batching configuration
     pub operation_batch: OperationBatchConfig,
+    /// Transaction overrides to use when sending transactions.
+    transaction_overrides: TransactionOverrides,
+    /// Metrics recorded for the requests to each RPC url. These are set by
+    /// the agent when building chain clients rather than parsed from config.
+    rpc_metrics: Option<JsonRpcClientMetrics>,
+}
+
+/// Cosmos transaction overrides.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionOverrides {
+    /// Gas limit to use for transactions.
+    /// If specified, gas is not estimated and all transactions will use this gas limit.
+    pub gas_limit: Option<u64>,
+    /// Multiplier applied to the simulated gas usage of a transaction to
+    /// get its gas limit. Defaults to 1.25.
+    pub gas_adjustment: Option<f64>,
+    /// The most a single transaction may pay in fees, in the canonical asset.
+    /// Gas price bumps after an insufficient fee error stop at this amount.
+    pub max_fee: Option<u128>,
+    /// Bech32 address of an account that has granted the signer a fee allowance.
+    /// If specified, fees are paid by this account instead of the signer.
+    pub fee_granter: Option<String>,
 }
 
 /// Untyped cosmos amount
@@ -83,9 +111,24 @@ impl ConnectionConf {
         self.grpc_urls.clone()
     }
 
-    /// Get the RPC url
-    pub fn get_rpc_url(&self) -> String {
-        self.rpc_url.clone()
+    /// Get the RPC urls
+    pub fn get_rpc_urls(&self) -> Vec<Url> {
+        self.rpc_urls.clone()
+    }
+
+    /// Get how requests are spread over the RPC urls
+    pub fn get_rpc_consensus_type(&self) -> RpcConsensusType {
+        self.rpc_consensus_type
+    }
+
+    /// Get the metrics recorded for RPC requests
+    pub fn get_rpc_metrics(&self) -> Option<JsonRpcClientMetrics> {
+        self.rpc_metrics.clone()
+    }
+
+    /// Set the metrics recorded for RPC requests
+    pub fn set_rpc_metrics(&mut self, rpc_metrics: JsonRpcClientMetrics) {
+        self.rpc_metrics = Some(rpc_metrics);
     }
 
     /// Get the chain ID
@@ -113,27 +156,37 @@ impl ConnectionConf {
         self.contract_address_bytes
     }
 
+    /// Get the transaction overrides
+    pub fn get_transaction_overrides(&self) -> &TransactionOverrides {
+        &self.transaction_overrides
+    }
+
     /// Create a new connection configuration
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         grpc_urls: Vec<Url>,
-        rpc_url: String,
+        rpc_urls: Vec<Url>,
+        rpc_consensus_type: RpcConsensusType,
         chain_id: String,
         bech32_prefix: String,
         canonical_asset: String,
         minimum_gas_price: RawCosmosAmount,
         contract_address_bytes: usize,
         operation_batch: OperationBatchConfig,
+        transaction_overrides: TransactionOverrides,
     ) -> Self {
         Self {
             grpc_urls,
-            rpc_url,
+            rpc_urls,
+            rpc_consensus_type,
             chain_id,
             bech32_prefix,
             canonical_asset,
             gas_price: minimum_gas_price,
             contract_address_bytes,
             operation_batch,
+            transaction_overrides,
+            rpc_metrics: None,
         }
     }
 }
@@ -3,6 +3,7 @@ use std::num::NonZeroU64;
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use hyperlane_core::ChainResult;
 use once_cell::sync::Lazy;
+use tendermint::abci::Event;
 
 use crate::grpc::{WasmGrpcProvider, WasmProvider};
 
@@ -31,6 +32,29 @@ pub(crate) async fn get_block_height_for_lag(
     Ok(block_height)
 }
 
+/// Returns the value of the first attribute with the given key, in the first
+/// event of the given kind that has it. Depending on the chain's version, both
+/// keys and values may be base64 encoded.
+pub(crate) fn find_event_attribute(events: &[Event], kind: &str, key: &str) -> Option<String> {
+    let key_base64 = BASE64.encode(key);
+    events
+        .iter()
+        .filter(|event| event.kind == kind)
+        .flat_map(|event| event.attributes.iter())
+        .find_map(|attr| {
+            if attr.key == key {
+                Some(attr.value.clone())
+            } else if attr.key == key_base64 {
+                BASE64
+                    .decode(&attr.value)
+                    .ok()
+                    .and_then(|value| String::from_utf8(value).ok())
+            } else {
+                None
+            }
+        })
+}
+
 #[cfg(test)]
 /// Helper function to create a Vec<EventAttribute> from a JSON string -
 /// crate::payloads::general::EventAttribute has a Deserialize impl while
@@ -1,10 +1,18 @@
-use async_trait::async_trait;
+use std::ops::RangeInclusive;
 
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use cosmrs::proto::cosmos::base::abci::v1beta1::TxResponse;
+use futures::future;
 use hyperlane_core::{
-    Announcement, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
-    HyperlaneProvider, SignedType, TxOutcome, ValidatorAnnounce, H160, H256, U256,
+    Announcement, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneProvider, Indexed, Indexer, LogMeta,
+    SequenceAwareIndexer, SignedType, TxOutcome, ValidatorAnnounce, ValidatorAnnouncement, H160,
+    H256, U256,
 };
+use once_cell::sync::Lazy;
+use tendermint::abci::EventAttribute;
+use tracing::{instrument, warn};
 
 use crate::{
     grpc::WasmProvider,
@@ -12,9 +20,11 @@ use crate::{
         self, AnnouncementRequest, AnnouncementRequestInner, GetAnnounceStorageLocationsRequest,
         GetAnnounceStorageLocationsRequestInner,
     },
+    rpc::{CosmosWasmIndexer, ParsedEvent, WasmIndexer},
     signers::Signer,
     types::tx_response_to_outcome,
-    ConnectionConf, CosmosProvider,
+    utils::{CONTRACT_ADDRESS_ATTRIBUTE_KEY, CONTRACT_ADDRESS_ATTRIBUTE_KEY_BASE64},
+    ConnectionConf, CosmosProvider, HyperlaneCosmosError,
 };
 
 /// A reference to a ValidatorAnnounce contract on some Cosmos chain
@@ -104,7 +114,6 @@ impl ValidatorAnnounce for CosmosValidatorAnnounce {
         let response: TxResponse = self
             .provider
             .grpc()
-            // TODO: consider transaction overrides for Cosmos.
             .wasm_send(announce_request, None)
             .await?;
 
@@ -117,3 +126,235 @@ impl ValidatorAnnounce for CosmosValidatorAnnounce {
         Some(0u64.into())
     }
 }
+
+const VALIDATOR_ATTRIBUTE_KEY: &str = "validator";
+pub(crate) static VALIDATOR_ATTRIBUTE_KEY_BASE64: Lazy<String> =
+    Lazy::new(|| BASE64.encode(VALIDATOR_ATTRIBUTE_KEY));
+
+const STORAGE_LOCATION_ATTRIBUTE_KEY: &str = "storage-location";
+pub(crate) static STORAGE_LOCATION_ATTRIBUTE_KEY_BASE64: Lazy<String> =
+    Lazy::new(|| BASE64.encode(STORAGE_LOCATION_ATTRIBUTE_KEY));
+
+#[derive(Debug, Clone)]
+/// A reference to a ValidatorAnnounceIndexer contract on some Cosmos chain
+pub struct CosmosValidatorAnnounceIndexer {
+    /// Cosmwasm indexer instance
+    indexer: Box<CosmosWasmIndexer>,
+}
+
+impl CosmosValidatorAnnounceIndexer {
+    /// The validator announcement event type from the CW contract.
+    const VALIDATOR_ANNOUNCEMENT_EVENT_TYPE: &str = "hpl_va::announce";
+
+    /// create new Cosmos ValidatorAnnounceIndexer agent
+    pub fn new(
+        conf: ConnectionConf,
+        locator: ContractLocator,
+        reorg_period: u32,
+    ) -> ChainResult<Self> {
+        let indexer = CosmosWasmIndexer::new(
+            conf,
+            locator,
+            Self::VALIDATOR_ANNOUNCEMENT_EVENT_TYPE.into(),
+            reorg_period,
+        )?;
+
+        Ok(Self {
+            indexer: Box::new(indexer),
+        })
+    }
+
+    #[instrument(err)]
+    fn validator_announcement_parser(
+        attrs: &Vec<EventAttribute>,
+    ) -> ChainResult<ParsedEvent<ValidatorAnnouncement>> {
+        let mut contract_address: Option<String> = None;
+        let mut announcement = IncompleteValidatorAnnouncement::default();
+
+        for attr in attrs {
+            let key = attr.key.as_str();
+            let value = attr.value.as_str();
+
+            match key {
+                CONTRACT_ADDRESS_ATTRIBUTE_KEY => {
+                    contract_address = Some(value.to_string());
+                }
+                v if *CONTRACT_ADDRESS_ATTRIBUTE_KEY_BASE64 == v => {
+                    contract_address = Some(String::from_utf8(
+                        BASE64
+                            .decode(value)
+                            .map_err(Into::<HyperlaneCosmosError>::into)?,
+                    )?);
+                }
+
+                VALIDATOR_ATTRIBUTE_KEY => {
+                    announcement.validator = Some(parse_validator(value)?);
+                }
+                v if *VALIDATOR_ATTRIBUTE_KEY_BASE64 == v => {
+                    announcement.validator = Some(parse_validator(&String::from_utf8(
+                        BASE64
+                            .decode(value)
+                            .map_err(Into::<HyperlaneCosmosError>::into)?,
+                    )?)?);
+                }
+
+                STORAGE_LOCATION_ATTRIBUTE_KEY => {
+                    announcement.storage_location = Some(value.to_string());
+                }
+                v if *STORAGE_LOCATION_ATTRIBUTE_KEY_BASE64 == v => {
+                    announcement.storage_location = Some(String::from_utf8(
+                        BASE64
+                            .decode(value)
+                            .map_err(Into::<HyperlaneCosmosError>::into)?,
+                    )?);
+                }
+
+                _ => {}
+            }
+        }
+
+        let contract_address = contract_address
+            .ok_or_else(|| ChainCommunicationError::from_other_str("missing contract_address"))?;
+
+        Ok(ParsedEvent::new(contract_address, announcement.try_into()?))
+    }
+}
+
+fn parse_validator(value: &str) -> ChainResult<H160> {
+    let bytes = hex::decode(value.trim_start_matches("0x"))?;
+    if bytes.len() != H160::len_bytes() {
+        return Err(ChainCommunicationError::from_other_str(
+            "invalid validator address length",
+        ));
+    }
+    Ok(H160::from_slice(&bytes))
+}
+
+#[async_trait]
+impl Indexer<ValidatorAnnouncement> for CosmosValidatorAnnounceIndexer {
+    /// Fetch list of logs between `range` of blocks
+    async fn fetch_logs(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<ValidatorAnnouncement>, LogMeta)>> {
+        let logs_futures: Vec<_> = range
+            .map(|block_number| {
+                let self_clone = self.clone();
+                tokio::spawn(async move {
+                    let logs = self_clone
+                        .indexer
+                        .get_logs_in_block(
+                            block_number,
+                            Self::validator_announcement_parser,
+                            "ValidatorAnnouncementCursor",
+                        )
+                        .await;
+                    (logs, block_number)
+                })
+            })
+            .collect();
+
+        let result = future::join_all(logs_futures)
+            .await
+            .into_iter()
+            .flatten()
+            .filter_map(|(logs_res, block_number)| match logs_res {
+                Ok(logs) => Some(logs),
+                Err(err) => {
+                    warn!(?err, ?block_number, "Failed to fetch logs for block");
+                    None
+                }
+            })
+            .flatten()
+            .map(|(log, meta)| (log.into(), meta))
+            .collect();
+
+        Ok(result)
+    }
+
+    /// Get the chain's latest block number that has reached finality
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.indexer.get_finalized_block_number().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<ValidatorAnnouncement> for CosmosValidatorAnnounceIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        // Announcements are not sequenced
+        let tip = self.get_finalized_block_number().await?;
+        Ok((None, tip))
+    }
+}
+
+#[derive(Default)]
+struct IncompleteValidatorAnnouncement {
+    validator: Option<H160>,
+    storage_location: Option<String>,
+}
+
+impl TryInto<ValidatorAnnouncement> for IncompleteValidatorAnnouncement {
+    type Error = ChainCommunicationError;
+
+    fn try_into(self) -> Result<ValidatorAnnouncement, Self::Error> {
+        let validator = self
+            .validator
+            .ok_or_else(|| ChainCommunicationError::from_other_str("missing validator"))?;
+        let storage_location = self
+            .storage_location
+            .ok_or_else(|| ChainCommunicationError::from_other_str("missing storage-location"))?;
+
+        Ok(ValidatorAnnouncement::new(validator, storage_location))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{rpc::ParsedEvent, utils::event_attributes_from_str};
+
+    use super::*;
+
+    #[test]
+    fn test_validator_announcement_parser() {
+        let expected = ParsedEvent::new(
+            "neutron17w4q6efzym3p4c6umyp4cjf2ustjtmwfqdhd7rt2fpcpk9fmjzsq0kj0f9".into(),
+            ValidatorAnnouncement::new(
+                H160::from_str("42b6de2edbaa62c2ea2309ad85d20b3e37d38acf").unwrap(),
+                "s3://hyperlane-validator-signatures-neutron/us-east-1".into(),
+            ),
+        );
+
+        let assert_parsed_event = |attrs: &Vec<EventAttribute>| {
+            let parsed_event =
+                CosmosValidatorAnnounceIndexer::validator_announcement_parser(attrs).unwrap();
+
+            assert_eq!(parsed_event, expected);
+        };
+
+        // Non-base64 version
+        let non_base64_attrs = event_attributes_from_str(
+            r#"[{"key":"_contract_address","value":"neutron17w4q6efzym3p4c6umyp4cjf2ustjtmwfqdhd7rt2fpcpk9fmjzsq0kj0f9","index":true},{"key":"sender","value":"neutron1ppqqtm0jxjyn6pnffzmq9j3h8lj0xpn9cs8vlx","index":true},{"key":"validator","value":"42b6de2edbaa62c2ea2309ad85d20b3e37d38acf","index":true},{"key":"storage-location","value":"s3://hyperlane-validator-signatures-neutron/us-east-1","index":true}]"#,
+        );
+        assert_parsed_event(&non_base64_attrs);
+
+        // Base64 version
+        let base64_attrs = event_attributes_from_str(&format!(
+            r#"[{{"key":"{}","value":"{}","index":true}},{{"key":"{}","value":"{}","index":true}},{{"key":"{}","value":"{}","index":true}}]"#,
+            *CONTRACT_ADDRESS_ATTRIBUTE_KEY_BASE64,
+            BASE64.encode("neutron17w4q6efzym3p4c6umyp4cjf2ustjtmwfqdhd7rt2fpcpk9fmjzsq0kj0f9"),
+            *VALIDATOR_ATTRIBUTE_KEY_BASE64,
+            BASE64.encode("42b6de2edbaa62c2ea2309ad85d20b3e37d38acf"),
+            *STORAGE_LOCATION_ATTRIBUTE_KEY_BASE64,
+            BASE64.encode("s3://hyperlane-validator-signatures-neutron/us-east-1"),
+        ));
+        assert_parsed_event(&base64_attrs);
+    }
+
+    #[test]
+    fn test_parse_validator_rejects_wrong_length() {
+        assert!(parse_validator("0x42b6de2edbaa62c2ea2309ad85d20b3e37d38acf").is_ok());
+        assert!(parse_validator("42b6de2e").is_err());
+    }
+}
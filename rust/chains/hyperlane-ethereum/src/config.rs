@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use hyperlane_core::{config::OperationBatchConfig, U256};
 use url::Url;
 
@@ -50,4 +52,13 @@ pub struct TransactionOverrides {
     pub max_fee_per_gas: Option<U256>,
     /// Max priority fee per gas to use for EIP-1559 transactions.
     pub max_priority_fee_per_gas: Option<U256>,
+    /// How long to wait for a transaction to be included before replacing it
+    /// with one that has the same nonce and higher fees. Defaults to 30 seconds.
+    pub gas_escalation_interval: Option<Duration>,
+    /// How much to raise fees by, in percent, each time a transaction is replaced.
+    /// Defaults to 20. Nodes reject replacements that raise fees by less than 10%.
+    pub gas_escalation_percent: Option<u64>,
+    /// The highest gas price, or max fee per gas for EIP-1559 transactions,
+    /// that replacement transactions may pay, in wei.
+    pub gas_price_cap: Option<U256>,
 }
@@ -373,7 +373,12 @@ where
         let contract_call = self
             .process_contract_call(message, metadata, tx_gas_limit)
             .await?;
-        let receipt = report_tx(contract_call).await?;
+        let receipt = report_tx(
+            contract_call,
+            self.provider.clone(),
+            &self.conn.transaction_overrides,
+        )
+        .await?;
         Ok(receipt.into())
     }
 
@@ -404,7 +409,12 @@ where
         let batch_call = multicall::batch::<_, ()>(&mut multicall, contract_calls);
         let call = self.add_gas_overrides(batch_call, None).await?;
 
-        let receipt = report_tx(call).await?;
+        let receipt = report_tx(
+            call,
+            self.provider.clone(),
+            &self.conn.transaction_overrides,
+        )
+        .await?;
         Ok(receipt.into())
     }
 
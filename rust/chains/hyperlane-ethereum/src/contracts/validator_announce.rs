@@ -1,14 +1,16 @@
 #![allow(clippy::enum_variant_names)]
 #![allow(missing_docs)]
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, ops::RangeInclusive, sync::Arc};
 
 use async_trait::async_trait;
 use ethers::providers::Middleware;
 use ethers_contract::builders::ContractCall;
 use hyperlane_core::{
-    Announcement, ChainResult, ContractLocator, HyperlaneAbi, HyperlaneChain, HyperlaneContract,
-    HyperlaneDomain, HyperlaneProvider, SignedType, TxOutcome, ValidatorAnnounce, H160, H256, U256,
+    Announcement, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneAbi,
+    HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneProvider, Indexed, Indexer,
+    LogMeta, SequenceAwareIndexer, SignedType, TxOutcome, ValidatorAnnounce, ValidatorAnnouncement,
+    H160, H256, U256,
 };
 use tracing::{instrument, log::trace};
 
@@ -49,6 +51,111 @@ impl BuildableWithProvider for ValidatorAnnounceBuilder {
     }
 }
 
+pub struct ValidatorAnnounceIndexerBuilder {
+    pub reorg_period: u32,
+}
+
+#[async_trait]
+impl BuildableWithProvider for ValidatorAnnounceIndexerBuilder {
+    type Output = Box<dyn SequenceAwareIndexer<ValidatorAnnouncement>>;
+
+    async fn build_with_provider<M: Middleware + 'static>(
+        &self,
+        provider: M,
+        _conn: &ConnectionConf,
+        locator: &ContractLocator,
+    ) -> Self::Output {
+        Box::new(EthereumValidatorAnnounceIndexer::new(
+            Arc::new(provider),
+            locator,
+            self.reorg_period,
+        ))
+    }
+}
+
+#[derive(Debug)]
+/// Struct that retrieves event data for an Ethereum ValidatorAnnounce
+pub struct EthereumValidatorAnnounceIndexer<M>
+where
+    M: Middleware,
+{
+    contract: Arc<EthereumValidatorAnnounceInternal<M>>,
+    provider: Arc<M>,
+    reorg_period: u32,
+}
+
+impl<M> EthereumValidatorAnnounceIndexer<M>
+where
+    M: Middleware + 'static,
+{
+    /// Create new EthereumValidatorAnnounceIndexer
+    pub fn new(provider: Arc<M>, locator: &ContractLocator, reorg_period: u32) -> Self {
+        Self {
+            contract: Arc::new(EthereumValidatorAnnounceInternal::new(
+                locator.address,
+                provider.clone(),
+            )),
+            provider,
+            reorg_period,
+        }
+    }
+}
+
+#[async_trait]
+impl<M> Indexer<ValidatorAnnouncement> for EthereumValidatorAnnounceIndexer<M>
+where
+    M: Middleware + 'static,
+{
+    /// Note: This call may return duplicates depending on the provider used
+    #[instrument(err, skip(self))]
+    async fn fetch_logs(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<ValidatorAnnouncement>, LogMeta)>> {
+        let events = self
+            .contract
+            .validator_announcement_filter()
+            .from_block(*range.start())
+            .to_block(*range.end())
+            .query_with_meta()
+            .await?;
+
+        let logs = events
+            .into_iter()
+            .map(|(log, log_meta)| {
+                (
+                    ValidatorAnnouncement::new(log.validator.into(), log.storage_location).into(),
+                    log_meta.into(),
+                )
+            })
+            .collect();
+        Ok(logs)
+    }
+
+    #[instrument(level = "debug", err, skip(self))]
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        Ok(self
+            .provider
+            .get_block_number()
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .as_u32()
+            .saturating_sub(self.reorg_period))
+    }
+}
+
+#[async_trait]
+impl<M> SequenceAwareIndexer<ValidatorAnnouncement> for EthereumValidatorAnnounceIndexer<M>
+where
+    M: Middleware + 'static,
+{
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        // Announcements are not sequenced
+        let tip = self.get_finalized_block_number().await?;
+        Ok((None, tip))
+    }
+}
+
 /// A reference to a ValidatorAnnounce contract on some Ethereum chain
 #[derive(Debug)]
 pub struct EthereumValidatorAnnounce<M>
@@ -164,7 +271,12 @@ where
     #[instrument(err, ret, skip(self))]
     async fn announce(&self, announcement: SignedType<Announcement>) -> ChainResult<TxOutcome> {
         let contract_call = self.announce_contract_call(announcement).await?;
-        let receipt = report_tx(contract_call).await?;
+        let receipt = report_tx(
+            contract_call,
+            self.provider.clone(),
+            &self.conn.transaction_overrides,
+        )
+        .await?;
         Ok(receipt.into())
     }
 }
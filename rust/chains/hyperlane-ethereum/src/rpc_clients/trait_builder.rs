@@ -18,7 +18,9 @@ use ethers_prometheus::json_rpc_client::{
     JsonRpcBlockGetter, JsonRpcClientMetrics, JsonRpcClientMetricsBuilder, NodeInfo,
     PrometheusJsonRpcClient, PrometheusJsonRpcClientConfig,
 };
-use ethers_prometheus::middleware::{MiddlewareMetrics, PrometheusMiddlewareConf};
+use ethers_prometheus::middleware::{
+    MiddlewareMetrics, PrometheusMiddleware, PrometheusMiddlewareConf,
+};
 use hyperlane_core::{
     ChainCommunicationError, ChainResult, ContractLocator, HyperlaneDomain, KnownHyperlaneDomain,
 };
@@ -93,7 +95,8 @@ pub trait BuildableWithProvider {
                     builder = builder.add_provider(weighted_provider);
                 }
                 let quorum_provider = builder.build();
-                self.build(quorum_provider, conn, locator, signer).await?
+                self.build(quorum_provider, conn, locator, signer, middleware_metrics)
+                    .await?
             }
             RpcConnectionConf::HttpFallback { urls } => {
                 let mut builder = FallbackProvider::builder();
@@ -116,8 +119,14 @@ pub trait BuildableWithProvider {
                     _,
                     JsonRpcBlockGetter<PrometheusJsonRpcClient<Http>>,
                 >::new(fallback_provider);
-                self.build(ethereum_fallback_provider, conn, locator, signer)
-                    .await?
+                self.build(
+                    ethereum_fallback_provider,
+                    conn,
+                    locator,
+                    signer,
+                    middleware_metrics,
+                )
+                .await?
             }
             RpcConnectionConf::Http { url } => {
                 let http_client = Client::builder()
@@ -132,14 +141,21 @@ pub trait BuildableWithProvider {
                     &middleware_metrics,
                 );
                 let retrying_http_provider = RetryingProvider::new(metrics_provider, None, None);
-                self.build(retrying_http_provider, conn, locator, signer)
-                    .await?
+                self.build(
+                    retrying_http_provider,
+                    conn,
+                    locator,
+                    signer,
+                    middleware_metrics,
+                )
+                .await?
             }
             RpcConnectionConf::Ws { url } => {
                 let ws = Ws::connect(url)
                     .await
                     .map_err(EthereumProviderConnectionError::from)?;
-                self.build(ws, conn, locator, signer).await?
+                self.build(ws, conn, locator, signer, middleware_metrics)
+                    .await?
             }
         })
     }
@@ -188,34 +204,47 @@ pub trait BuildableWithProvider {
         conn: &ConnectionConf,
         locator: &ContractLocator,
         signer: Option<Signers>,
+        middleware_metrics: Option<(MiddlewareMetrics, PrometheusMiddlewareConf)>,
     ) -> ChainResult<Self::Output>
     where
         P: JsonRpcClient + 'static,
     {
         let provider = wrap_with_gas_oracle(Provider::new(client), locator.domain)?;
-        self.build_with_signer(provider, conn, locator, signer)
+        self.build_with_signer(provider, conn, locator, signer, middleware_metrics)
             .await
     }
 
     /// Wrap the provider creation with a signing provider if signers were
-    /// provided, and then create the associated trait.
+    /// provided, and then create the associated trait. Signing providers are
+    /// also instrumented with middleware metrics, since they are the only
+    /// ones that send transactions.
     async fn build_with_signer<M>(
         &self,
         provider: M,
         conn: &ConnectionConf,
         locator: &ContractLocator,
         signer: Option<Signers>,
+        middleware_metrics: Option<(MiddlewareMetrics, PrometheusMiddlewareConf)>,
     ) -> ChainResult<Self::Output>
     where
         M: Middleware + 'static,
     {
-        Ok(if let Some(signer) = signer {
-            let signing_provider = wrap_with_signer(provider, signer)
-                .await
-                .map_err(ChainCommunicationError::from_other)?;
-            self.build_with_provider(signing_provider, conn, locator)
-        } else {
-            self.build_with_provider(provider, conn, locator)
+        Ok(match (signer, middleware_metrics) {
+            (Some(signer), Some((metrics, metrics_conf))) => {
+                let signing_provider = wrap_with_signer(provider, signer)
+                    .await
+                    .map_err(ChainCommunicationError::from_other)?;
+                let metrics_provider =
+                    PrometheusMiddleware::new(signing_provider, metrics, metrics_conf);
+                self.build_with_provider(metrics_provider, conn, locator)
+            }
+            (Some(signer), None) => {
+                let signing_provider = wrap_with_signer(provider, signer)
+                    .await
+                    .map_err(ChainCommunicationError::from_other)?;
+                self.build_with_provider(signing_provider, conn, locator)
+            }
+            (None, _) => self.build_with_provider(provider, conn, locator),
         }
         .await)
     }
@@ -5,8 +5,8 @@ use std::time::Duration;
 use ethers::{
     abi::Detokenize,
     prelude::{NameOrAddress, TransactionReceipt},
-    providers::{JsonRpcClient, PendingTransaction, ProviderError},
-    types::Eip1559TransactionRequest,
+    providers::ProviderError,
+    types::{transaction::eip2718::TypedTransaction, Eip1559TransactionRequest, H256},
 };
 use ethers_contract::builders::ContractCall;
 use ethers_core::{
@@ -16,8 +16,9 @@ use ethers_core::{
         EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE,
     },
 };
-use hyperlane_core::{utils::bytes_to_hex, ChainCommunicationError, ChainResult, H256, U256};
-use tracing::{error, info};
+use hyperlane_core::{utils::bytes_to_hex, ChainCommunicationError, ChainResult, U256};
+use tokio::time::{sleep, Instant};
+use tracing::{error, info, warn};
 
 use crate::{Middleware, TransactionOverrides};
 
@@ -25,9 +26,24 @@ use crate::{Middleware, TransactionOverrides};
 pub const GAS_ESTIMATE_BUFFER: u32 = 75_000;
 
 const PENDING_TRANSACTION_POLLING_INTERVAL: Duration = Duration::from_secs(2);
+/// How long to wait for a transaction, including any replacements, to be included
+const PENDING_TRANSACTION_TIMEOUT: Duration = Duration::from_secs(150);
+/// How long to wait for a transaction to be included before replacing it, if
+/// not overridden
+const DEFAULT_GAS_ESCALATION_INTERVAL: Duration = Duration::from_secs(30);
+/// How much to raise fees by, in percent, when replacing a transaction, if not
+/// overridden
+const DEFAULT_GAS_ESCALATION_PERCENT: u64 = 20;
 
-/// Dispatches a transaction, logs the tx id, and returns the result
-pub(crate) async fn report_tx<M, D>(tx: ContractCall<M, D>) -> ChainResult<TransactionReceipt>
+/// Dispatches a transaction, logs the tx id, and returns the result.
+/// If the transaction isn't included in time, it is replaced by one with the
+/// same nonce and higher fees, following the escalation schedule in
+/// `transaction_overrides`.
+pub(crate) async fn report_tx<M, D>(
+    mut tx: ContractCall<M, D>,
+    provider: Arc<M>,
+    transaction_overrides: &TransactionOverrides,
+) -> ChainResult<TransactionReceipt>
 where
     M: Middleware + 'static,
     D: Detokenize,
@@ -44,40 +60,121 @@ where
         .cloned()
         .unwrap_or_else(|| NameOrAddress::Address(Default::default()));
 
-    info!(?to, %data, "Dispatching transaction");
-    // We can set the gas higher here!
-    let dispatch_fut = tx.send();
-    let dispatched = dispatch_fut
-        .await?
-        .interval(PENDING_TRANSACTION_POLLING_INTERVAL);
-    track_pending_tx(dispatched).await
-}
+    // Fix the nonce and fees up front so that replacements can reuse the nonce
+    // and raise the fees.
+    provider
+        .fill_transaction(&mut tx.tx, None)
+        .await
+        .map_err(ChainCommunicationError::from_other)?;
+    let nonce = tx.tx.nonce().cloned();
 
-pub(crate) async fn track_pending_tx<P: JsonRpcClient>(
-    pending_tx: PendingTransaction<'_, P>,
-) -> ChainResult<TransactionReceipt> {
-    let tx_hash: H256 = (*pending_tx).into();
+    info!(?to, %data, ?nonce, "Dispatching transaction");
+    let escalation_interval = transaction_overrides
+        .gas_escalation_interval
+        .unwrap_or(DEFAULT_GAS_ESCALATION_INTERVAL);
+    let escalation_percent = transaction_overrides
+        .gas_escalation_percent
+        .unwrap_or(DEFAULT_GAS_ESCALATION_PERCENT);
+    let gas_price_cap = transaction_overrides.gas_price_cap.map(Into::into);
 
-    info!(?tx_hash, "Dispatched tx");
+    let deadline = Instant::now() + PENDING_TRANSACTION_TIMEOUT;
+    let mut tx_hashes: Vec<H256> = vec![];
+    let mut resend = true;
+    loop {
+        if resend {
+            match tx.send().await {
+                Ok(pending_tx) => {
+                    let tx_hash = *pending_tx;
+                    info!(?tx_hash, ?nonce, "Dispatched tx");
+                    tx_hashes.push(tx_hash);
+                }
+                Err(err) if tx_hashes.is_empty() => return Err(err.into()),
+                // A replacement can fail if an earlier transaction was just
+                // included, so keep waiting on the ones already sent.
+                Err(err) => {
+                    warn!(?nonce, error = ?err, "failed to replace pending transaction");
+                }
+            }
+        }
 
-    match tokio::time::timeout(Duration::from_secs(150), pending_tx).await {
-        // all good
-        Ok(Ok(Some(receipt))) => {
-            info!(?tx_hash, "confirmed transaction");
+        let wait_until = deadline.min(Instant::now() + escalation_interval);
+        if let Some(receipt) = wait_for_receipt(&provider, &tx_hashes, wait_until).await? {
+            info!(tx_hash = ?receipt.transaction_hash, "confirmed transaction");
+            return Ok(receipt);
+        }
+        if Instant::now() >= deadline {
+            error!(?tx_hashes, "waiting for receipt timed out");
+            return Err(ChainCommunicationError::TransactionTimeout());
+        }
 
-            Ok(receipt)
+        resend = escalate_fees(&mut tx.tx, escalation_percent, gas_price_cap);
+        if resend {
+            warn!(
+                ?nonce,
+                gas_price = ?tx.tx.gas_price(),
+                "transaction not included in time, replacing it with higher fees"
+            );
         }
-        // ethers-rs will return None if it can no longer poll for the tx in the mempool
-        Ok(Ok(None)) => Err(ChainCommunicationError::TransactionDropped(tx_hash)),
-        // Received error, pass it through
-        Ok(Err(x)) => {
-            error!(?tx_hash, error = ?x, "encountered error when waiting for receipt");
-            Err(x.into())
+    }
+}
+
+/// Polls for the receipt of any of `tx_hashes` until `until`. Only one of them
+/// can be included, since they all share a nonce.
+async fn wait_for_receipt<M: Middleware>(
+    provider: &M,
+    tx_hashes: &[H256],
+    until: Instant,
+) -> ChainResult<Option<TransactionReceipt>> {
+    loop {
+        for tx_hash in tx_hashes {
+            let receipt = provider
+                .get_transaction_receipt(*tx_hash)
+                .await
+                .map_err(ChainCommunicationError::from_other)?;
+            if receipt.is_some() {
+                return Ok(receipt);
+            }
         }
-        // Timed out
-        Err(x) => {
-            error!(?tx_hash, error = ?x, "waiting for receipt timed out");
-            Err(ChainCommunicationError::TransactionTimeout())
+        if Instant::now() >= until {
+            return Ok(None);
+        }
+        sleep(PENDING_TRANSACTION_POLLING_INTERVAL).await;
+    }
+}
+
+/// Raises the fees of `tx` by `percent`, without going over `cap`. Returns
+/// false if the fees can't be raised any further.
+fn escalate_fees(tx: &mut TypedTransaction, percent: u64, cap: Option<EthersU256>) -> bool {
+    let bump = |fee: EthersU256| {
+        let bumped =
+            (fee.saturating_mul((100 + percent).into()) / 100).max(fee.saturating_add(1.into()));
+        cap.map_or(bumped, |cap| bumped.min(cap))
+    };
+    match tx {
+        TypedTransaction::Eip1559(inner) => {
+            let (Some(max_fee), Some(max_priority_fee)) =
+                (inner.max_fee_per_gas, inner.max_priority_fee_per_gas)
+            else {
+                return false;
+            };
+            let bumped_max_fee = bump(max_fee);
+            if bumped_max_fee <= max_fee {
+                return false;
+            }
+            inner.max_fee_per_gas = Some(bumped_max_fee);
+            inner.max_priority_fee_per_gas = Some(bump(max_priority_fee).min(bumped_max_fee));
+            true
+        }
+        _ => {
+            let Some(gas_price) = tx.gas_price() else {
+                return false;
+            };
+            let bumped_gas_price = bump(gas_price);
+            if bumped_gas_price <= gas_price {
+                return false;
+            }
+            tx.set_gas_price(bumped_gas_price);
+            true
         }
     }
 }
@@ -148,7 +245,7 @@ where
     request = request.max_fee_per_gas(max_fee);
     request = request.max_priority_fee_per_gas(max_priority_fee);
     let mut eip_1559_tx = tx;
-    eip_1559_tx.tx = ethers::types::transaction::eip2718::TypedTransaction::Eip1559(request);
+    eip_1559_tx.tx = TypedTransaction::Eip1559(request);
     Ok(eip_1559_tx.gas(gas_limit))
 }
 
@@ -213,3 +310,36 @@ where
         Ok(call)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use ethers::types::TransactionRequest;
+
+    use super::*;
+
+    #[test]
+    fn test_escalate_legacy_fees() {
+        let mut tx: TypedTransaction = TransactionRequest::new().gas_price(100).into();
+        assert!(escalate_fees(&mut tx, 20, None));
+        assert_eq!(tx.gas_price(), Some(120.into()));
+
+        // Stops at the cap
+        assert!(escalate_fees(&mut tx, 20, Some(130.into())));
+        assert_eq!(tx.gas_price(), Some(130.into()));
+        assert!(!escalate_fees(&mut tx, 20, Some(130.into())));
+    }
+
+    #[test]
+    fn test_escalate_eip1559_fees() {
+        let mut tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .max_fee_per_gas(100)
+            .max_priority_fee_per_gas(10)
+            .into();
+        assert!(escalate_fees(&mut tx, 20, Some(110.into())));
+        let TypedTransaction::Eip1559(inner) = tx else {
+            panic!("expected an EIP-1559 transaction");
+        };
+        assert_eq!(inner.max_fee_per_gas, Some(110.into()));
+        assert_eq!(inner.max_priority_fee_per_gas, Some(12.into()));
+    }
+}
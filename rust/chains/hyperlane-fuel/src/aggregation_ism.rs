@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use fuels::prelude::Bech32ContractId;
+use tracing::instrument;
+
+use hyperlane_core::{
+    AggregationIsm, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, RawHyperlaneMessage,
+    H256,
+};
+
+use crate::{
+    contracts::aggregation_ism::AggregationIsm as FuelAggregationIsmInner, conversions::*,
+    utils::make_wallet, ConnectionConf, FuelProvider,
+};
+
+/// A reference to a AggregationIsm contract on some Fuel chain
+#[derive(Debug)]
+pub struct FuelAggregationIsm {
+    contract: FuelAggregationIsmInner,
+    domain: HyperlaneDomain,
+    provider: FuelProvider,
+}
+
+impl FuelAggregationIsm {
+    /// Create a reference to a AggregationIsm at a specific Fuel address
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> ChainResult<Self> {
+        let provider = FuelProvider::new(locator.domain.clone(), conf)?;
+        let wallet = make_wallet(provider.provider(), None);
+        Ok(Self {
+            contract: FuelAggregationIsmInner::new(
+                Bech32ContractId::from_h256(&locator.address),
+                wallet,
+            ),
+            domain: locator.domain.clone(),
+            provider,
+        })
+    }
+}
+
+impl HyperlaneContract for FuelAggregationIsm {
+    fn address(&self) -> H256 {
+        self.contract.contract_id().into_h256()
+    }
+}
+
+impl HyperlaneChain for FuelAggregationIsm {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.provider.clone())
+    }
+}
+
+#[async_trait]
+impl AggregationIsm for FuelAggregationIsm {
+    /// Returns the `m` ISMs and `n` threshold needed to n-of-m verify the message
+    #[instrument(err, ret, skip(self))]
+    async fn modules_and_threshold(
+        &self,
+        message: &HyperlaneMessage,
+    ) -> ChainResult<(Vec<H256>, u8)> {
+        let (modules, threshold) = self
+            .contract
+            .methods()
+            .modules_and_threshold(RawHyperlaneMessage::from(message))
+            .simulate()
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value;
+        let modules = modules
+            .into_iter()
+            .map(|module| module.into_h256())
+            .collect();
+        Ok((modules, threshold))
+    }
+}
@@ -0,0 +1,19 @@
+use hyperlane_core::ChainCommunicationError;
+
+/// Errors from the crates specific to the hyperlane-fuel
+/// implementation.
+/// This error can then be converted into the broader error type
+/// in hyperlane-core using the `From` trait impl
+#[derive(Debug, thiserror::Error)]
+pub enum HyperlaneFuelError {
+    /// The operation needs contract bindings or events that the Fuel
+    /// integration does not have yet
+    #[error("{0} is not supported on Fuel yet")]
+    Unsupported(&'static str),
+}
+
+impl From<HyperlaneFuelError> for ChainCommunicationError {
+    fn from(value: HyperlaneFuelError) -> Self {
+        ChainCommunicationError::from_other(value)
+    }
+}
@@ -0,0 +1,97 @@
+use std::ops::RangeInclusive;
+
+use fuels::{prelude::ContractId, tx::Receipt};
+use hyperlane_core::{
+    ChainCommunicationError, ChainResult, HyperlaneProviderError, LogMeta, H256, H512, U256,
+};
+
+use crate::{conversions::*, FuelProvider};
+
+/// The data of a `LogData` receipt emitted by a contract
+#[derive(Debug, Clone)]
+pub(crate) struct FuelLog {
+    /// The log id, which is the id of the logged type in the contract ABI,
+    /// or chosen by the contract for raw logs
+    pub log_id: u64,
+    /// The logged bytes
+    pub data: Vec<u8>,
+}
+
+/// Finds the logs a contract emitted by walking the receipts of every
+/// transaction in a range of blocks. Fuel nodes can't filter logs by
+/// contract, so this is what every Fuel indexer builds on.
+#[derive(Debug, Clone)]
+pub(crate) struct FuelLogIndexer {
+    provider: FuelProvider,
+    contract_id: ContractId,
+}
+
+impl FuelLogIndexer {
+    pub fn new(provider: FuelProvider, address: H256) -> Self {
+        Self {
+            provider,
+            contract_id: ContractId::from_h256(&address),
+        }
+    }
+
+    /// The logs the contract emitted in successful transactions within the
+    /// block range
+    pub async fn fetch_logs(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(FuelLog, LogMeta)>> {
+        let provider = self.provider.provider();
+        let mut logs = vec![];
+        for height in range {
+            let height = height as u64;
+            let block = provider
+                .block_by_height(height)
+                .await
+                .map_err(ChainCommunicationError::from_other)?
+                .ok_or(HyperlaneProviderError::CouldNotFindBlockByHeight(height))?;
+            for (transaction_index, tx_id) in block.transactions.iter().enumerate() {
+                let receipts = provider
+                    .get_receipts(tx_id)
+                    .await
+                    .map_err(ChainCommunicationError::from_other)?;
+                // Logs of reverted transactions are still part of their receipts
+                let reverted = receipts
+                    .iter()
+                    .any(|r| matches!(r, Receipt::Revert { .. } | Receipt::Panic { .. }));
+                if reverted {
+                    continue;
+                }
+                for (log_index, receipt) in receipts.into_iter().enumerate() {
+                    let Receipt::LogData { id, rb, data, .. } = receipt else {
+                        continue;
+                    };
+                    if id != self.contract_id {
+                        continue;
+                    }
+                    let meta = LogMeta {
+                        address: self.contract_id.into_h256(),
+                        block_number: height,
+                        block_hash: H256::from(*block.id),
+                        transaction_id: H512::from(H256::from(**tx_id)),
+                        transaction_index: transaction_index as u64,
+                        log_index: U256::from(log_index),
+                    };
+                    logs.push((FuelLog { log_id: rb, data }, meta));
+                }
+            }
+        }
+        Ok(logs)
+    }
+
+    /// Fuel blocks are final as soon as they are produced, so this is the
+    /// latest block height
+    pub async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.provider
+            .provider()
+            .latest_block_height()
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .try_into()
+            .map_err(ChainCommunicationError::from_other)
+    }
+}
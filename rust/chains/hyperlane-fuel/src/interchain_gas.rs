@@ -1,29 +1,51 @@
 use std::ops::RangeInclusive;
 
 use async_trait::async_trait;
+use tracing::{instrument, warn};
 
 use hyperlane_core::{
-    ChainResult, HyperlaneChain, HyperlaneContract, Indexed, Indexer, InterchainGasPaymaster,
+    ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, Indexed, Indexer,
+    InterchainGasPaymaster, SequenceAwareIndexer,
 };
 use hyperlane_core::{HyperlaneDomain, HyperlaneProvider, InterchainGasPayment, LogMeta, H256};
 
+use crate::{indexer::FuelLogIndexer, ConnectionConf, FuelProvider};
+
+/// The id of `GasPaymentEvent` in the IGP ABI's logged types
+const GAS_PAYMENT_LOG_ID: u64 = 0;
+
 /// A reference to an IGP contract on some Fuel chain
 #[derive(Debug)]
-pub struct FuelInterchainGasPaymaster {}
+pub struct FuelInterchainGasPaymaster {
+    domain: HyperlaneDomain,
+    address: H256,
+    provider: FuelProvider,
+}
+
+impl FuelInterchainGasPaymaster {
+    /// Create a reference to a IGP at a specific Fuel address
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> ChainResult<Self> {
+        Ok(Self {
+            domain: locator.domain.clone(),
+            address: locator.address,
+            provider: FuelProvider::new(locator.domain.clone(), conf)?,
+        })
+    }
+}
 
 impl HyperlaneContract for FuelInterchainGasPaymaster {
     fn address(&self) -> H256 {
-        todo!()
+        self.address
     }
 }
 
 impl HyperlaneChain for FuelInterchainGasPaymaster {
     fn domain(&self) -> &HyperlaneDomain {
-        todo!()
+        &self.domain
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        todo!()
+        Box::new(self.provider.clone())
     }
 }
 
@@ -31,18 +53,103 @@ impl InterchainGasPaymaster for FuelInterchainGasPaymaster {}
 
 /// Struct that retrieves event data for a Fuel IGP contract
 #[derive(Debug)]
-pub struct FuelInterchainGasPaymasterIndexer {}
+pub struct FuelInterchainGasPaymasterIndexer {
+    log_indexer: FuelLogIndexer,
+}
+
+impl FuelInterchainGasPaymasterIndexer {
+    /// Create a new fuel IGP indexer
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> ChainResult<Self> {
+        let provider = FuelProvider::new(locator.domain.clone(), conf)?;
+        Ok(Self {
+            log_indexer: FuelLogIndexer::new(provider, locator.address),
+        })
+    }
+
+    /// Decodes an ABI encoded `GasPaymentEvent`, in which every field takes
+    /// up whole 8 byte words:
+    /// `message_id: b256, destination_domain: u32, gas_amount: u64, payment: u64`
+    fn decode_gas_payment(data: &[u8]) -> Option<InterchainGasPayment> {
+        if data.len() != 56 {
+            return None;
+        }
+        let word = |offset: usize| {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&data[offset..offset + 8]);
+            u64::from_be_bytes(word)
+        };
+        Some(InterchainGasPayment {
+            message_id: H256::from_slice(&data[..32]),
+            destination: word(32).try_into().ok()?,
+            gas_amount: word(40).into(),
+            payment: word(48).into(),
+        })
+    }
+}
 
 #[async_trait]
 impl Indexer<InterchainGasPayment> for FuelInterchainGasPaymasterIndexer {
+    #[instrument(err, skip(self))]
     async fn fetch_logs(
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<InterchainGasPayment>, LogMeta)>> {
-        todo!()
+        let logs = self.log_indexer.fetch_logs(range).await?;
+        let mut payments = vec![];
+        for (log, meta) in logs {
+            if log.log_id != GAS_PAYMENT_LOG_ID {
+                continue;
+            }
+            match Self::decode_gas_payment(&log.data) {
+                Some(payment) => payments.push((Indexed::new(payment), meta)),
+                None => warn!(?meta, "Failed to decode a Fuel gas payment log"),
+            }
+        }
+        Ok(payments)
     }
 
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
-        todo!()
+        self.log_indexer.get_finalized_block_number().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<InterchainGasPayment> for FuelInterchainGasPaymasterIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        // Gas payments are indexed by block range, without a sequence
+        let tip = self.get_finalized_block_number().await?;
+        Ok((None, tip))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperlane_core::{InterchainGasPayment, H256};
+
+    use super::FuelInterchainGasPaymasterIndexer;
+
+    #[test]
+    fn test_decode_gas_payment() {
+        let message_id = H256::repeat_byte(0xab);
+        let data = [
+            message_id.as_bytes(),
+            &13374u64.to_be_bytes(),
+            &200_000u64.to_be_bytes(),
+            &1_000u64.to_be_bytes(),
+        ]
+        .concat();
+        assert_eq!(
+            FuelInterchainGasPaymasterIndexer::decode_gas_payment(&data),
+            Some(InterchainGasPayment {
+                message_id,
+                destination: 13374,
+                payment: 1_000.into(),
+                gas_amount: 200_000.into(),
+            })
+        );
+        assert_eq!(
+            FuelInterchainGasPaymasterIndexer::decode_gas_payment(&data[..48]),
+            None
+        );
     }
 }
@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use fuels::prelude::Bech32ContractId;
+use num_traits::FromPrimitive;
+use tracing::{instrument, warn};
+
+use hyperlane_core::{
+    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, InterchainSecurityModule, ModuleType,
+    RawHyperlaneMessage, H256, U256,
+};
+
+use crate::{
+    contracts::interchain_security_module::InterchainSecurityModule as FuelIsmInner,
+    conversions::*, utils::make_wallet, ConnectionConf, FuelProvider,
+};
+
+/// A reference to an ISM contract on some Fuel chain
+#[derive(Debug)]
+pub struct FuelInterchainSecurityModule {
+    contract: FuelIsmInner,
+    domain: HyperlaneDomain,
+    provider: FuelProvider,
+}
+
+impl FuelInterchainSecurityModule {
+    /// Create a reference to an ISM at a specific Fuel address
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> ChainResult<Self> {
+        let provider = FuelProvider::new(locator.domain.clone(), conf)?;
+        let wallet = make_wallet(provider.provider(), None);
+        Ok(Self {
+            contract: FuelIsmInner::new(Bech32ContractId::from_h256(&locator.address), wallet),
+            domain: locator.domain.clone(),
+            provider,
+        })
+    }
+}
+
+impl HyperlaneContract for FuelInterchainSecurityModule {
+    fn address(&self) -> H256 {
+        self.contract.contract_id().into_h256()
+    }
+}
+
+impl HyperlaneChain for FuelInterchainSecurityModule {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.provider.clone())
+    }
+}
+
+#[async_trait]
+impl InterchainSecurityModule for FuelInterchainSecurityModule {
+    #[instrument(err, ret, skip(self))]
+    async fn module_type(&self) -> ChainResult<ModuleType> {
+        let module_type = self
+            .contract
+            .methods()
+            .module_type()
+            .simulate()
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value;
+        if let Some(module_type) = ModuleType::from_u8(module_type) {
+            Ok(module_type)
+        } else {
+            warn!(%module_type, "Unknown module type");
+            Ok(ModuleType::Unused)
+        }
+    }
+
+    #[instrument(err, ret, skip(self))]
+    async fn dry_run_verify(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+    ) -> ChainResult<Option<U256>> {
+        let response = self
+            .contract
+            .methods()
+            .verify(metadata.to_vec(), RawHyperlaneMessage::from(message))
+            .estimate_tx_dependencies(None)
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .simulate()
+            .await;
+        match response {
+            Ok(response) if response.value => Ok(Some(response.gas_used.into())),
+            // The ISM rejected the metadata
+            _ => Ok(None),
+        }
+    }
+}
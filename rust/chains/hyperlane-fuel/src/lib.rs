@@ -1,4 +1,7 @@
 //! Implementation of hyperlane for fuel.
+//!
+//! Events are read from the raw `LogData` receipts the contracts emit, see
+//! `abis/` for the interfaces the contracts are expected to implement.
 
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
@@ -6,18 +9,25 @@
 #![allow(unused_variables)]
 
 pub use self::{
-    interchain_gas::*, mailbox::*, multisig_ism::*, provider::*, routing_ism::*, trait_builder::*,
+    aggregation_ism::*, error::*, interchain_gas::*, interchain_security_module::*, mailbox::*,
+    merkle_tree_hook::*, multisig_ism::*, provider::*, routing_ism::*, trait_builder::*,
     validator_announce::*,
 };
 
+mod aggregation_ism;
 mod contracts;
 mod conversions;
+mod error;
+mod indexer;
 mod interchain_gas;
+mod interchain_security_module;
 mod mailbox;
+mod merkle_tree_hook;
 mod multisig_ism;
 mod provider;
 mod routing_ism;
 mod trait_builder;
+mod utils;
 mod validator_announce;
 
 /// Safe default imports of commonly used traits/types.
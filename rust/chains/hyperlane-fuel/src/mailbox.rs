@@ -4,42 +4,66 @@ use std::num::NonZeroU64;
 use std::ops::RangeInclusive;
 
 use async_trait::async_trait;
-use fuels::prelude::{Bech32ContractId, WalletUnlocked};
-use hyperlane_core::Indexed;
-use tracing::instrument;
+use fuels::{
+    prelude::{Bech32ContractId, TxParameters, WalletUnlocked},
+    types::Bits256,
+};
+use hyperlane_core::{Decode, Indexed, SequenceAwareIndexer};
+use tracing::{instrument, warn};
 
 use hyperlane_core::{
-    utils::bytes_to_hex, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneAbi,
-    HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage, HyperlaneProvider,
-    Indexer, LogMeta, Mailbox, TxCostEstimate, TxOutcome, H256, U256,
+    utils::bytes_to_hex, ChainCommunicationError, ChainResult, ContractLocator, FixedPointNumber,
+    HyperlaneAbi, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage,
+    HyperlaneProvider, Indexer, LogMeta, Mailbox, TxCostEstimate, TxOutcome, H256, U256,
 };
 
 use crate::{
-    contracts::mailbox::Mailbox as FuelMailboxInner, conversions::*, make_provider, ConnectionConf,
+    contracts::{mailbox::Mailbox as FuelMailboxInner, message_recipient::MessageRecipient},
+    conversions::*,
+    indexer::FuelLogIndexer,
+    utils::{make_wallet, min_gas_price, to_fuel_message, tx_outcome},
+    ConnectionConf, FuelProvider,
 };
 
+/// The id of the `b256` type in the Mailbox ABI's logged types, which is the
+/// message id logged on process
+const PROCESS_LOG_ID: u64 = 6;
+
+/// The length of an encoded message without its body
+const MESSAGE_HEADER_LEN: usize = 77;
+
 /// A reference to a Mailbox contract on some Fuel chain
 pub struct FuelMailbox {
     contract: FuelMailboxInner,
     domain: HyperlaneDomain,
+    provider: FuelProvider,
+    wallet: WalletUnlocked,
 }
 
 impl FuelMailbox {
-    /// Create a new fuel mailbox
+    /// Create a new fuel mailbox. Without a wallet the mailbox can only be
+    /// queried.
     pub fn new(
         conf: &ConnectionConf,
         locator: ContractLocator,
-        mut wallet: WalletUnlocked,
+        wallet: Option<WalletUnlocked>,
     ) -> ChainResult<Self> {
-        let provider = make_provider(conf)?;
-        wallet.set_provider(provider);
+        let provider = FuelProvider::new(locator.domain.clone(), conf)?;
+        let wallet = make_wallet(provider.provider(), wallet);
         let address = Bech32ContractId::from_h256(&locator.address);
 
         Ok(FuelMailbox {
-            contract: FuelMailboxInner::new(address, wallet),
+            contract: FuelMailboxInner::new(address, wallet.clone()),
             domain: locator.domain.clone(),
+            provider,
+            wallet,
         })
     }
+
+    /// The mailbox contract bindings
+    pub(crate) fn contract(&self) -> &FuelMailboxInner {
+        &self.contract
+    }
 }
 
 impl HyperlaneContract for FuelMailbox {
@@ -54,7 +78,7 @@ impl HyperlaneChain for FuelMailbox {
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        todo!()
+        Box::new(self.provider.clone())
     }
 }
 
@@ -68,10 +92,8 @@ impl Debug for FuelMailbox {
 impl Mailbox for FuelMailbox {
     #[instrument(level = "debug", err, ret, skip(self))]
     async fn count(&self, lag: Option<NonZeroU64>) -> ChainResult<u32> {
-        assert!(
-            lag.is_none(),
-            "Fuel does not support querying point-in-time"
-        );
+        // Fuel blocks are final as soon as they are produced, so the latest
+        // state is never reorged and the lag doesn't need to be applied
         self.contract
             .methods()
             .count()
@@ -83,17 +105,43 @@ impl Mailbox for FuelMailbox {
 
     #[instrument(level = "debug", err, ret, skip(self))]
     async fn delivered(&self, id: H256) -> ChainResult<bool> {
-        todo!()
+        self.contract
+            .methods()
+            .delivered(Bits256::from_h256(&id))
+            .simulate()
+            .await
+            .map(|r| r.value)
+            .map_err(ChainCommunicationError::from_other)
     }
 
     #[instrument(err, ret, skip(self))]
     async fn default_ism(&self) -> ChainResult<H256> {
-        todo!()
+        self.contract
+            .methods()
+            .get_default_ism()
+            .simulate()
+            .await
+            .map(|r| r.value.into_h256())
+            .map_err(ChainCommunicationError::from_other)
     }
 
     #[instrument(err, ret, skip(self))]
     async fn recipient_ism(&self, recipient: H256) -> ChainResult<H256> {
-        todo!()
+        // Recipients without their own ISM return the zero id, which the
+        // mailbox replaces with its default ISM
+        let ism =
+            MessageRecipient::new(Bech32ContractId::from_h256(&recipient), self.wallet.clone())
+                .methods()
+                .interchain_security_module()
+                .simulate()
+                .await
+                .map(|r| r.value.into_h256())
+                .map_err(ChainCommunicationError::from_other)?;
+        if ism.is_zero() {
+            self.default_ism().await
+        } else {
+            Ok(ism)
+        }
     }
 
     #[instrument(err, ret, skip(self))]
@@ -103,7 +151,26 @@ impl Mailbox for FuelMailbox {
         metadata: &[u8],
         tx_gas_limit: Option<U256>,
     ) -> ChainResult<TxOutcome> {
-        todo!()
+        let gas_price = min_gas_price(self.provider.provider()).await?;
+        let tx_params = TxParameters::new(
+            Some(gas_price),
+            tx_gas_limit.map(|limit| limit.as_u64()),
+            None,
+        );
+        // The recipient and the ISMs it uses have to be declared as inputs of
+        // the transaction, which is what estimating its dependencies does
+        let response = self
+            .contract
+            .methods()
+            .process(metadata.to_vec(), to_fuel_message(message))
+            .tx_params(tx_params)
+            .estimate_tx_dependencies(None)
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .call()
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        Ok(tx_outcome(&response, gas_price))
     }
 
     #[instrument(err, ret, skip(self), fields(msg=%message, metadata=%bytes_to_hex(metadata)))]
@@ -112,43 +179,124 @@ impl Mailbox for FuelMailbox {
         message: &HyperlaneMessage,
         metadata: &[u8],
     ) -> ChainResult<TxCostEstimate> {
-        todo!()
+        let gas_price = min_gas_price(self.provider.provider()).await?;
+        let response = self
+            .contract
+            .methods()
+            .process(metadata.to_vec(), to_fuel_message(message))
+            .estimate_tx_dependencies(None)
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .simulate()
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        Ok(TxCostEstimate {
+            gas_limit: response.gas_used.into(),
+            gas_price: FixedPointNumber::from(gas_price),
+            l2_gas_limit: None,
+        })
     }
 
     fn process_calldata(&self, message: &HyperlaneMessage, metadata: &[u8]) -> Vec<u8> {
-        todo!()
+        // Only used to batch process calls, which Fuel does not support
+        vec![]
     }
 }
 
 /// Struct that retrieves event data for a Fuel Mailbox contract
 #[derive(Debug)]
-pub struct FuelMailboxIndexer {}
+pub struct FuelMailboxIndexer {
+    mailbox: FuelMailbox,
+    log_indexer: FuelLogIndexer,
+}
+
+impl FuelMailboxIndexer {
+    /// Create a new fuel mailbox indexer
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> ChainResult<Self> {
+        let mailbox = FuelMailbox::new(conf, locator, None)?;
+        let log_indexer = FuelLogIndexer::new(mailbox.provider.clone(), mailbox.address());
+        Ok(Self {
+            mailbox,
+            log_indexer,
+        })
+    }
+
+    /// The messages dispatched in the block range. The mailbox logs each
+    /// dispatched message as raw log data holding the encoded message.
+    pub(crate) async fn dispatched_messages(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(HyperlaneMessage, LogMeta)>> {
+        let logs = self.log_indexer.fetch_logs(range).await?;
+        let mut messages = vec![];
+        for (log, meta) in logs {
+            if log.log_id == PROCESS_LOG_ID || log.data.len() < MESSAGE_HEADER_LEN {
+                continue;
+            }
+            match HyperlaneMessage::read_from(&mut log.data.as_slice()) {
+                Ok(message) => messages.push((message, meta)),
+                Err(err) => warn!(?err, ?meta, "Failed to decode a Fuel mailbox log"),
+            }
+        }
+        Ok(messages)
+    }
+}
 
 #[async_trait]
 impl Indexer<HyperlaneMessage> for FuelMailboxIndexer {
+    #[instrument(err, skip(self))]
     async fn fetch_logs(
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
-        todo!()
+        let messages = self.dispatched_messages(range).await?;
+        Ok(messages
+            .into_iter()
+            .map(|(message, meta)| (message.into(), meta))
+            .collect())
     }
 
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
-        todo!()
+        self.log_indexer.get_finalized_block_number().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<HyperlaneMessage> for FuelMailboxIndexer {
+    #[instrument(err, skip(self))]
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = Indexer::<HyperlaneMessage>::get_finalized_block_number(self).await?;
+        let count = Mailbox::count(&self.mailbox, None).await?;
+        Ok((Some(count), tip))
     }
 }
 
 #[async_trait]
 impl Indexer<H256> for FuelMailboxIndexer {
+    #[instrument(err, skip(self))]
     async fn fetch_logs(
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
-        todo!()
+        let logs = self.log_indexer.fetch_logs(range).await?;
+        Ok(logs
+            .into_iter()
+            .filter(|(log, _)| log.log_id == PROCESS_LOG_ID && log.data.len() == 32)
+            .map(|(log, meta)| (Indexed::new(H256::from_slice(&log.data)), meta))
+            .collect())
     }
 
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
-        todo!()
+        self.log_indexer.get_finalized_block_number().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<H256> for FuelMailboxIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        // Deliveries are indexed by block range, without a sequence
+        let tip = Indexer::<H256>::get_finalized_block_number(self).await?;
+        Ok((None, tip))
     }
 }
 
@@ -159,6 +307,6 @@ impl HyperlaneAbi for FuelMailboxAbi {
 
     fn fn_map() -> HashMap<Vec<u8>, &'static str> {
         // Can't support this without Fuels exporting it in the generated code
-        todo!()
+        HashMap::new()
     }
 }
@@ -0,0 +1,95 @@
+use std::{num::NonZeroU64, ops::RangeInclusive};
+
+use async_trait::async_trait;
+use hyperlane_core::{
+    accumulator::incremental::IncrementalMerkle, ChainCommunicationError, ChainResult, Checkpoint,
+    HyperlaneChain, HyperlaneContract, HyperlaneMessage, Indexed, Indexer, LogMeta, Mailbox,
+    MerkleTreeHook, MerkleTreeInsertion, SequenceAwareIndexer,
+};
+use tracing::instrument;
+
+use crate::{conversions::*, FuelMailbox, FuelMailboxIndexer};
+
+/// The Fuel mailbox keeps the merkle tree itself rather than in a separate
+/// hook, so the lag is ignored for the same reason as in `Mailbox::count`.
+#[async_trait]
+impl MerkleTreeHook for FuelMailbox {
+    #[instrument(err, ret, skip(self))]
+    async fn tree(&self, lag: Option<NonZeroU64>) -> ChainResult<IncrementalMerkle> {
+        let branch = self
+            .contract()
+            .methods()
+            .branch()
+            .simulate()
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value
+            .map(|node| node.into_h256());
+        let count = Mailbox::count(self, lag).await?;
+        Ok(IncrementalMerkle::new(branch, count as usize))
+    }
+
+    #[instrument(err, ret, skip(self))]
+    async fn latest_checkpoint(&self, lag: Option<NonZeroU64>) -> ChainResult<Checkpoint> {
+        let (root, index) = self
+            .contract()
+            .methods()
+            .latest_checkpoint()
+            .simulate()
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value;
+        Ok(Checkpoint {
+            merkle_tree_hook_address: self.address(),
+            mailbox_domain: self.domain().id(),
+            root: root.into_h256(),
+            index,
+        })
+    }
+
+    #[instrument(err, ret, skip(self))]
+    async fn count(&self, lag: Option<NonZeroU64>) -> ChainResult<u32> {
+        Mailbox::count(self, lag).await
+    }
+}
+
+/// Struct that retrieves event data for a Fuel merkle tree hook contract
+/// For now it's just a wrapper around the FuelMailboxIndexer
+#[derive(Debug)]
+pub struct FuelMerkleTreeHookIndexer(FuelMailboxIndexer);
+
+impl FuelMerkleTreeHookIndexer {
+    /// Create a new fuel merkle tree hook indexer
+    pub fn new(mailbox_indexer: FuelMailboxIndexer) -> Self {
+        Self(mailbox_indexer)
+    }
+}
+
+#[async_trait]
+impl Indexer<MerkleTreeInsertion> for FuelMerkleTreeHookIndexer {
+    async fn fetch_logs(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<MerkleTreeInsertion>, LogMeta)>> {
+        let messages = self.0.dispatched_messages(range).await?;
+        let merkle_tree_insertions = messages
+            .into_iter()
+            .map(|(message, meta)| {
+                let insertion = MerkleTreeInsertion::new(message.nonce, message.id());
+                (insertion.into(), meta)
+            })
+            .collect();
+        Ok(merkle_tree_insertions)
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        Indexer::<HyperlaneMessage>::get_finalized_block_number(&self.0).await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<MerkleTreeInsertion> for FuelMerkleTreeHookIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        SequenceAwareIndexer::<HyperlaneMessage>::latest_sequence_count_and_tip(&self.0).await
+    }
+}
@@ -1,37 +1,77 @@
 use async_trait::async_trait;
+use fuels::prelude::Bech32ContractId;
+use tracing::instrument;
 
 use hyperlane_core::{
-    ChainResult, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage,
-    HyperlaneProvider, MultisigIsm, H256,
+    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, MultisigIsm, RawHyperlaneMessage, H256,
+};
+
+use crate::{
+    contracts::multisig_ism::MultisigIsm as FuelMultisigIsmInner, conversions::*,
+    utils::make_wallet, ConnectionConf, FuelProvider,
 };
 
 /// A reference to a MultisigIsm contract on some Fuel chain
 #[derive(Debug)]
-pub struct FuelMultisigIsm {}
+pub struct FuelMultisigIsm {
+    contract: FuelMultisigIsmInner,
+    domain: HyperlaneDomain,
+    provider: FuelProvider,
+}
+
+impl FuelMultisigIsm {
+    /// Create a reference to a MultisigIsm at a specific Fuel address
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> ChainResult<Self> {
+        let provider = FuelProvider::new(locator.domain.clone(), conf)?;
+        let wallet = make_wallet(provider.provider(), None);
+        Ok(Self {
+            contract: FuelMultisigIsmInner::new(
+                Bech32ContractId::from_h256(&locator.address),
+                wallet,
+            ),
+            domain: locator.domain.clone(),
+            provider,
+        })
+    }
+}
 
 impl HyperlaneContract for FuelMultisigIsm {
     fn address(&self) -> H256 {
-        todo!()
+        self.contract.contract_id().into_h256()
     }
 }
 
 impl HyperlaneChain for FuelMultisigIsm {
     fn domain(&self) -> &HyperlaneDomain {
-        todo!()
+        &self.domain
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        todo!()
+        Box::new(self.provider.clone())
     }
 }
 
 #[async_trait]
 impl MultisigIsm for FuelMultisigIsm {
     /// Returns the validator and threshold needed to verify message
+    #[instrument(err, ret, skip(self))]
     async fn validators_and_threshold(
         &self,
         message: &HyperlaneMessage,
     ) -> ChainResult<(Vec<H256>, u8)> {
-        todo!()
+        let (validators, threshold) = self
+            .contract
+            .methods()
+            .validators_and_threshold(RawHyperlaneMessage::from(message))
+            .simulate()
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value;
+        let validators = validators
+            .into_iter()
+            .map(|validator| validator.into_h256())
+            .collect();
+        Ok((validators, threshold))
     }
 }
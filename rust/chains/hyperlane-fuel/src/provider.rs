@@ -1,40 +1,102 @@
+use std::str::FromStr;
+
 use async_trait::async_trait;
+use fuels::{
+    prelude::{AssetId, Bech32Address, Provider},
+    tx::Bytes32,
+    types::block::Block,
+};
 
 use hyperlane_core::{
-    BlockInfo, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain, HyperlaneProvider, TxnInfo,
-    H256, U256,
+    BlockInfo, ChainCommunicationError, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain,
+    HyperlaneProvider, HyperlaneProviderError, TxnInfo, H256, H512, U256,
 };
 
+use crate::{make_provider, ConnectionConf, HyperlaneFuelError};
+
 /// A wrapper around a fuel provider to get generic blockchain information.
-#[derive(Debug)]
-pub struct FuelProvider {}
+#[derive(Debug, Clone)]
+pub struct FuelProvider {
+    domain: HyperlaneDomain,
+    provider: Provider,
+}
+
+impl FuelProvider {
+    /// Create a new fuel provider
+    pub fn new(domain: HyperlaneDomain, conf: &ConnectionConf) -> ChainResult<Self> {
+        Ok(Self {
+            domain,
+            provider: make_provider(conf)?,
+        })
+    }
+
+    /// Get the underlying fuels provider
+    pub fn provider(&self) -> &Provider {
+        &self.provider
+    }
+
+    fn block_info(block: Block) -> BlockInfo {
+        BlockInfo {
+            hash: H256::from(*block.id),
+            timestamp: block
+                .header
+                .time
+                .map(|time| time.timestamp() as u64)
+                .unwrap_or_default(),
+            number: block.header.height,
+        }
+    }
+}
 
 impl HyperlaneChain for FuelProvider {
     fn domain(&self) -> &HyperlaneDomain {
-        todo!()
+        &self.domain
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        todo!()
+        Box::new(self.clone())
     }
 }
 
 #[async_trait]
 impl HyperlaneProvider for FuelProvider {
     async fn get_block_by_hash(&self, hash: &H256) -> ChainResult<BlockInfo> {
-        todo!()
+        let block = self
+            .provider
+            .block(&Bytes32::new(hash.0))
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .ok_or(HyperlaneProviderError::CouldNotFindObjectByHash(*hash))?;
+        Ok(Self::block_info(block))
+    }
+
+    async fn get_block_by_height(&self, height: u64) -> ChainResult<BlockInfo> {
+        let block = self
+            .provider
+            .block_by_height(height)
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .ok_or(HyperlaneProviderError::CouldNotFindBlockByHeight(height))?;
+        Ok(Self::block_info(block))
     }
 
-    async fn get_txn_by_hash(&self, hash: &H256) -> ChainResult<TxnInfo> {
-        todo!()
+    async fn get_txn_by_hash(&self, hash: &H512) -> ChainResult<TxnInfo> {
+        Err(HyperlaneFuelError::Unsupported("Fetching transactions by hash").into())
     }
 
     async fn is_contract(&self, address: &H256) -> ChainResult<bool> {
-        todo!()
+        Err(HyperlaneFuelError::Unsupported("Checking whether an address is a contract").into())
     }
 
     async fn get_balance(&self, address: String) -> ChainResult<U256> {
-        todo!()
+        let address =
+            Bech32Address::from_str(&address).map_err(ChainCommunicationError::from_other)?;
+        let balance = self
+            .provider
+            .get_asset_balance(&address, AssetId::BASE)
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        Ok(balance.into())
     }
 
     async fn get_chain_metrics(&self) -> ChainResult<Option<ChainInfo>> {
@@ -1,34 +1,68 @@
 use async_trait::async_trait;
+use fuels::prelude::Bech32ContractId;
+use tracing::instrument;
 
 use hyperlane_core::{
-    ChainResult, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage,
-    HyperlaneProvider, RoutingIsm, H256,
+    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, RawHyperlaneMessage, RoutingIsm, H256,
+};
+
+use crate::{
+    contracts::routing_ism::RoutingIsm as FuelRoutingIsmInner, conversions::*, utils::make_wallet,
+    ConnectionConf, FuelProvider,
 };
 
 /// A reference to a RoutingIsm contract on some Fuel chain
 #[derive(Debug)]
-pub struct FuelRoutingIsm {}
+pub struct FuelRoutingIsm {
+    contract: FuelRoutingIsmInner,
+    domain: HyperlaneDomain,
+    provider: FuelProvider,
+}
+
+impl FuelRoutingIsm {
+    /// Create a reference to a RoutingIsm at a specific Fuel address
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> ChainResult<Self> {
+        let provider = FuelProvider::new(locator.domain.clone(), conf)?;
+        let wallet = make_wallet(provider.provider(), None);
+        Ok(Self {
+            contract: FuelRoutingIsmInner::new(
+                Bech32ContractId::from_h256(&locator.address),
+                wallet,
+            ),
+            domain: locator.domain.clone(),
+            provider,
+        })
+    }
+}
 
 impl HyperlaneContract for FuelRoutingIsm {
     fn address(&self) -> H256 {
-        todo!()
+        self.contract.contract_id().into_h256()
     }
 }
 
 impl HyperlaneChain for FuelRoutingIsm {
     fn domain(&self) -> &HyperlaneDomain {
-        todo!()
+        &self.domain
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        todo!()
+        Box::new(self.provider.clone())
     }
 }
 
 #[async_trait]
 impl RoutingIsm for FuelRoutingIsm {
     /// Returns the ism needed to verify message
+    #[instrument(err, ret, skip(self))]
     async fn route(&self, message: &HyperlaneMessage) -> ChainResult<H256> {
-        todo!()
+        self.contract
+            .methods()
+            .route(RawHyperlaneMessage::from(message))
+            .simulate()
+            .await
+            .map(|r| r.value.into_h256())
+            .map_err(ChainCommunicationError::from_other)
     }
 }
@@ -0,0 +1,55 @@
+use fuels::{
+    prelude::{FuelCallResponse, Provider, WalletUnlocked},
+    types::Bits256,
+};
+use hyperlane_core::{
+    ChainCommunicationError, ChainResult, FixedPointNumber, HyperlaneMessage, TxOutcome, H256, H512,
+};
+
+use crate::{contracts::mailbox::Message, conversions::*};
+
+/// The wallet contract instances are created with. Contracts that are only
+/// queried don't need a funded wallet, so a random one is used when no
+/// signer is configured.
+pub(crate) fn make_wallet(provider: &Provider, wallet: Option<WalletUnlocked>) -> WalletUnlocked {
+    let mut wallet = wallet.unwrap_or_else(|| WalletUnlocked::new_random(None));
+    wallet.set_provider(provider.clone());
+    wallet
+}
+
+/// The minimum gas price the node accepts, which is what transactions are
+/// submitted with
+pub(crate) async fn min_gas_price(provider: &Provider) -> ChainResult<u64> {
+    provider
+        .node_info()
+        .await
+        .map(|info| info.min_gas_price)
+        .map_err(ChainCommunicationError::from_other)
+}
+
+/// The outcome of a contract call that was submitted with `gas_price`
+pub(crate) fn tx_outcome<D>(response: &FuelCallResponse<D>, gas_price: u64) -> TxOutcome {
+    TxOutcome {
+        transaction_id: response
+            .tx_id
+            .map(|id| H512::from(H256::from(*id)))
+            .unwrap_or_default(),
+        // Calls that revert return an error instead of a response
+        executed: true,
+        gas_used: response.gas_used.into(),
+        gas_price: FixedPointNumber::from(gas_price),
+    }
+}
+
+/// The Mailbox ABI representation of a message
+pub(crate) fn to_fuel_message(message: &HyperlaneMessage) -> Message {
+    Message {
+        version: message.version,
+        nonce: message.nonce,
+        origin: message.origin,
+        sender: Bits256::from_h256(&message.sender),
+        destination: message.destination,
+        recipient: Bits256::from_h256(&message.recipient),
+        body: message.body.clone(),
+    }
+}
@@ -1,44 +1,203 @@
+use std::ops::RangeInclusive;
+
 use async_trait::async_trait;
+use fuels::{
+    prelude::{Bech32ContractId, TxParameters, WalletUnlocked},
+    types::Bits256,
+};
+use tracing::{instrument, warn};
 
 use hyperlane_core::{
-    Announcement, ChainResult, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
-    HyperlaneProvider, SignedType, TxOutcome, ValidatorAnnounce, H256, U256,
+    Announcement, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneProvider, Indexed, Indexer, LogMeta,
+    SequenceAwareIndexer, SignedType, TxOutcome, ValidatorAnnounce, ValidatorAnnouncement, H160,
+    H256, U256,
+};
+
+use crate::{
+    contracts::validator_announce::ValidatorAnnounce as FuelValidatorAnnounceInner,
+    conversions::*,
+    indexer::FuelLogIndexer,
+    utils::{make_wallet, min_gas_price, tx_outcome},
+    ConnectionConf, FuelProvider,
 };
 
+/// The log id of the raw log data the ValidatorAnnounce contract emits on
+/// each announcement, which holds the validator as a `b256` followed by the
+/// storage location
+const ANNOUNCEMENT_LOG_ID: u64 = 0;
+
 /// A reference to a ValidatorAnnounce contract on some Fuel chain
 #[derive(Debug)]
-pub struct FuelValidatorAnnounce {}
+pub struct FuelValidatorAnnounce {
+    contract: FuelValidatorAnnounceInner,
+    domain: HyperlaneDomain,
+    provider: FuelProvider,
+}
+
+impl FuelValidatorAnnounce {
+    /// Create a reference to a ValidatorAnnounce at a specific Fuel address.
+    /// Without a wallet the contract can only be queried.
+    pub fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator,
+        wallet: Option<WalletUnlocked>,
+    ) -> ChainResult<Self> {
+        let provider = FuelProvider::new(locator.domain.clone(), conf)?;
+        let wallet = make_wallet(provider.provider(), wallet);
+        Ok(Self {
+            contract: FuelValidatorAnnounceInner::new(
+                Bech32ContractId::from_h256(&locator.address),
+                wallet,
+            ),
+            domain: locator.domain.clone(),
+            provider,
+        })
+    }
+
+    async fn storage_locations(&self, validator: Bits256) -> ChainResult<Vec<String>> {
+        let count = self
+            .contract
+            .methods()
+            .get_announced_storage_location_count(validator)
+            .simulate()
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value;
+        let mut locations = Vec::with_capacity(count as usize);
+        for index in 0..count {
+            let location = self
+                .contract
+                .methods()
+                .get_announced_storage_location(validator, index)
+                .simulate()
+                .await
+                .map_err(ChainCommunicationError::from_other)?
+                .value;
+            locations
+                .push(String::from_utf8(location).map_err(ChainCommunicationError::from_other)?);
+        }
+        Ok(locations)
+    }
+}
 
 impl HyperlaneContract for FuelValidatorAnnounce {
     fn address(&self) -> H256 {
-        todo!()
+        self.contract.contract_id().into_h256()
     }
 }
 
 impl HyperlaneChain for FuelValidatorAnnounce {
     fn domain(&self) -> &HyperlaneDomain {
-        todo!()
+        &self.domain
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        todo!()
+        Box::new(self.provider.clone())
     }
 }
 
 #[async_trait]
 impl ValidatorAnnounce for FuelValidatorAnnounce {
+    #[instrument(err, ret, skip(self))]
     async fn get_announced_storage_locations(
         &self,
         validators: &[H256],
     ) -> ChainResult<Vec<Vec<String>>> {
-        todo!()
+        let mut storage_locations = Vec::with_capacity(validators.len());
+        for validator in validators {
+            storage_locations.push(
+                self.storage_locations(Bits256::from_h256(validator))
+                    .await?,
+            );
+        }
+        Ok(storage_locations)
     }
 
+    #[instrument(err, ret, skip(self))]
     async fn announce(&self, announcement: SignedType<Announcement>) -> ChainResult<TxOutcome> {
-        todo!()
+        let gas_price = min_gas_price(self.provider.provider()).await?;
+        let validator: H256 = announcement.value.validator.into();
+        let response = self
+            .contract
+            .methods()
+            .announce(
+                Bits256::from_h256(&validator),
+                announcement.value.storage_location.into_bytes(),
+                announcement.signature.to_vec(),
+            )
+            .tx_params(TxParameters::new(Some(gas_price), None, None))
+            .call()
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        Ok(tx_outcome(&response, gas_price))
     }
 
     async fn announce_tokens_needed(&self, announcement: SignedType<Announcement>) -> Option<U256> {
-        todo!()
+        // TODO: check the wallet balance. For now, just try announcing and
+        // allow the announce attempt to fail if there are not enough tokens.
+        Some(U256::zero())
+    }
+}
+
+/// Struct that retrieves announcements from a Fuel ValidatorAnnounce contract
+#[derive(Debug)]
+pub struct FuelValidatorAnnounceIndexer {
+    log_indexer: FuelLogIndexer,
+}
+
+impl FuelValidatorAnnounceIndexer {
+    /// Create a new fuel validator announce indexer
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> ChainResult<Self> {
+        let provider = FuelProvider::new(locator.domain.clone(), conf)?;
+        Ok(Self {
+            log_indexer: FuelLogIndexer::new(provider, locator.address),
+        })
+    }
+
+    fn decode_announcement(data: &[u8]) -> Option<ValidatorAnnouncement> {
+        if data.len() < 32 {
+            return None;
+        }
+        let (validator, storage_location) = data.split_at(32);
+        Some(ValidatorAnnouncement {
+            validator: H160::from(H256::from_slice(validator)),
+            storage_location: String::from_utf8(storage_location.to_vec()).ok()?,
+        })
+    }
+}
+
+#[async_trait]
+impl Indexer<ValidatorAnnouncement> for FuelValidatorAnnounceIndexer {
+    #[instrument(err, skip(self))]
+    async fn fetch_logs(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<ValidatorAnnouncement>, LogMeta)>> {
+        let logs = self.log_indexer.fetch_logs(range).await?;
+        let mut announcements = vec![];
+        for (log, meta) in logs {
+            if log.log_id != ANNOUNCEMENT_LOG_ID {
+                continue;
+            }
+            match Self::decode_announcement(&log.data) {
+                Some(announcement) => announcements.push((Indexed::new(announcement), meta)),
+                None => warn!(?meta, "Failed to decode a Fuel validator announcement log"),
+            }
+        }
+        Ok(announcements)
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.log_indexer.get_finalized_block_number().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<ValidatorAnnouncement> for FuelValidatorAnnounceIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        // Announcements are indexed by block range, without a sequence
+        let tip = self.get_finalized_block_number().await?;
+        Ok((None, tip))
     }
 }
@@ -1,6 +1,12 @@
-use solana_client::nonblocking::rpc_client::RpcClient;
+use hyperlane_core::{rpc_clients::RpcConsensusType, HyperlaneDomain};
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_client::RpcClientConfig};
 use solana_sdk::commitment_config::CommitmentConfig;
 
+use crate::{
+    rpc_clients::{SealevelFallbackRpcSender, SealevelQuorumRpcSender, SealevelRpcSender},
+    ConnectionConf,
+};
+
 /// Kludge to implement Debug for RpcClient.
 pub struct RpcClientWithDebug(RpcClient);
 
@@ -9,8 +15,27 @@ impl RpcClientWithDebug {
         Self(RpcClient::new(rpc_endpoint))
     }
 
-    pub fn new_with_commitment(rpc_endpoint: String, commitment: CommitmentConfig) -> Self {
-        Self(RpcClient::new_with_commitment(rpc_endpoint, commitment))
+    /// Creates a client that spreads requests over the configured urls
+    /// according to the configured consensus type
+    pub fn new_with_commitment(
+        conf: &ConnectionConf,
+        domain: &HyperlaneDomain,
+        commitment: CommitmentConfig,
+    ) -> Self {
+        let senders = conf.urls.iter().map(|url| {
+            SealevelRpcSender::new(url.clone(), domain.name(), conf.rpc_metrics.clone())
+        });
+        let config = RpcClientConfig::with_commitment(commitment);
+        match conf.rpc_consensus_type {
+            RpcConsensusType::Fallback => Self(RpcClient::new_sender(
+                SealevelFallbackRpcSender::new(senders),
+                config,
+            )),
+            RpcConsensusType::Quorum => Self(RpcClient::new_sender(
+                SealevelQuorumRpcSender::new(senders),
+                config,
+            )),
+        }
     }
 }
 
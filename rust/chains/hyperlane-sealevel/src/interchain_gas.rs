@@ -119,7 +119,8 @@ impl SealevelInterchainGasPaymasterIndexer {
     ) -> ChainResult<Self> {
         // Set the `processed` commitment at rpc level
         let rpc_client = RpcClientWithDebug::new_with_commitment(
-            conf.url.to_string(),
+            conf,
+            igp_account_locator.domain,
             CommitmentConfig::processed(),
         );
 
@@ -1,16 +1,26 @@
 use async_trait::async_trait;
 use num_traits::cast::FromPrimitive;
-use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+};
 use tracing::warn;
 
 use hyperlane_core::{
-    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
-    HyperlaneDomain, HyperlaneMessage, InterchainSecurityModule, ModuleType, H256, U256,
+    ChainCommunicationError, ChainResult, ContractLocator, Encode as _, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneMessage, InterchainSecurityModule, ModuleType,
+    H256, U256,
+};
+use hyperlane_sealevel_interchain_security_module_interface::{
+    InterchainSecurityModuleInstruction, VerifyInstruction, VERIFY_ACCOUNT_METAS_PDA_SEEDS,
 };
-use hyperlane_sealevel_interchain_security_module_interface::InterchainSecurityModuleInstruction;
 use serializable_account_meta::SimulationReturnData;
 
-use crate::{utils::simulate_instruction, ConnectionConf, RpcClientWithDebug, SealevelProvider};
+use crate::{
+    utils::{get_account_metas, simulate_instruction, simulate_instructions_compute_units},
+    ConnectionConf, RpcClientWithDebug, SealevelProvider,
+};
 
 /// A reference to an InterchainSecurityModule contract on some Sealevel chain
 #[derive(Debug)]
@@ -35,6 +45,30 @@ impl SealevelInterchainSecurityModule {
     fn rpc(&self) -> &RpcClientWithDebug {
         self.provider.rpc()
     }
+
+    fn payer(&self) -> ChainResult<&Keypair> {
+        self.payer
+            .as_ref()
+            .ok_or_else(|| ChainCommunicationError::SignerUnavailable)
+    }
+
+    /// Gets the account metas required for the ISM's `Verify` instruction.
+    async fn get_verify_account_metas(
+        &self,
+        verify_instruction: VerifyInstruction,
+    ) -> ChainResult<Vec<AccountMeta>> {
+        let (account_metas_pda_key, _) =
+            Pubkey::find_program_address(VERIFY_ACCOUNT_METAS_PDA_SEEDS, &self.program_id);
+        let instruction = Instruction::new_with_bytes(
+            self.program_id,
+            &InterchainSecurityModuleInstruction::VerifyAccountMetas(verify_instruction)
+                .encode()
+                .map_err(ChainCommunicationError::from_other)?[..],
+            vec![AccountMeta::new(account_metas_pda_key, false)],
+        );
+
+        get_account_metas(self.rpc(), self.payer()?, instruction).await
+    }
 }
 
 impl HyperlaneContract for SealevelInterchainSecurityModule {
@@ -66,9 +100,7 @@ impl InterchainSecurityModule for SealevelInterchainSecurityModule {
 
         let module = simulate_instruction::<SimulationReturnData<u32>>(
             self.rpc(),
-            self.payer
-                .as_ref()
-                .ok_or_else(|| ChainCommunicationError::SignerUnavailable)?,
+            self.payer()?,
             instruction,
         )
         .await?
@@ -85,12 +117,30 @@ impl InterchainSecurityModule for SealevelInterchainSecurityModule {
         }
     }
 
+    /// Simulates the ISM's `Verify` instruction, returning the compute units
+    /// consumed if verification succeeded.
     async fn dry_run_verify(
         &self,
-        _message: &HyperlaneMessage,
-        _metadata: &[u8],
+        message: &HyperlaneMessage,
+        metadata: &[u8],
     ) -> ChainResult<Option<U256>> {
-        // TODO: Implement this once we have aggregation ISM support in Sealevel
-        Ok(Some(U256::zero()))
+        let verify_instruction = VerifyInstruction {
+            metadata: metadata.to_vec(),
+            message: message.to_vec(),
+        };
+        let account_metas = self
+            .get_verify_account_metas(verify_instruction.clone())
+            .await?;
+        let instruction = Instruction::new_with_bytes(
+            self.program_id,
+            &InterchainSecurityModuleInstruction::Verify(verify_instruction)
+                .encode()
+                .map_err(ChainCommunicationError::from_other)?[..],
+            account_metas,
+        );
+
+        let compute_units =
+            simulate_instructions_compute_units(self.rpc(), self.payer()?, &[instruction]).await?;
+        Ok(compute_units.map(U256::from))
     }
 }
@@ -10,6 +10,7 @@ pub use interchain_gas::*;
 pub use interchain_security_module::*;
 pub use mailbox::*;
 pub use merkle_tree_hook::*;
+pub use priority_fee::*;
 pub use provider::*;
 pub use solana_sdk::signer::keypair::Keypair;
 pub use trait_builder::*;
@@ -21,7 +22,9 @@ mod interchain_security_module;
 mod mailbox;
 mod merkle_tree_hook;
 mod multisig_ism;
+mod priority_fee;
 mod provider;
+mod rpc_clients;
 mod trait_builder;
 mod utils;
 
@@ -1,10 +1,13 @@
 #![allow(warnings)] // FIXME remove
 
-use std::{collections::HashMap, num::NonZeroU64, ops::RangeInclusive, str::FromStr as _};
+use std::{
+    collections::HashMap, num::NonZeroU64, ops::RangeInclusive, str::FromStr as _, time::Duration,
+};
 
 use async_trait::async_trait;
 use borsh::{BorshDeserialize, BorshSerialize};
 use jsonrpc_core::futures_util::TryFutureExt;
+use tokio::time::sleep;
 use tracing::{debug, info, instrument, warn};
 
 use hyperlane_core::{
@@ -18,7 +21,7 @@ use hyperlane_sealevel_interchain_security_module_interface::{
     InterchainSecurityModuleInstruction, VerifyInstruction,
 };
 use hyperlane_sealevel_mailbox::{
-    accounts::{DispatchedMessageAccount, InboxAccount, OutboxAccount},
+    accounts::{DispatchedMessageAccount, InboxAccount, OutboxAccount, ProcessedMessageAccount},
     instruction::InboxProcess,
     mailbox_dispatched_message_pda_seeds, mailbox_inbox_pda_seeds, mailbox_outbox_pda_seeds,
     mailbox_process_authority_pda_seeds, mailbox_processed_message_pda_seeds,
@@ -30,6 +33,7 @@ use serializable_account_meta::SimulationReturnData;
 use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
 use solana_client::{
     nonblocking::rpc_client::RpcClient,
+    rpc_client::GetConfirmedSignaturesForAddress2Config,
     rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcSendTransactionConfig},
     rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
 };
@@ -54,16 +58,25 @@ use solana_transaction_status::{
 
 use crate::RpcClientWithDebug;
 use crate::{
-    utils::{get_account_metas, get_finalized_block_number, simulate_instruction},
-    ConnectionConf, SealevelProvider,
+    utils::{
+        get_account_metas, get_block_info, get_finalized_block_number, simulate_instruction,
+        simulate_instructions_compute_units,
+    },
+    ConnectionConf, PriorityFeeConfig, SealevelProvider,
 };
 
 const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
 const SPL_NOOP: &str = "noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV";
 
 // The max amount of compute units for a transaction.
-// TODO: consider a more sane value and/or use IGP gas payments instead.
-const PROCESS_COMPUTE_UNITS: u32 = 1_400_000;
+const MAX_COMPUTE_UNITS: u32 = 1_400_000;
+// The buffer added to the simulated compute units, in percent.
+const COMPUTE_UNITS_BUFFER_PERCENT: u32 = 10;
+// How many times a transaction that expired before landing is resubmitted
+// with a bumped priority fee.
+const MAX_PROCESS_RESUBMISSIONS: usize = 3;
+// How often to poll for a submitted transaction's status.
+const TX_STATUS_POLL_INTERVAL: Duration = Duration::from_millis(500);
 
 /// A reference to a Mailbox contract on some Sealevel chain
 pub struct SealevelMailbox {
@@ -72,6 +85,7 @@ pub struct SealevelMailbox {
     pub(crate) outbox: (Pubkey, u8),
     pub(crate) provider: SealevelProvider,
     payer: Option<Keypair>,
+    priority_fee: PriorityFeeConfig,
 }
 
 impl SealevelMailbox {
@@ -98,6 +112,7 @@ impl SealevelMailbox {
             outbox,
             provider,
             payer,
+            priority_fee: conf.priority_fee.clone(),
         })
     }
 
@@ -237,6 +252,79 @@ impl SealevelMailbox {
         .await
     }
 
+    /// Simulates the inbox process instruction to find the compute units it
+    /// needs, plus a buffer.
+    async fn process_compute_units(
+        &self,
+        payer: &Keypair,
+        inbox_instruction: Instruction,
+    ) -> ChainResult<u32> {
+        // Simulate with the max limit so the default one doesn't get in the way
+        let simulated_units = simulate_instructions_compute_units(
+            &self.rpc(),
+            payer,
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNITS),
+                inbox_instruction,
+            ],
+        )
+        .await?
+        .ok_or_else(|| {
+            ChainCommunicationError::from_other_str(
+                "Simulation of the transaction to process message failed",
+            )
+        })?;
+        let compute_units = u32::try_from(simulated_units)
+            .unwrap_or(MAX_COMPUTE_UNITS)
+            .saturating_mul(100 + COMPUTE_UNITS_BUFFER_PERCENT)
+            / 100;
+        Ok(compute_units.min(MAX_COMPUTE_UNITS))
+    }
+
+    /// Sends a transaction and waits for it to land with the given commitment.
+    /// Returns the signature and whether the transaction executed
+    /// successfully, or `None` if its blockhash expired before it landed.
+    async fn send_and_wait_for_transaction(
+        &self,
+        txn: &Transaction,
+        last_valid_block_height: u64,
+        commitment: CommitmentConfig,
+    ) -> ChainResult<Option<(Signature, bool)>> {
+        let signature = self
+            .rpc()
+            .send_transaction(txn)
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+
+        loop {
+            let status = self
+                .rpc()
+                .get_signature_statuses(&[signature])
+                .await
+                .map_err(ChainCommunicationError::from_other)?
+                .value
+                .into_iter()
+                .next()
+                .flatten();
+            if let Some(status) = status {
+                if status.satisfies_commitment(commitment) {
+                    return Ok(Some((signature, status.err.is_none())));
+                }
+            }
+
+            let block_height = self
+                .rpc()
+                .get_block_height_with_commitment(commitment)
+                .await
+                .map_err(ChainCommunicationError::from_other)?;
+            if block_height > last_valid_block_height {
+                return Ok(None);
+            }
+
+            sleep(TX_STATUS_POLL_INTERVAL).await;
+        }
+    }
+
     async fn get_account_metas_with_instruction_bytes(
         &self,
         program_id: Pubkey,
@@ -353,12 +441,6 @@ impl Mailbox for SealevelMailbox {
             .as_ref()
             .ok_or_else(|| ChainCommunicationError::SignerUnavailable)?;
 
-        let mut instructions = Vec::with_capacity(2);
-        // Set the compute unit limit.
-        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(
-            PROCESS_COMPUTE_UNITS,
-        ));
-
         // "processed" level commitment does not guarantee finality.
         // roughly 5% of blocks end up on a dropped fork.
         // However we don't want this function to be a bottleneck and there already
@@ -434,46 +516,74 @@ impl Mailbox for SealevelMailbox {
             data: ixn_data,
             accounts,
         };
-        instructions.push(inbox_instruction);
-        let (recent_blockhash, _) = self
-            .rpc()
-            .get_latest_blockhash_with_commitment(commitment)
-            .await
-            .map_err(ChainCommunicationError::from_other)?;
 
-        let txn = Transaction::new_signed_with_payer(
-            &instructions,
-            Some(&payer.pubkey()),
-            &[payer],
-            recent_blockhash,
-        );
+        let compute_units = self
+            .process_compute_units(payer, inbox_instruction.clone())
+            .await?;
+        let writable_accounts: Vec<Pubkey> = inbox_instruction
+            .accounts
+            .iter()
+            .filter(|account| account.is_writable)
+            .map(|account| account.pubkey)
+            .collect();
+        let mut priority_fee = self
+            .priority_fee
+            .initial_price(self.rpc(), &writable_accounts, compute_units)
+            .await?;
 
-        tracing::info!(?txn, "Created sealevel transaction to process message");
+        for attempt in 0..=MAX_PROCESS_RESUBMISSIONS {
+            let instructions = [
+                ComputeBudgetInstruction::set_compute_unit_limit(compute_units),
+                ComputeBudgetInstruction::set_compute_unit_price(priority_fee),
+                inbox_instruction.clone(),
+            ];
+            let (recent_blockhash, last_valid_block_height) = self
+                .rpc()
+                .get_latest_blockhash_with_commitment(commitment)
+                .await
+                .map_err(ChainCommunicationError::from_other)?;
+
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &[payer],
+                recent_blockhash,
+            );
 
-        let signature = self
-            .rpc()
-            .send_and_confirm_transaction(&txn)
-            .await
-            .map_err(ChainCommunicationError::from_other)?;
+            tracing::info!(
+                ?txn,
+                compute_units,
+                priority_fee,
+                attempt,
+                "Created sealevel transaction to process message"
+            );
 
-        tracing::info!(?txn, ?signature, "Sealevel transaction sent");
+            let Some((signature, executed)) = self
+                .send_and_wait_for_transaction(&txn, last_valid_block_height, commitment)
+                .await?
+            else {
+                priority_fee = self.priority_fee.bumped_price(priority_fee, compute_units);
+                warn!(
+                    ?txn,
+                    priority_fee, "Sealevel transaction expired before landing, resubmitting"
+                );
+                continue;
+            };
+
+            tracing::info!(?txn, ?signature, executed, "Sealevel transaction landed");
+
+            return Ok(TxOutcome {
+                transaction_id: signature.into(),
+                executed,
+                // TODO use correct data upon integrating IGP support
+                gas_price: U256::zero().try_into()?,
+                gas_used: U256::zero(),
+            });
+        }
 
-        let executed = self
-            .rpc()
-            .confirm_transaction_with_commitment(&signature, commitment)
-            .await
-            .map_err(|err| warn!("Failed to confirm inbox process transaction: {}", err))
-            .map(|ctx| ctx.value)
-            .unwrap_or(false);
-        let txid = signature.into();
-
-        Ok(TxOutcome {
-            transaction_id: txid,
-            executed,
-            // TODO use correct data upon integrating IGP support
-            gas_price: U256::zero().try_into()?,
-            gas_used: U256::zero(),
-        })
+        Err(ChainCommunicationError::from_other_str(
+            "Sealevel transaction to process message did not land before expiring",
+        ))
     }
 
     #[instrument(err, ret, skip(self))]
@@ -500,6 +610,7 @@ impl Mailbox for SealevelMailbox {
 pub struct SealevelMailboxIndexer {
     mailbox: SealevelMailbox,
     program_id: Pubkey,
+    index_transaction_details: bool,
 }
 
 impl SealevelMailboxIndexer {
@@ -509,6 +620,7 @@ impl SealevelMailboxIndexer {
         Ok(Self {
             program_id,
             mailbox,
+            index_transaction_details: conf.index_transaction_details,
         })
     }
 
@@ -528,6 +640,35 @@ impl SealevelMailboxIndexer {
         Ok(height)
     }
 
+    /// Finds the signature of the transaction that created a message storage
+    /// PDA at `slot`, which is the transaction that dispatched or processed the
+    /// message.
+    async fn get_storage_pda_signature(
+        &self,
+        storage_pda: &Pubkey,
+        slot: u64,
+    ) -> ChainResult<H512> {
+        let signatures = self
+            .rpc()
+            .get_signatures_for_address_with_config(
+                storage_pda,
+                GetConfirmedSignaturesForAddress2Config {
+                    commitment: Some(CommitmentConfig::finalized()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        let signature = signatures.iter().find(|s| s.slot == slot).ok_or_else(|| {
+            ChainCommunicationError::from_other_str(
+                "Could not find the transaction that created the message storage PDA",
+            )
+        })?;
+        let signature = Signature::from_str(&signature.signature)
+            .map_err(ChainCommunicationError::from_other)?;
+        Ok(H512::from_slice(signature.as_ref()))
+    }
+
     async fn get_message_with_nonce(
         &self,
         nonce: u32,
@@ -619,18 +760,112 @@ impl SealevelMailboxIndexer {
 
         Ok((
             hyperlane_message.into(),
-            LogMeta {
-                address: self.mailbox.program_id.to_bytes().into(),
-                block_number: dispatched_message_account.slot,
-                // TODO: get these when building out scraper support.
-                // It's inconvenient to get these :|
-                block_hash: H256::zero(),
-                transaction_id: H512::zero(),
-                transaction_index: 0,
-                log_index: U256::zero(),
+            self.log_meta(
+                &valid_message_storage_pda_pubkey,
+                dispatched_message_account.slot,
+            )
+            .await?,
+        ))
+    }
+
+    async fn get_delivered_message_with_sequence(
+        &self,
+        sequence: u32,
+    ) -> ChainResult<(Indexed<H256>, LogMeta)> {
+        let target_message_account_bytes = &[
+            &hyperlane_sealevel_mailbox::accounts::PROCESSED_MESSAGE_DISCRIMINATOR[..],
+            &(sequence as u64).to_le_bytes()[..],
+        ]
+        .concat();
+        let target_message_account_bytes = base64::encode(target_message_account_bytes);
+
+        // Like dispatched messages, only request the `message_id` field of
+        // the matching accounts, which is needed to validate their pubkeys.
+        let memcmp = RpcFilterType::Memcmp(Memcmp {
+            // Ignore the first byte, which is the `initialized` bool flag.
+            offset: 1,
+            bytes: MemcmpEncodedBytes::Base64(target_message_account_bytes),
+            encoding: None,
+        });
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![memcmp]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                data_slice: Some(UiDataSliceConfig {
+                    offset: 1 + 8 + 8, // the offset to get the `message_id` field
+                    length: 32,        // the length of the `message_id` field
+                }),
+                commitment: Some(CommitmentConfig::finalized()),
+                min_context_slot: None,
             },
+            with_context: Some(false),
+        };
+        let accounts = self
+            .rpc()
+            .get_program_accounts_with_config(&self.mailbox.program_id, config)
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+
+        let valid_message_storage_pda_pubkey = accounts
+            .into_iter()
+            .find_map(|(pubkey, account)| {
+                let message_id = H256::from_slice(&account.data);
+                let (expected_pubkey, _bump) = Pubkey::try_find_program_address(
+                    mailbox_processed_message_pda_seeds!(message_id),
+                    &self.mailbox.program_id,
+                )?;
+                (expected_pubkey == pubkey).then_some(pubkey)
+            })
+            .ok_or_else(|| {
+                ChainCommunicationError::from_other_str(
+                    "Could not find valid processed message storage PDA pubkey",
+                )
+            })?;
+
+        let account = self
+            .rpc()
+            .get_account_with_commitment(
+                &valid_message_storage_pda_pubkey,
+                CommitmentConfig::finalized(),
+            )
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value
+            .ok_or_else(|| {
+                ChainCommunicationError::from_other_str("Could not find account data")
+            })?;
+        let processed_message_account = ProcessedMessageAccount::fetch(&mut account.data.as_ref())
+            .map_err(ChainCommunicationError::from_other)?
+            .into_inner();
+
+        Ok((
+            Indexed::new(processed_message_account.message_id).with_sequence(sequence),
+            self.log_meta(
+                &valid_message_storage_pda_pubkey,
+                processed_message_account.slot,
+            )
+            .await?,
         ))
     }
+
+    async fn log_meta(&self, storage_pda: &Pubkey, slot: u64) -> ChainResult<LogMeta> {
+        let (block_hash, transaction_id) = if self.index_transaction_details {
+            let block_info = get_block_info(self.rpc(), slot).await?;
+            let transaction_id = self.get_storage_pda_signature(storage_pda, slot).await?;
+            (block_info.hash, transaction_id)
+        } else {
+            (H256::zero(), H512::zero())
+        };
+
+        Ok(LogMeta {
+            address: self.mailbox.program_id.to_bytes().into(),
+            block_number: slot,
+            block_hash,
+            transaction_id,
+            transaction_index: 0,
+            log_index: U256::zero(),
+        })
+    }
 }
 
 #[async_trait]
@@ -672,9 +907,16 @@ impl Indexer<HyperlaneMessage> for SealevelMailboxIndexer {
 impl Indexer<H256> for SealevelMailboxIndexer {
     async fn fetch_logs(
         &self,
-        _range: RangeInclusive<u32>,
+        range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
-        todo!()
+        info!(?range, "Fetching SealevelMailboxIndexer H256 delivery logs");
+
+        let message_capacity = range.end().saturating_sub(*range.start());
+        let mut message_ids = Vec::with_capacity(message_capacity as usize);
+        for sequence in range {
+            message_ids.push(self.get_delivered_message_with_sequence(sequence).await?);
+        }
+        Ok(message_ids)
     }
 
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
@@ -684,11 +926,26 @@ impl Indexer<H256> for SealevelMailboxIndexer {
 
 #[async_trait]
 impl SequenceAwareIndexer<H256> for SealevelMailboxIndexer {
+    #[instrument(err, skip(self))]
     async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
-        // TODO: implement when sealevel scraper support is implemented
-        info!("Message delivery indexing not implemented");
         let tip = Indexer::<H256>::get_finalized_block_number(self).await?;
-        Ok((Some(1), tip))
+        let inbox_account = self
+            .rpc()
+            .get_account_with_commitment(&self.mailbox.inbox.0, CommitmentConfig::finalized())
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value
+            .ok_or_else(|| {
+                ChainCommunicationError::from_other_str("Could not find inbox account")
+            })?;
+        let inbox = InboxAccount::fetch(&mut inbox_account.data.as_ref())
+            .map_err(ChainCommunicationError::from_other)?
+            .into_inner();
+        let count = inbox
+            .processed_count
+            .try_into()
+            .map_err(ChainCommunicationError::from_other)?;
+        Ok((Some(count), tip))
     }
 }
 
@@ -0,0 +1,140 @@
+use hyperlane_core::{ChainCommunicationError, ChainResult};
+use serde::Deserialize;
+use serde_json::json;
+use solana_client::rpc_request::RpcRequest;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::RpcClientWithDebug;
+
+const MICRO_LAMPORTS_PER_LAMPORT: u64 = 1_000_000;
+
+/// How the priority fee paid by a transaction is chosen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PriorityFeeStrategy {
+    /// Always pay the given price, in micro-lamports per compute unit
+    Fixed(u64),
+    /// Pay the given percentile (0-100) of the prices recently paid to
+    /// write-lock the accounts used by the transaction
+    Percentile(u8),
+}
+
+impl Default for PriorityFeeStrategy {
+    fn default() -> Self {
+        Self::Fixed(0)
+    }
+}
+
+/// Priority fee configuration for submitted transactions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriorityFeeConfig {
+    /// How the initial priority fee is chosen
+    pub strategy: PriorityFeeStrategy,
+    /// The most a single transaction may pay in priority fees, in lamports
+    pub max_lamports_per_tx: Option<u64>,
+    /// How much to raise the price by, in percent, each time a transaction
+    /// expires before it lands
+    pub bump_percent: u64,
+}
+
+impl Default for PriorityFeeConfig {
+    fn default() -> Self {
+        Self {
+            strategy: Default::default(),
+            max_lamports_per_tx: None,
+            bump_percent: 50,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcPrioritizationFee {
+    prioritization_fee: u64,
+}
+
+impl PriorityFeeConfig {
+    /// The initial price to pay for a transaction that write-locks
+    /// `writable_accounts`, in micro-lamports per compute unit
+    pub async fn initial_price(
+        &self,
+        rpc_client: &RpcClientWithDebug,
+        writable_accounts: &[Pubkey],
+        compute_units: u32,
+    ) -> ChainResult<u64> {
+        let price = match self.strategy {
+            PriorityFeeStrategy::Fixed(price) => price,
+            PriorityFeeStrategy::Percentile(percentile) => {
+                let addresses: Vec<String> =
+                    writable_accounts.iter().map(Pubkey::to_string).collect();
+                let fees: Vec<RpcPrioritizationFee> = rpc_client
+                    .send(
+                        RpcRequest::Custom {
+                            method: "getRecentPrioritizationFees",
+                        },
+                        json!([addresses]),
+                    )
+                    .await
+                    .map_err(ChainCommunicationError::from_other)?;
+                percentile_of(
+                    fees.into_iter().map(|fee| fee.prioritization_fee).collect(),
+                    percentile,
+                )
+            }
+        };
+        Ok(self.capped(price, compute_units))
+    }
+
+    /// The price to pay after a transaction paying `price` expired
+    pub fn bumped_price(&self, price: u64, compute_units: u32) -> u64 {
+        // Make sure a zero price still increases
+        let bumped = price
+            .saturating_mul(100 + self.bump_percent)
+            .saturating_div(100)
+            .max(price.saturating_add(1));
+        self.capped(bumped, compute_units)
+    }
+
+    fn capped(&self, price: u64, compute_units: u32) -> u64 {
+        match self.max_lamports_per_tx {
+            Some(max_lamports) if compute_units > 0 => price.min(
+                max_lamports.saturating_mul(MICRO_LAMPORTS_PER_LAMPORT) / u64::from(compute_units),
+            ),
+            _ => price,
+        }
+    }
+}
+
+fn percentile_of(mut fees: Vec<u64>, percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    fees.sort_unstable();
+    let index = (fees.len() - 1) * usize::from(percentile.min(100)) / 100;
+    fees[index]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_percentile_of() {
+        assert_eq!(percentile_of(vec![], 50), 0);
+        assert_eq!(percentile_of(vec![5, 1, 3, 2, 4], 0), 1);
+        assert_eq!(percentile_of(vec![5, 1, 3, 2, 4], 50), 3);
+        assert_eq!(percentile_of(vec![5, 1, 3, 2, 4], 100), 5);
+    }
+
+    #[test]
+    fn test_bumped_price_is_capped() {
+        let config = PriorityFeeConfig {
+            strategy: PriorityFeeStrategy::Fixed(0),
+            // 1000 micro-lamports per compute unit for 1000 compute units
+            max_lamports_per_tx: Some(1),
+            bump_percent: 50,
+        };
+        assert_eq!(config.bumped_price(0, 1000), 1);
+        assert_eq!(config.bumped_price(100, 1000), 150);
+        assert_eq!(config.bumped_price(900, 1000), 1000);
+    }
+}
@@ -3,12 +3,17 @@ use std::{str::FromStr, sync::Arc};
 use async_trait::async_trait;
 
 use hyperlane_core::{
-    BlockInfo, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain, HyperlaneProvider, TxnInfo,
-    H256, U256,
+    BlockInfo, ChainCommunicationError, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain,
+    HyperlaneProvider, TxnInfo, TxnReceiptInfo, H256, H512, U256,
 };
-use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::UiTransactionEncoding;
 
-use crate::{client::RpcClientWithDebug, error::HyperlaneSealevelError, ConnectionConf};
+use crate::{
+    client::RpcClientWithDebug, error::HyperlaneSealevelError, utils::get_block_info,
+    ConnectionConf,
+};
 
 /// A wrapper around a Sealevel provider to get generic blockchain information.
 #[derive(Debug)]
@@ -22,7 +27,8 @@ impl SealevelProvider {
     pub fn new(domain: HyperlaneDomain, conf: &ConnectionConf) -> Self {
         // Set the `processed` commitment at rpc level
         let rpc_client = Arc::new(RpcClientWithDebug::new_with_commitment(
-            conf.url.to_string(),
+            conf,
+            &domain,
             CommitmentConfig::processed(),
         ));
 
@@ -62,16 +68,78 @@ impl HyperlaneChain for SealevelProvider {
 #[async_trait]
 impl HyperlaneProvider for SealevelProvider {
     async fn get_block_by_hash(&self, _hash: &H256) -> ChainResult<BlockInfo> {
-        todo!() // FIXME
+        // The Solana RPC has no way to look up a block by its hash
+        Err(ChainCommunicationError::from_other_str(
+            "Sealevel blocks can only be looked up by slot",
+        ))
     }
 
-    async fn get_txn_by_hash(&self, _hash: &H256) -> ChainResult<TxnInfo> {
-        todo!() // FIXME
+    async fn get_block_by_height(&self, height: u64) -> ChainResult<BlockInfo> {
+        get_block_info(&self.rpc_client, height).await
     }
 
-    async fn is_contract(&self, _address: &H256) -> ChainResult<bool> {
-        // FIXME
-        Ok(true)
+    /// Solana charges a fee per signature rather than per unit of gas, so the
+    /// fee in lamports is reported as the gas used, at a gas price of 1.
+    /// There are no nonces either, so the nonce is always 0.
+    async fn get_txn_by_hash(&self, hash: &H512) -> ChainResult<TxnInfo> {
+        let signature = Signature::new(hash.as_bytes());
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::finalized()),
+            max_supported_transaction_version: Some(0),
+        };
+        let txn = self
+            .rpc_client
+            .get_transaction_with_config(&signature, config)
+            .await
+            .map_err(Into::<HyperlaneSealevelError>::into)?;
+        let decoded = txn.transaction.transaction.decode().ok_or_else(|| {
+            ChainCommunicationError::from_other_str("Could not decode Sealevel transaction")
+        })?;
+
+        let account_keys = decoded.message.static_account_keys();
+        // The fee payer always comes first
+        let sender = account_keys.first().ok_or_else(|| {
+            ChainCommunicationError::from_other_str("Sealevel transaction has no fee payer")
+        })?;
+        // Compute budget instructions come first, so the program that was
+        // interacted with is the one called last
+        let recipient = decoded
+            .message
+            .instructions()
+            .last()
+            .and_then(|instruction| account_keys.get(instruction.program_id_index as usize));
+        let fee = txn.transaction.meta.as_ref().map(|meta| meta.fee);
+
+        Ok(TxnInfo {
+            hash: *hash,
+            gas_limit: fee.unwrap_or_default().into(),
+            max_priority_fee_per_gas: None,
+            max_fee_per_gas: None,
+            gas_price: Some(U256::one()),
+            nonce: 0,
+            sender: H256::from(sender.to_bytes()),
+            recipient: recipient.map(|recipient| H256::from(recipient.to_bytes())),
+            receipt: fee.map(|fee| TxnReceiptInfo {
+                gas_used: fee.into(),
+                cumulative_gas_used: fee.into(),
+                effective_gas_price: Some(U256::one()),
+            }),
+        })
+    }
+
+    async fn is_contract(&self, address: &H256) -> ChainResult<bool> {
+        // Programs are the only executable accounts
+        let account = self
+            .rpc_client
+            .get_account_with_commitment(
+                &Pubkey::new_from_array(address.to_fixed_bytes()),
+                CommitmentConfig::finalized(),
+            )
+            .await
+            .map_err(Into::<HyperlaneSealevelError>::into)?
+            .value;
+        Ok(account.map_or(false, |account| account.executable))
     }
 
     async fn get_balance(&self, address: String) -> ChainResult<U256> {
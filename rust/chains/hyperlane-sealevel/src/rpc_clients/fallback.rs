@@ -0,0 +1,107 @@
+use std::fmt::{Debug, Formatter};
+
+use async_trait::async_trait;
+use hyperlane_core::{rpc_clients::FallbackProvider, ChainCommunicationError};
+use serde_json::Value;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    rpc_custom_error::JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY,
+    rpc_request::{RpcError, RpcRequest},
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+
+use super::SealevelRpcSender;
+
+/// An `RpcSender` that sends each request to the highest priority endpoint
+/// that is healthy, falling back to the next one on transport errors.
+///
+/// Errors returned by a healthy node (e.g. a failed simulation) are
+/// deterministic, so they are returned as-is rather than retried elsewhere.
+pub struct SealevelFallbackRpcSender {
+    fallback_provider: FallbackProvider<SealevelRpcSender, SealevelRpcSender>,
+}
+
+impl SealevelFallbackRpcSender {
+    /// Create a new fallback sender over `senders`, in order of priority
+    pub fn new(senders: impl IntoIterator<Item = SealevelRpcSender>) -> Self {
+        Self {
+            fallback_provider: FallbackProvider::new(senders),
+        }
+    }
+
+    /// Whether an error means the endpoint, rather than the request, is at fault
+    fn should_fallback(err: &ClientError) -> bool {
+        match err.kind() {
+            ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) => true,
+            ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => {
+                *code == JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Debug for SealevelFallbackRpcSender {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.fallback_provider.fmt(f)
+    }
+}
+
+#[async_trait]
+impl RpcSender for SealevelFallbackRpcSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        self.fallback_provider
+            .call(move |provider| {
+                let params = params.clone();
+                let future = async move {
+                    match provider.send(request, params).await {
+                        Err(err) if Self::should_fallback(&err) => {
+                            Err(ChainCommunicationError::from_other(err))
+                        }
+                        result => Ok(result),
+                    }
+                };
+                Box::pin(future)
+            })
+            .await
+            .map_err(|err| ClientError::from(ClientErrorKind::Custom(err.to_string())))?
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        SealevelRpcSender::combined_transport_stats(&self.fallback_provider.inner.providers)
+    }
+
+    fn url(&self) -> String {
+        SealevelRpcSender::joined_urls(&self.fallback_provider.inner.providers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_client::rpc_request::RpcResponseErrorData;
+
+    use super::*;
+
+    fn response_error(code: i64) -> ClientError {
+        ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            code,
+            message: "error".to_owned(),
+            data: RpcResponseErrorData::Empty,
+        })
+        .into()
+    }
+
+    #[test]
+    fn test_should_fallback() {
+        assert!(SealevelFallbackRpcSender::should_fallback(&response_error(
+            JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY
+        )));
+        // e.g. a transaction that failed simulation
+        assert!(!SealevelFallbackRpcSender::should_fallback(
+            &response_error(-32002)
+        ));
+        assert!(!SealevelFallbackRpcSender::should_fallback(
+            &ClientErrorKind::Custom("error".to_owned()).into()
+        ));
+    }
+}
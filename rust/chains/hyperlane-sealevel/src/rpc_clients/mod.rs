@@ -0,0 +1,5 @@
+pub use self::{fallback::*, quorum::*, sender::*};
+
+mod fallback;
+mod quorum;
+mod sender;
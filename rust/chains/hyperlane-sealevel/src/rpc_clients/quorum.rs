@@ -0,0 +1,200 @@
+use std::fmt::{Debug, Formatter};
+
+use async_trait::async_trait;
+use hyperlane_core::{rpc_clients::QuorumProvider, ChainCommunicationError};
+use serde_json::Value;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    rpc_request::RpcRequest,
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+
+use super::{SealevelFallbackRpcSender, SealevelRpcSender};
+
+/// An `RpcSender` that sends each request to every endpoint and returns the
+/// response that a majority of them agree on.
+///
+/// Requests whose result depends on the slot an endpoint is at (e.g. the
+/// latest blockhash) can't be expected to agree, so they are sent to a single
+/// endpoint instead.
+pub struct SealevelQuorumRpcSender {
+    quorum_provider: QuorumProvider<SealevelRpcSender>,
+    fallback: SealevelFallbackRpcSender,
+}
+
+impl SealevelQuorumRpcSender {
+    /// Create a new quorum sender over `senders`
+    pub fn new(senders: impl IntoIterator<Item = SealevelRpcSender>) -> Self {
+        let senders: Vec<_> = senders.into_iter().collect();
+        Self {
+            fallback: SealevelFallbackRpcSender::new(senders.clone()),
+            quorum_provider: QuorumProvider::new(senders),
+        }
+    }
+
+    /// Whether endpoints at different slots return different results for
+    /// `request`
+    fn is_slot_dependent(request: RpcRequest) -> bool {
+        matches!(
+            request,
+            RpcRequest::GetLatestBlockhash
+                | RpcRequest::GetRecentPrioritizationFees
+                | RpcRequest::GetSignatureStatuses
+                | RpcRequest::SimulateTransaction
+        )
+    }
+
+    /// Responses that include the slot they were read at are compared by
+    /// their value only, since endpoints are rarely at the same slot.
+    fn comparable_response(response: &Value) -> &Value {
+        match (response.get("context"), response.get("value")) {
+            (Some(_), Some(value)) => value,
+            _ => response,
+        }
+    }
+}
+
+impl Debug for SealevelQuorumRpcSender {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.quorum_provider.fmt(f)
+    }
+}
+
+#[async_trait]
+impl RpcSender for SealevelQuorumRpcSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        if Self::is_slot_dependent(request) {
+            return self.fallback.send(request, params).await;
+        }
+        let results = self
+            .quorum_provider
+            .call_all(move |provider| {
+                let params = params.clone();
+                let future = async move {
+                    provider
+                        .send(request, params)
+                        .await
+                        .map_err(ChainCommunicationError::from_other)
+                };
+                Box::pin(future)
+            })
+            .await;
+        let response = match request {
+            RpcRequest::GetBlockHeight | RpcRequest::GetSlot => {
+                let heights = results
+                    .into_iter()
+                    .map(|result| {
+                        serde_json::from_value(result?).map_err(ChainCommunicationError::from_other)
+                    })
+                    .collect();
+                self.quorum_provider.agreed_height(heights).map(Value::from)
+            }
+            _ => self.quorum_provider.agreed_response(results, |response| {
+                Self::comparable_response(response).clone()
+            }),
+        };
+        response.map_err(|err| ClientError::from(ClientErrorKind::Custom(err.to_string())))
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        SealevelRpcSender::combined_transport_stats(self.quorum_provider.providers())
+    }
+
+    fn url(&self) -> String {
+        SealevelRpcSender::joined_urls(self.quorum_provider.providers())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use serde_json::json;
+    use url::Url;
+
+    use super::*;
+
+    /// Serve `result` to every JSON-RPC request sent to the returned url
+    fn serve(result: Value) -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+                let mut request = vec![0; content_length];
+                reader.read_exact(&mut request).unwrap();
+                let body = json!({"jsonrpc": "2.0", "result": result, "id": 1}).to_string();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        url.parse().unwrap()
+    }
+
+    fn blockhash_response(slot: u64, blockhash: &str) -> Value {
+        json!({
+            "context": {"slot": slot},
+            "value": {"blockhash": blockhash, "lastValidBlockHeight": slot + 150}
+        })
+    }
+
+    #[tokio::test]
+    async fn test_slot_dependent_requests_do_not_need_agreement() {
+        let first = blockhash_response(10, "4sGjMW1sUnHzSxGspuhpqLDx6wiyjNtZAMdL4VZHirAn");
+        let second = blockhash_response(11, "8tvdxEJvAk4WobSqGsv1T4uJLYmLjjdmVXxuawaVK4Ac");
+        let sender = SealevelQuorumRpcSender::new([
+            SealevelRpcSender::new(serve(first.clone()), "test", None),
+            SealevelRpcSender::new(serve(second), "test", None),
+        ]);
+
+        // the blockhash comes from the highest priority endpoint
+        let response = sender
+            .send(RpcRequest::GetLatestBlockhash, json!([]))
+            .await
+            .unwrap();
+        assert_eq!(response, first);
+
+        // the same disagreement fails quorum for slot-independent requests
+        assert!(sender
+            .send(RpcRequest::GetAccountInfo, json!([]))
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_comparable_response_ignores_context() {
+        let response = json!({"context": {"slot": 10}, "value": {"lamports": 5}});
+        assert_eq!(
+            SealevelQuorumRpcSender::comparable_response(&response),
+            &json!({"lamports": 5})
+        );
+        let response = json!({"lamports": 5});
+        assert_eq!(
+            SealevelQuorumRpcSender::comparable_response(&response),
+            &response
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    fmt::{Debug, Formatter},
+    sync::Arc,
+    time::Instant,
+};
+
+use async_trait::async_trait;
+use ethers_prometheus::json_rpc_client::{
+    ChainInfo, JsonRpcClientMetrics, NodeInfo, PrometheusJsonRpcClientConfig,
+};
+use hyperlane_core::{rpc_clients::BlockNumberGetter, ChainCommunicationError};
+use itertools::Itertools;
+use serde_json::{json, Value};
+use solana_client::{
+    client_error::Result as ClientResult,
+    http_sender::HttpSender,
+    rpc_request::RpcRequest,
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+use url::Url;
+
+/// A JSON-RPC sender connected to a single Sealevel RPC endpoint
+#[derive(Clone)]
+pub struct SealevelRpcSender {
+    sender: Arc<HttpSender>,
+    url: Url,
+    metrics: Option<JsonRpcClientMetrics>,
+    metrics_config: PrometheusJsonRpcClientConfig,
+}
+
+impl SealevelRpcSender {
+    /// Create a new sender for the given url, recording its requests in
+    /// `metrics` if set
+    pub fn new(url: Url, chain_name: &str, metrics: Option<JsonRpcClientMetrics>) -> Self {
+        let metrics_config = PrometheusJsonRpcClientConfig {
+            node: Some(NodeInfo {
+                host: url.host_str().map(str::to_owned),
+            }),
+            chain: Some(ChainInfo {
+                name: Some(chain_name.to_owned()),
+            }),
+        };
+        Self {
+            sender: Arc::new(HttpSender::new(url.to_string())),
+            url,
+            metrics,
+            metrics_config,
+        }
+    }
+
+    /// Send a request to this endpoint
+    pub async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let start = Instant::now();
+        let result = self.sender.send(request, params).await;
+        if let Some(metrics) = &self.metrics {
+            metrics.increment(
+                &self.metrics_config,
+                &request.to_string(),
+                start,
+                result.is_ok(),
+            );
+        }
+        result
+    }
+
+    /// The transport stats of all `senders` added together
+    pub fn combined_transport_stats<'a>(
+        senders: impl IntoIterator<Item = &'a Self>,
+    ) -> RpcTransportStats {
+        senders
+            .into_iter()
+            .map(|sender| sender.sender.get_transport_stats())
+            .fold(RpcTransportStats::default(), |mut acc, stats| {
+                acc.request_count += stats.request_count;
+                acc.elapsed_time += stats.elapsed_time;
+                acc.rate_limited_time += stats.rate_limited_time;
+                acc
+            })
+    }
+
+    /// The urls of all `senders`
+    pub fn joined_urls<'a>(senders: impl IntoIterator<Item = &'a Self>) -> String {
+        senders
+            .into_iter()
+            .map(|sender| sender.url.as_str())
+            .join(", ")
+    }
+}
+
+impl Debug for SealevelRpcSender {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SealevelRpcSender")
+            .field("url", &self.url.as_str())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl BlockNumberGetter for SealevelRpcSender {
+    async fn get_block_number(&self) -> Result<u64, ChainCommunicationError> {
+        let response = self
+            .send(RpcRequest::GetBlockHeight, json!([]))
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        serde_json::from_value(response).map_err(ChainCommunicationError::from_other)
+    }
+}
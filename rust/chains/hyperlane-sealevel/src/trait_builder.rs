@@ -1,13 +1,29 @@
-use hyperlane_core::{config::OperationBatchConfig, ChainCommunicationError};
+use ethers_prometheus::json_rpc_client::JsonRpcClientMetrics;
+use hyperlane_core::{
+    config::OperationBatchConfig, rpc_clients::RpcConsensusType, ChainCommunicationError,
+};
 use url::Url;
 
+use crate::PriorityFeeConfig;
+
 /// Sealevel connection configuration
 #[derive(Debug, Clone)]
 pub struct ConnectionConf {
-    /// Fully qualified string to connect to
-    pub url: Url,
+    /// Fully qualified urls to connect to, in order of priority
+    pub urls: Vec<Url>,
+    /// How requests are spread over `urls`
+    pub rpc_consensus_type: RpcConsensusType,
     /// Operation batching configuration
     pub operation_batch: OperationBatchConfig,
+    /// Priority fee configuration for submitted transactions
+    pub priority_fee: PriorityFeeConfig,
+    /// Whether indexers look up the block hash and transaction id of every
+    /// log. This costs extra RPC calls per log, so only agents that store
+    /// them enable it.
+    pub index_transaction_details: bool,
+    /// Metrics recorded for the requests to each url. These are set by the
+    /// agent when building chain clients rather than parsed from config.
+    pub rpc_metrics: Option<JsonRpcClientMetrics>,
 }
 
 /// An error type when parsing a connection configuration.
@@ -1,17 +1,24 @@
+use std::str::FromStr;
+
 use base64::Engine;
 use borsh::{BorshDeserialize, BorshSerialize};
-use hyperlane_core::{ChainCommunicationError, ChainResult};
+use hyperlane_core::{BlockInfo, ChainCommunicationError, ChainResult, H256};
 
 use serializable_account_meta::{SerializableAccountMeta, SimulationReturnData};
-use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient, rpc_config::RpcBlockConfig,
+    rpc_response::RpcSimulateTransactionResult,
+};
 use solana_sdk::{
     commitment_config::CommitmentConfig,
+    hash::Hash,
     instruction::{AccountMeta, Instruction},
     message::Message,
     signature::{Keypair, Signer},
     transaction::Transaction,
 };
-use solana_transaction_status::UiReturnDataEncoding;
+use solana_transaction_status::{TransactionDetails, UiReturnDataEncoding};
+use tracing::debug;
 
 use crate::client::RpcClientWithDebug;
 
@@ -24,20 +31,8 @@ pub async fn simulate_instruction<T: BorshDeserialize + BorshSerialize>(
     payer: &Keypair,
     instruction: Instruction,
 ) -> ChainResult<Option<T>> {
-    let commitment = CommitmentConfig::finalized();
-    let (recent_blockhash, _) = rpc_client
-        .get_latest_blockhash_with_commitment(commitment)
-        .await
-        .map_err(ChainCommunicationError::from_other)?;
-    let return_data = rpc_client
-        .simulate_transaction(&Transaction::new_unsigned(Message::new_with_blockhash(
-            &[instruction],
-            Some(&payer.pubkey()),
-            &recent_blockhash,
-        )))
-        .await
-        .map_err(ChainCommunicationError::from_other)?
-        .value
+    let return_data = simulate_transaction(rpc_client, payer, &[instruction])
+        .await?
         .return_data;
 
     if let Some(return_data) = return_data {
@@ -56,6 +51,43 @@ pub async fn simulate_instruction<T: BorshDeserialize + BorshSerialize>(
     Ok(None)
 }
 
+/// Simulates a transaction made of `instructions`, returning the compute
+/// units it consumed. If the simulated transaction failed, returns Ok(None).
+pub async fn simulate_instructions_compute_units(
+    rpc_client: &RpcClient,
+    payer: &Keypair,
+    instructions: &[Instruction],
+) -> ChainResult<Option<u64>> {
+    let result = simulate_transaction(rpc_client, payer, instructions).await?;
+    if let Some(err) = result.err {
+        debug!(?err, logs = ?result.logs, "Simulated transaction failed");
+        return Ok(None);
+    }
+    Ok(Some(result.units_consumed.unwrap_or_default()))
+}
+
+async fn simulate_transaction(
+    rpc_client: &RpcClient,
+    payer: &Keypair,
+    instructions: &[Instruction],
+) -> ChainResult<RpcSimulateTransactionResult> {
+    let commitment = CommitmentConfig::finalized();
+    let (recent_blockhash, _) = rpc_client
+        .get_latest_blockhash_with_commitment(commitment)
+        .await
+        .map_err(ChainCommunicationError::from_other)?;
+    let result = rpc_client
+        .simulate_transaction(&Transaction::new_unsigned(Message::new_with_blockhash(
+            instructions,
+            Some(&payer.pubkey()),
+            &recent_blockhash,
+        )))
+        .await
+        .map_err(ChainCommunicationError::from_other)?
+        .value;
+    Ok(result)
+}
+
 /// Simulates an Instruction that will return a list of AccountMetas.
 pub async fn get_account_metas(
     rpc_client: &RpcClient,
@@ -91,3 +123,25 @@ pub async fn get_finalized_block_number(rpc_client: &RpcClientWithDebug) -> Chai
         .expect("sealevel block height exceeds u32::MAX");
     Ok(height)
 }
+
+/// Gets the hash and time of the block produced in `slot`.
+pub async fn get_block_info(rpc_client: &RpcClient, slot: u64) -> ChainResult<BlockInfo> {
+    let config = RpcBlockConfig {
+        // We only need the block's hash and time, not its transactions
+        transaction_details: Some(TransactionDetails::None),
+        rewards: Some(false),
+        commitment: Some(CommitmentConfig::finalized()),
+        max_supported_transaction_version: Some(0),
+        ..Default::default()
+    };
+    let block = rpc_client
+        .get_block_with_config(slot, config)
+        .await
+        .map_err(ChainCommunicationError::from_other)?;
+    let hash = Hash::from_str(&block.blockhash).map_err(ChainCommunicationError::from_other)?;
+    Ok(BlockInfo {
+        hash: H256::from(hash.to_bytes()),
+        timestamp: block.block_time.unwrap_or_default() as u64,
+        number: slot,
+    })
+}
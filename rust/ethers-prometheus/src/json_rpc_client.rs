@@ -53,6 +53,41 @@ pub struct JsonRpcClientMetrics {
     request_duration_seconds: Option<CounterVec>,
 }
 
+impl JsonRpcClientMetrics {
+    /// Record a request to the node described by `config` that started at
+    /// `start`. Clients that aren't an ethers `JsonRpcClient` use this to
+    /// record their requests in the same metrics.
+    pub fn increment(
+        &self,
+        config: &PrometheusJsonRpcClientConfig,
+        method: &str,
+        start: Instant,
+        success: bool,
+    ) {
+        let labels = hashmap! {
+            "provider_node" => config.node_host(),
+            "chain" => config.chain_name(),
+            "method" => method,
+            "status" => if success { "success" } else { "failure" }
+        };
+        if let Some(counter) = &self.request_count {
+            counter.with(&labels).inc()
+        }
+        if let Some(counter) = &self.request_duration_seconds {
+            counter
+                .with(&labels)
+                .inc_by((Instant::now() - start).as_secs_f64())
+        };
+    }
+}
+
+impl Debug for JsonRpcClientMetrics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonRpcClientMetrics")
+            .finish_non_exhaustive()
+    }
+}
+
 /// Expected label names for the metric.
 pub const REQUEST_COUNT_LABELS: &[&str] = &["provider_node", "chain", "method", "status"];
 /// Help string for the metric.
@@ -168,20 +203,8 @@ where
     {
         let start = Instant::now();
         let res = self.inner.request(method, params).await;
-        let labels = hashmap! {
-            "provider_node" => self.config.node_host(),
-            "chain" => self.config.chain_name(),
-            "method" => method,
-            "status" => if res.is_ok() { "success" } else { "failure" }
-        };
-        if let Some(counter) = &self.metrics.request_count {
-            counter.with(&labels).inc()
-        }
-        if let Some(counter) = &self.metrics.request_duration_seconds {
-            counter
-                .with(&labels)
-                .inc_by((Instant::now() - start).as_secs_f64())
-        };
+        self.metrics
+            .increment(&self.config, method, start, res.is_ok());
         res
     }
 }
@@ -4,7 +4,7 @@
 use std::clone::Clone;
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
 use async_trait::async_trait;
@@ -110,6 +110,12 @@ pub const TRANSACTION_SEND_TOTAL_LABELS: &[&str] =
 /// Help string for the metric.
 pub const TRANSACTION_SEND_TOTAL_HELP: &str = "Number of transactions sent";
 
+/// Expected label names for the `transaction_replacement_total` metric.
+pub const TRANSACTION_REPLACEMENT_TOTAL_LABELS: &[&str] = &["chain", "address_from", "address_to"];
+/// Help string for the metric.
+pub const TRANSACTION_REPLACEMENT_TOTAL_HELP: &str =
+    "Number of pending transactions replaced by one with the same nonce";
+
 /// Container for all the relevant middleware metrics.
 #[derive(Clone, Builder)]
 pub struct MiddlewareMetrics {
@@ -175,6 +181,15 @@ pub struct MiddlewareMetrics {
     /// - `txn_status`: `dispatched`, `completed`, or `failed`
     #[builder(setter(into, strip_option), default)]
     transaction_send_total: Option<IntCounterVec>,
+
+    /// Number of pending transactions replaced by one with the same nonce,
+    /// usually to pay higher fees.
+    /// - `chain`: the chain name (or chain ID if the name is unknown) of the
+    ///   chain the tx occurred on.
+    /// - `address_from`: source address of the transaction.
+    /// - `address_to`: destination address of the transaction.
+    #[builder(setter(into, strip_option), default)]
+    transaction_replacement_total: Option<IntCounterVec>,
     // /// Gas spent on completed transactions.
     // /// - `chain`: the chain name (or ID if the name is unknown) of the chain the tx occurred
     // on. /// - `address_from`: source address of the transaction.
@@ -191,6 +206,9 @@ pub struct PrometheusMiddleware<M> {
     inner: Arc<M>,
     metrics: MiddlewareMetrics,
     conf: Arc<RwLock<PrometheusMiddlewareConf>>,
+    /// The last nonce each address sent a transaction with, used to tell
+    /// replacements apart from new transactions.
+    last_nonces: Arc<Mutex<HashMap<Address, U256>>>,
 }
 
 /// Configuration for the prometheus middleware. This can be loaded via serde.
@@ -253,6 +271,26 @@ impl<M: Middleware> Middleware for PrometheusMiddleware<M> {
             })
             .inc()
         }
+        if let (Some(m), Some(from), Some(nonce)) = (
+            &self.metrics.transaction_replacement_total,
+            tx.from(),
+            tx.nonce(),
+        ) {
+            let is_replacement = self
+                .last_nonces
+                .lock()
+                .expect("lock poisoned")
+                .insert(*from, *nonce)
+                == Some(*nonce);
+            if is_replacement {
+                m.with(&hashmap! {
+                    "chain" => chain.as_str(),
+                    "address_from" => addr_from.as_str(),
+                    "address_to" => addr_to.as_str(),
+                })
+                .inc()
+            }
+        }
 
         let result = self.inner.send_transaction(tx, block).await;
 
@@ -436,6 +474,7 @@ impl<M> PrometheusMiddleware<M> {
             inner: Arc::new(inner),
             metrics,
             conf: Arc::new(RwLock::new(conf)),
+            last_nonces: Default::default(),
         }
     }
 }
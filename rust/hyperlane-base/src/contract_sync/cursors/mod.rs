@@ -2,6 +2,7 @@ pub(crate) mod sequence_aware;
 
 use hyperlane_core::{
     Delivery, HyperlaneDomainProtocol, HyperlaneMessage, InterchainGasPayment, MerkleTreeInsertion,
+    ValidatorAnnouncement,
 };
 pub(crate) use sequence_aware::ForwardBackwardSequenceAwareSyncCursor;
 
@@ -21,7 +22,7 @@ impl Indexable for HyperlaneMessage {
     fn indexing_cursor(domain: HyperlaneDomainProtocol) -> CursorType {
         match domain {
             HyperlaneDomainProtocol::Ethereum => CursorType::SequenceAware,
-            HyperlaneDomainProtocol::Fuel => todo!(),
+            HyperlaneDomainProtocol::Fuel => CursorType::SequenceAware,
             HyperlaneDomainProtocol::Sealevel => CursorType::SequenceAware,
             HyperlaneDomainProtocol::Cosmos => CursorType::SequenceAware,
         }
@@ -32,7 +33,7 @@ impl Indexable for InterchainGasPayment {
     fn indexing_cursor(domain: HyperlaneDomainProtocol) -> CursorType {
         match domain {
             HyperlaneDomainProtocol::Ethereum => CursorType::RateLimited,
-            HyperlaneDomainProtocol::Fuel => todo!(),
+            HyperlaneDomainProtocol::Fuel => CursorType::RateLimited,
             HyperlaneDomainProtocol::Sealevel => CursorType::SequenceAware,
             HyperlaneDomainProtocol::Cosmos => CursorType::RateLimited,
         }
@@ -43,7 +44,7 @@ impl Indexable for MerkleTreeInsertion {
     fn indexing_cursor(domain: HyperlaneDomainProtocol) -> CursorType {
         match domain {
             HyperlaneDomainProtocol::Ethereum => CursorType::SequenceAware,
-            HyperlaneDomainProtocol::Fuel => todo!(),
+            HyperlaneDomainProtocol::Fuel => CursorType::SequenceAware,
             HyperlaneDomainProtocol::Sealevel => CursorType::SequenceAware,
             HyperlaneDomainProtocol::Cosmos => CursorType::SequenceAware,
         }
@@ -54,9 +55,20 @@ impl Indexable for Delivery {
     fn indexing_cursor(domain: HyperlaneDomainProtocol) -> CursorType {
         match domain {
             HyperlaneDomainProtocol::Ethereum => CursorType::RateLimited,
-            HyperlaneDomainProtocol::Fuel => todo!(),
+            HyperlaneDomainProtocol::Fuel => CursorType::RateLimited,
             HyperlaneDomainProtocol::Sealevel => CursorType::SequenceAware,
             HyperlaneDomainProtocol::Cosmos => CursorType::RateLimited,
         }
     }
 }
+
+impl Indexable for ValidatorAnnouncement {
+    fn indexing_cursor(domain: HyperlaneDomainProtocol) -> CursorType {
+        match domain {
+            HyperlaneDomainProtocol::Ethereum => CursorType::RateLimited,
+            HyperlaneDomainProtocol::Fuel => CursorType::RateLimited,
+            HyperlaneDomainProtocol::Sealevel => CursorType::RateLimited,
+            HyperlaneDomainProtocol::Cosmos => CursorType::RateLimited,
+        }
+    }
+}
@@ -4,10 +4,10 @@ use paste::paste;
 use tracing::{debug, instrument, trace};
 
 use hyperlane_core::{
-    GasPaymentKey, HyperlaneDomain, HyperlaneLogStore, HyperlaneMessage,
+    DeadLetteredMessage, GasPaymentKey, HyperlaneDomain, HyperlaneLogStore, HyperlaneMessage,
     HyperlaneSequenceAwareIndexerStoreReader, HyperlaneWatermarkedLogStore, Indexed,
     InterchainGasExpenditure, InterchainGasPayment, InterchainGasPaymentMeta, LogMeta,
-    MerkleTreeInsertion, H256,
+    MerkleTreeInsertion, PendingMessageState, RecipientPauseKey, H256,
 };
 
 use super::{
@@ -28,11 +28,18 @@ const GAS_PAYMENT_META_PROCESSED: &str = "gas_payment_meta_processed_v3_";
 const GAS_EXPENDITURE_FOR_MESSAGE_ID: &str = "gas_expenditure_for_message_id_v2_";
 const PENDING_MESSAGE_RETRY_COUNT_FOR_MESSAGE_ID: &str =
     "pending_message_retry_count_for_message_id_";
+const PENDING_MESSAGE_STATE_FOR_MESSAGE_ID: &str = "pending_message_state_for_message_id_";
+const PENDING_MESSAGE_FIRST_SEEN_FOR_MESSAGE_ID: &str =
+    "pending_message_first_seen_for_message_id_";
+const DEAD_LETTERED_MESSAGE: &str = "dead_lettered_message_";
 const MERKLE_TREE_INSERTION: &str = "merkle_tree_insertion_";
 const MERKLE_LEAF_INDEX_BY_MESSAGE_ID: &str = "merkle_leaf_index_by_message_id_";
 const MERKLE_TREE_INSERTION_BLOCK_NUMBER_BY_LEAF_INDEX: &str =
     "merkle_tree_insertion_block_number_by_leaf_index_";
 const LATEST_INDEXED_GAS_PAYMENT_BLOCK: &str = "latest_indexed_gas_payment_block";
+const OPERATOR_DROPPED_MESSAGE: &str = "operator_dropped_message_";
+const OPERATOR_PAUSED_DESTINATION: &str = "operator_paused_destination_";
+const OPERATOR_PAUSED_RECIPIENT: &str = "operator_paused_recipient_";
 
 type DbResult<T> = std::result::Result<T, DbError>;
 
@@ -172,6 +179,26 @@ impl HyperlaneRocksDB {
         Ok(true)
     }
 
+    /// Retrieve every dead-lettered message id and why it was dead-lettered
+    pub fn retrieve_dead_lettered_messages(&self) -> DbResult<Vec<(H256, DeadLetteredMessage)>> {
+        self.retrieve_all_keyed_decodable(DEAD_LETTERED_MESSAGE)
+    }
+
+    /// Remove a message from the dead-lettered messages
+    pub fn remove_dead_lettered_message(&self, message_id: &H256) -> DbResult<()> {
+        self.delete_keyed(DEAD_LETTERED_MESSAGE, message_id)
+    }
+
+    /// Remove the persisted state of a pending message
+    pub fn remove_pending_message_state(&self, message_id: &H256) -> DbResult<()> {
+        self.delete_keyed(PENDING_MESSAGE_STATE_FOR_MESSAGE_ID, message_id)
+    }
+
+    /// Retrieve a message by its id
+    pub fn retrieve_message_by_message_id(&self, id: &H256) -> DbResult<Option<HyperlaneMessage>> {
+        self.retrieve_message_by_id(id)
+    }
+
     /// Store the merkle tree insertion event, and also store a mapping from message_id to leaf_index
     pub fn process_tree_insertion(
         &self,
@@ -458,6 +485,27 @@ make_store_and_retrieve!(
     H256,
     u32
 );
+make_store_and_retrieve!(
+    pub,
+    pending_message_state_by_message_id,
+    PENDING_MESSAGE_STATE_FOR_MESSAGE_ID,
+    H256,
+    PendingMessageState
+);
+make_store_and_retrieve!(
+    pub,
+    pending_message_first_seen_by_message_id,
+    PENDING_MESSAGE_FIRST_SEEN_FOR_MESSAGE_ID,
+    H256,
+    u64
+);
+make_store_and_retrieve!(
+    pub,
+    dead_lettered_message_by_message_id,
+    DEAD_LETTERED_MESSAGE,
+    H256,
+    DeadLetteredMessage
+);
 make_store_and_retrieve!(
     pub,
     merkle_tree_insertion_by_leaf_index,
@@ -479,3 +527,24 @@ make_store_and_retrieve!(
     u32,
     u64
 );
+make_store_and_retrieve!(
+    pub,
+    operator_dropped_by_message_id,
+    OPERATOR_DROPPED_MESSAGE,
+    H256,
+    bool
+);
+make_store_and_retrieve!(
+    pub,
+    operator_paused_by_destination_domain,
+    OPERATOR_PAUSED_DESTINATION,
+    u32,
+    bool
+);
+make_store_and_retrieve!(
+    pub,
+    operator_paused_by_recipient,
+    OPERATOR_PAUSED_RECIPIENT,
+    RecipientPauseKey,
+    bool
+);
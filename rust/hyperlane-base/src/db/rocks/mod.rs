@@ -109,4 +109,24 @@ impl DB {
     pub fn retrieve(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
         Ok(self.0.get(key)?)
     }
+
+    /// Delete a value from the DB
+    pub fn delete(&self, key: &[u8]) -> Result<()> {
+        Ok(self.0.delete(key)?)
+    }
+
+    /// Retrieve every key value pair whose key starts with `prefix`
+    pub fn retrieve_by_prefix(&self, prefix: &[u8]) -> Result<Vec<(Box<[u8]>, Box<[u8]>)>> {
+        let mut entries = vec![];
+        for entry in self.0.prefix_iterator(prefix) {
+            let (key, value) = entry?;
+            // The iterator continues past the prefix if no prefix extractor is
+            // configured
+            if !key.starts_with(prefix) {
+                break;
+            }
+            entries.push((key, value));
+        }
+        Ok(entries)
+    }
 }
@@ -85,4 +85,28 @@ impl TypedDB {
     ) -> Result<Option<V>> {
         self.retrieve_decodable(prefix, key.to_vec())
     }
+
+    /// Delete the value of an encodable key
+    pub fn delete_keyed<K: Encode>(&self, prefix: impl AsRef<[u8]>, key: &K) -> Result<()> {
+        self.db
+            .delete(&self.prefixed_key(prefix.as_ref(), &key.to_vec()))
+    }
+
+    /// Retrieve every decodable kv pair stored under a prefix
+    pub fn retrieve_all_keyed_decodable<K: Decode, V: Decode>(
+        &self,
+        prefix: impl AsRef<[u8]>,
+    ) -> Result<Vec<(K, V)>> {
+        let full_prefix = self.prefixed_key(prefix.as_ref(), &[]);
+        self.db
+            .retrieve_by_prefix(&full_prefix)?
+            .into_iter()
+            .map(|(key, value)| {
+                Ok((
+                    K::read_from(&mut &key[full_prefix.len()..])?,
+                    V::read_from(&mut value.as_ref())?,
+                ))
+            })
+            .collect()
+    }
 }
@@ -38,9 +38,17 @@ pub struct CoreMetrics {
     span_events: IntCounterVec,
     last_known_message_nonce: IntGaugeVec,
     submitter_queue_length: IntGaugeVec,
+    operations_throttled_count: IntCounterVec,
+
+    route_gas_paid: CounterVec,
+    route_gas_spent: CounterVec,
+    route_gas_net: GaugeVec,
 
     operations_processed_count: IntCounterVec,
     messages_processed_count: IntCounterVec,
+    messages_dead_lettered_count: IntCounterVec,
+    dry_run_messages_count: IntCounterVec,
+    dry_run_estimated_cost: CounterVec,
 
     latest_checkpoint: IntGaugeVec,
 
@@ -136,6 +144,46 @@ impl CoreMetrics {
             registry
         )?;
 
+        let operations_throttled_count = register_int_counter_vec_with_registry!(
+            opts!(
+                namespaced!("operations_throttled_count"),
+                "Number of operations held back by a rate limit",
+                const_labels_ref
+            ),
+            &["remote", "queue_name", "app"],
+            registry
+        )?;
+
+        let route_gas_paid = register_counter_vec_with_registry!(
+            opts!(
+                namespaced!("route_gas_paid"),
+                "Gas payments of delivered messages, in native tokens of the origin",
+                const_labels_ref
+            ),
+            &["origin", "remote", "app_context"],
+            registry
+        )?;
+
+        let route_gas_spent = register_counter_vec_with_registry!(
+            opts!(
+                namespaced!("route_gas_spent"),
+                "Tokens spent delivering messages, in native tokens of the remote",
+                const_labels_ref
+            ),
+            &["origin", "remote", "app_context"],
+            registry
+        )?;
+
+        let route_gas_net = register_gauge_vec_with_registry!(
+            opts!(
+                namespaced!("route_gas_net"),
+                "Value of the gas paid minus value of the tokens spent delivering messages, in a common currency",
+                const_labels_ref
+            ),
+            &["origin", "remote", "app_context"],
+            registry
+        )?;
+
         let latest_checkpoint = register_int_gauge_vec_with_registry!(
             opts!(
                 namespaced!("latest_checkpoint"),
@@ -166,6 +214,36 @@ impl CoreMetrics {
             registry
         )?;
 
+        let messages_dead_lettered_count = register_int_counter_vec_with_registry!(
+            opts!(
+                namespaced!("messages_dead_lettered_count"),
+                "Number of messages given up on after exceeding their retry budget",
+                const_labels_ref
+            ),
+            &["origin", "remote"],
+            registry
+        )?;
+
+        let dry_run_messages_count = register_int_counter_vec_with_registry!(
+            opts!(
+                namespaced!("dry_run_messages_count"),
+                "Number of messages that would have been submitted in dry-run mode",
+                const_labels_ref
+            ),
+            &["origin", "remote"],
+            registry
+        )?;
+
+        let dry_run_estimated_cost = register_counter_vec_with_registry!(
+            opts!(
+                namespaced!("dry_run_estimated_cost"),
+                "Estimated tokens that would have been spent in dry-run mode, in native tokens of the remote",
+                const_labels_ref
+            ),
+            &["origin", "remote"],
+            registry
+        )?;
+
         Ok(Self {
             agent_name: for_agent.into(),
             registry,
@@ -178,9 +256,17 @@ impl CoreMetrics {
             last_known_message_nonce,
 
             submitter_queue_length,
+            operations_throttled_count,
+
+            route_gas_paid,
+            route_gas_spent,
+            route_gas_net,
 
             operations_processed_count,
             messages_processed_count,
+            messages_dead_lettered_count,
+            dry_run_messages_count,
+            dry_run_estimated_cost,
 
             latest_checkpoint,
 
@@ -327,6 +413,48 @@ impl CoreMetrics {
         self.submitter_queue_length.clone()
     }
 
+    /// The number of operations that were ready to be processed but were held
+    /// back because their app exceeded its rate limit. Each operation is only
+    /// counted once, however many times it is held back.
+    ///
+    /// Labels:
+    /// - `remote`: Remote chain the queue is for.
+    /// - `queue_name`: Which queue the operation is in.
+    /// - `app`: The scheduling group of the operation.
+    pub fn operations_throttled_count(&self) -> IntCounterVec {
+        self.operations_throttled_count.clone()
+    }
+
+    /// The sum of the gas payments of the messages delivered by this process
+    /// during its lifetime, in whole native tokens of the origin chain.
+    ///
+    /// Labels:
+    /// - `origin`: Chain the messages came from.
+    /// - `remote`: Chain the messages were delivered to.
+    /// - `app_context`: The app context of the messages.
+    pub fn route_gas_paid(&self) -> CounterVec {
+        self.route_gas_paid.clone()
+    }
+
+    /// The tokens spent by this process during its lifetime on attempts to
+    /// deliver messages, successful or not, in whole native tokens of the
+    /// remote chain.
+    ///
+    /// Labels are the same as `route_gas_paid`.
+    pub fn route_gas_spent(&self) -> CounterVec {
+        self.route_gas_spent.clone()
+    }
+
+    /// The value of `route_gas_paid` minus the value of `route_gas_spent`,
+    /// both converted to the common currency of the relayer's exchange rates.
+    /// Only reported for routes whose payments and expenditures could all be
+    /// converted.
+    ///
+    /// Labels are the same as `route_gas_paid`.
+    pub fn route_gas_net(&self) -> GaugeVec {
+        self.route_gas_net.clone()
+    }
+
     /// The number of operations successfully submitted by this process during
     /// its lifetime.
     ///
@@ -371,6 +499,36 @@ impl CoreMetrics {
         self.messages_processed_count.clone()
     }
 
+    /// The number of messages this process gave up on during its lifetime
+    /// because they exceeded the max retries or max age, and moved to the
+    /// dead-letter store.
+    ///
+    /// Labels:
+    /// - `origin`: Chain the message came from.
+    /// - `remote`: Chain the message was destined for.
+    pub fn messages_dead_lettered_count(&self) -> IntCounterVec {
+        self.messages_dead_lettered_count.clone()
+    }
+
+    /// The number of messages this process prepared but did not submit
+    /// because it is running in dry-run mode.
+    ///
+    /// Labels:
+    /// - `origin`: Chain the message came from.
+    /// - `remote`: Chain the message would have been delivered to.
+    pub fn dry_run_messages_count(&self) -> IntCounterVec {
+        self.dry_run_messages_count.clone()
+    }
+
+    /// The estimated cost of the transactions this process would have
+    /// submitted if it weren't running in dry-run mode, in whole native tokens
+    /// of the remote.
+    ///
+    /// Labels are the same as `dry_run_messages_count`.
+    pub fn dry_run_estimated_cost(&self) -> CounterVec {
+        self.dry_run_estimated_cost.clone()
+    }
+
     /// Measure of span durations provided by tracing.
     ///
     /// Labels:
@@ -36,5 +36,10 @@ pub(crate) fn create_provider_metrics(metrics: &CoreMetrics) -> Result<Middlewar
             TRANSACTION_SEND_TOTAL_HELP,
             TRANSACTION_SEND_TOTAL_LABELS,
         )?)
+        .transaction_replacement_total(metrics.new_int_counter(
+            "transaction_replacement_total",
+            TRANSACTION_REPLACEMENT_TOTAL_HELP,
+            TRANSACTION_REPLACEMENT_TOTAL_LABELS,
+        )?)
         .build()?)
 }
@@ -11,7 +11,7 @@ use hyperlane_core::{
     HyperlaneDomain, HyperlaneDomainProtocol, HyperlaneMessage, HyperlaneProvider, IndexMode,
     InterchainGasPaymaster, InterchainGasPayment, InterchainSecurityModule, Mailbox,
     MerkleTreeHook, MerkleTreeInsertion, MultisigIsm, RoutingIsm, SequenceAwareIndexer,
-    ValidatorAnnounce, H256,
+    ValidatorAnnounce, ValidatorAnnouncement, H256,
 };
 use hyperlane_cosmos as h_cosmos;
 use hyperlane_ethereum::{
@@ -70,6 +70,9 @@ pub type IgpIndexer = Arc<dyn SequenceAwareIndexer<InterchainGasPayment>>;
 /// A sequence-aware indexer for merkle tree hooks
 pub type MerkleTreeHookIndexer = Arc<dyn SequenceAwareIndexer<MerkleTreeInsertion>>;
 
+/// A sequence-aware indexer for validator announcements
+pub type ValidatorAnnounceIndexer = Arc<dyn SequenceAwareIndexer<ValidatorAnnouncement>>;
+
 #[async_trait]
 impl TryFromWithMetrics<ChainConf> for MessageIndexer {
     async fn try_from_with_metrics(conf: &ChainConf, metrics: &CoreMetrics) -> Result<Self> {
@@ -102,6 +105,15 @@ impl TryFromWithMetrics<ChainConf> for MerkleTreeHookIndexer {
     }
 }
 
+#[async_trait]
+impl TryFromWithMetrics<ChainConf> for ValidatorAnnounceIndexer {
+    async fn try_from_with_metrics(conf: &ChainConf, metrics: &CoreMetrics) -> Result<Self> {
+        conf.build_validator_announce_indexer(metrics)
+            .await
+            .map(Into::into)
+    }
+}
+
 /// A connection to _some_ blockchain.
 #[derive(Clone, Debug)]
 pub enum ChainConnectionConf {
@@ -174,12 +186,15 @@ impl ChainConf {
     ) -> Result<Box<dyn HyperlaneProvider>> {
         let ctx = "Building provider";
         let locator = self.locator(H256::zero());
-        match &self.connection {
+        match &self.connection_with_metrics(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(conf, &locator, metrics, h_eth::HyperlaneProviderBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let provider = h_fuel::FuelProvider::new(locator.domain.clone(), conf)?;
+                Ok(Box::new(provider) as Box<dyn HyperlaneProvider>)
+            }
             ChainConnectionConf::Sealevel(conf) => Ok(Box::new(h_sealevel::SealevelProvider::new(
                 locator.domain.clone(),
                 conf,
@@ -202,14 +217,14 @@ impl ChainConf {
         let ctx = "Building mailbox";
         let locator = self.locator(self.addresses.mailbox);
 
-        match &self.connection {
+        match &self.connection_with_metrics(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(conf, &locator, metrics, h_eth::MailboxBuilder {})
                     .await
             }
             ChainConnectionConf::Fuel(conf) => {
                 let wallet = self.fuel_signer().await.context(ctx)?;
-                hyperlane_fuel::FuelMailbox::new(conf, locator, wallet)
+                h_fuel::FuelMailbox::new(conf, locator, wallet)
                     .map(|m| Box::new(m) as Box<dyn Mailbox>)
                     .map_err(Into::into)
             }
@@ -237,14 +252,14 @@ impl ChainConf {
         let ctx = "Building merkle tree hook";
         let locator = self.locator(self.addresses.merkle_tree_hook);
 
-        match &self.connection {
+        match &self.connection_with_metrics(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(conf, &locator, metrics, h_eth::MerkleTreeHookBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_conf) => {
-                todo!("Fuel does not support merkle tree hooks yet")
-            }
+            ChainConnectionConf::Fuel(conf) => h_fuel::FuelMailbox::new(conf, locator, None)
+                .map(|m| Box::new(m) as Box<dyn MerkleTreeHook>)
+                .map_err(Into::into),
             ChainConnectionConf::Sealevel(conf) => {
                 h_sealevel::SealevelMailbox::new(conf, locator, None)
                     .map(|m| Box::new(m) as Box<dyn MerkleTreeHook>)
@@ -269,7 +284,7 @@ impl ChainConf {
         let ctx = "Building delivery indexer";
         let locator = self.locator(self.addresses.mailbox);
 
-        match &self.connection {
+        match &self.connection_with_metrics(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(
                     conf,
@@ -281,7 +296,10 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let indexer = Box::new(h_fuel::FuelMailboxIndexer::new(conf, locator)?);
+                Ok(indexer as Box<dyn SequenceAwareIndexer<HyperlaneMessage>>)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let indexer = Box::new(h_sealevel::SealevelMailboxIndexer::new(conf, locator)?);
                 Ok(indexer as Box<dyn SequenceAwareIndexer<HyperlaneMessage>>)
@@ -308,7 +326,7 @@ impl ChainConf {
         let ctx = "Building delivery indexer";
         let locator = self.locator(self.addresses.mailbox);
 
-        match &self.connection {
+        match &self.connection_with_metrics(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(
                     conf,
@@ -320,7 +338,10 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let indexer = Box::new(h_fuel::FuelMailboxIndexer::new(conf, locator)?);
+                Ok(indexer as Box<dyn SequenceAwareIndexer<H256>>)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let indexer = Box::new(h_sealevel::SealevelMailboxIndexer::new(conf, locator)?);
                 Ok(indexer as Box<dyn SequenceAwareIndexer<H256>>)
@@ -348,7 +369,7 @@ impl ChainConf {
         let ctx = "Building IGP";
         let locator = self.locator(self.addresses.interchain_gas_paymaster);
 
-        match &self.connection {
+        match &self.connection_with_metrics(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(
                     conf,
@@ -358,7 +379,10 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let paymaster = Box::new(h_fuel::FuelInterchainGasPaymaster::new(conf, locator)?);
+                Ok(paymaster as Box<dyn InterchainGasPaymaster>)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let paymaster = Box::new(
                     h_sealevel::SealevelInterchainGasPaymaster::new(conf, &locator).await?,
@@ -386,7 +410,7 @@ impl ChainConf {
         let ctx = "Building IGP indexer";
         let locator = self.locator(self.addresses.interchain_gas_paymaster);
 
-        match &self.connection {
+        match &self.connection_with_metrics(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(
                     conf,
@@ -399,7 +423,12 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let indexer = Box::new(h_fuel::FuelInterchainGasPaymasterIndexer::new(
+                    conf, locator,
+                )?);
+                Ok(indexer as Box<dyn SequenceAwareIndexer<InterchainGasPayment>>)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let indexer = Box::new(
                     h_sealevel::SealevelInterchainGasPaymasterIndexer::new(conf, locator).await?,
@@ -426,7 +455,7 @@ impl ChainConf {
         let ctx = "Building merkle tree hook indexer";
         let locator = self.locator(self.addresses.merkle_tree_hook);
 
-        match &self.connection {
+        match &self.connection_with_metrics(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(
                     conf,
@@ -438,7 +467,11 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let mailbox_indexer = h_fuel::FuelMailboxIndexer::new(conf, locator)?;
+                let indexer = Box::new(h_fuel::FuelMerkleTreeHookIndexer::new(mailbox_indexer));
+                Ok(indexer as Box<dyn SequenceAwareIndexer<MerkleTreeInsertion>>)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let mailbox_indexer =
                     Box::new(h_sealevel::SealevelMailboxIndexer::new(conf, locator)?);
@@ -469,12 +502,16 @@ impl ChainConf {
     ) -> Result<Box<dyn ValidatorAnnounce>> {
         let ctx = "Building validator announce";
         let locator = self.locator(self.addresses.validator_announce);
-        match &self.connection {
+        match &self.connection_with_metrics(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(conf, &locator, metrics, h_eth::ValidatorAnnounceBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_signer().await.context(ctx)?;
+                let va = Box::new(h_fuel::FuelValidatorAnnounce::new(conf, locator, wallet)?);
+                Ok(va as Box<dyn ValidatorAnnounce>)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let va = Box::new(h_sealevel::SealevelValidatorAnnounce::new(conf, locator));
                 Ok(va as Box<dyn ValidatorAnnounce>)
@@ -493,6 +530,49 @@ impl ChainConf {
         .context("Building ValidatorAnnounce")
     }
 
+    /// Try to convert the chain settings into a validator announce indexer
+    pub async fn build_validator_announce_indexer(
+        &self,
+        metrics: &CoreMetrics,
+    ) -> Result<Box<dyn SequenceAwareIndexer<ValidatorAnnouncement>>> {
+        let ctx = "Building validator announce indexer";
+        let locator = self.locator(self.addresses.validator_announce);
+
+        match &self.connection_with_metrics(metrics) {
+            ChainConnectionConf::Ethereum(conf) => {
+                self.build_ethereum(
+                    conf,
+                    &locator,
+                    metrics,
+                    h_eth::ValidatorAnnounceIndexerBuilder {
+                        reorg_period: self.reorg_period,
+                    },
+                )
+                .await
+            }
+            ChainConnectionConf::Fuel(conf) => {
+                let indexer = Box::new(h_fuel::FuelValidatorAnnounceIndexer::new(conf, locator)?);
+                Ok(indexer as Box<dyn SequenceAwareIndexer<ValidatorAnnouncement>>)
+            }
+            ChainConnectionConf::Sealevel(_) => {
+                // Announcements are stored in accounts rather than emitted as
+                // events, so there is nothing to index by range
+                Err(eyre!(
+                    "Validator announcements cannot be indexed on Sealevel"
+                ))
+            }
+            ChainConnectionConf::Cosmos(conf) => {
+                let indexer = Box::new(h_cosmos::CosmosValidatorAnnounceIndexer::new(
+                    conf.clone(),
+                    locator,
+                    self.reorg_period,
+                )?);
+                Ok(indexer as Box<dyn SequenceAwareIndexer<ValidatorAnnouncement>>)
+            }
+        }
+        .context(ctx)
+    }
+
     /// Try to convert the chain setting into an InterchainSecurityModule
     /// contract
     pub async fn build_ism(
@@ -503,7 +583,7 @@ impl ChainConf {
         let ctx = "Building ISM";
         let locator = self.locator(address);
 
-        match &self.connection {
+        match &self.connection_with_metrics(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(
                     conf,
@@ -513,7 +593,10 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let ism = Box::new(h_fuel::FuelInterchainSecurityModule::new(conf, locator)?);
+                Ok(ism as Box<dyn InterchainSecurityModule>)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let keypair = self.sealevel_signer().await.context(ctx)?;
                 let ism = Box::new(h_sealevel::SealevelInterchainSecurityModule::new(
@@ -541,13 +624,16 @@ impl ChainConf {
         let ctx = "Building multisig ISM";
         let locator = self.locator(address);
 
-        match &self.connection {
+        match &self.connection_with_metrics(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(conf, &locator, metrics, h_eth::MultisigIsmBuilder {})
                     .await
             }
 
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let ism = Box::new(h_fuel::FuelMultisigIsm::new(conf, locator)?);
+                Ok(ism as Box<dyn MultisigIsm>)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let keypair = self.sealevel_signer().await.context(ctx)?;
                 let ism = Box::new(h_sealevel::SealevelMultisigIsm::new(conf, locator, keypair));
@@ -578,12 +664,15 @@ impl ChainConf {
             address,
         };
 
-        match &self.connection {
+        match &self.connection_with_metrics(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(conf, &locator, metrics, h_eth::RoutingIsmBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let ism = Box::new(h_fuel::FuelRoutingIsm::new(conf, locator)?);
+                Ok(ism as Box<dyn RoutingIsm>)
+            }
             ChainConnectionConf::Sealevel(_) => {
                 Err(eyre!("Sealevel does not support routing ISM yet")).context(ctx)
             }
@@ -612,12 +701,15 @@ impl ChainConf {
             address,
         };
 
-        match &self.connection {
+        match &self.connection_with_metrics(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(conf, &locator, metrics, h_eth::AggregationIsmBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let ism = Box::new(h_fuel::FuelAggregationIsm::new(conf, locator)?);
+                Ok(ism as Box<dyn AggregationIsm>)
+            }
             ChainConnectionConf::Sealevel(_) => {
                 Err(eyre!("Sealevel does not support aggregation ISM yet")).context(ctx)
             }
@@ -647,12 +739,12 @@ impl ChainConf {
             address,
         };
 
-        match &self.connection {
+        match &self.connection_with_metrics(metrics) {
             ChainConnectionConf::Ethereum(conf) => {
                 self.build_ethereum(conf, &locator, metrics, h_eth::CcipReadIsmBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(_) => Err(eyre!("Fuel does not support CCIP read ISM yet")),
             ChainConnectionConf::Sealevel(_) => {
                 Err(eyre!("Sealevel does not support CCIP read ISM yet")).context(ctx)
             }
@@ -694,10 +786,8 @@ impl ChainConf {
         self.signer().await
     }
 
-    async fn fuel_signer(&self) -> Result<fuels::prelude::WalletUnlocked> {
-        self.signer().await.and_then(|opt| {
-            opt.ok_or_else(|| eyre!("Fuel requires a signer to construct contract instances"))
-        })
+    async fn fuel_signer(&self) -> Result<Option<fuels::prelude::WalletUnlocked>> {
+        self.signer().await
     }
 
     async fn sealevel_signer(&self) -> Result<Option<h_sealevel::Keypair>> {
@@ -765,6 +855,22 @@ impl ChainConf {
         cfg
     }
 
+    /// The connection config with the agent's RPC metrics attached. Ethereum
+    /// providers get them when they are built instead.
+    fn connection_with_metrics(&self, metrics: &CoreMetrics) -> ChainConnectionConf {
+        let mut connection = self.connection.clone();
+        match &mut connection {
+            ChainConnectionConf::Sealevel(conf) => {
+                conf.rpc_metrics = Some(metrics.json_rpc_client_metrics());
+            }
+            ChainConnectionConf::Cosmos(conf) => {
+                conf.set_rpc_metrics(metrics.json_rpc_client_metrics());
+            }
+            ChainConnectionConf::Ethereum(_) | ChainConnectionConf::Fuel(_) => {}
+        }
+        connection
+    }
+
     fn locator(&self, address: H256) -> ContractLocator {
         ContractLocator {
             domain: &self.domain,
@@ -1,7 +1,11 @@
+use std::time::Duration;
+
 use eyre::eyre;
 use h_eth::TransactionOverrides;
 use hyperlane_core::config::{ConfigErrResultExt, OperationBatchConfig};
-use hyperlane_core::{config::ConfigParsingError, HyperlaneDomainProtocol};
+use hyperlane_core::{
+    config::ConfigParsingError, rpc_clients::RpcConsensusType, HyperlaneDomainProtocol,
+};
 use url::Url;
 
 use crate::settings::envs::*;
@@ -62,6 +66,22 @@ pub fn build_ethereum_connection_conf(
                 .get_opt_key("maxPriorityFeePerGas")
                 .parse_u256()
                 .end(),
+            gas_escalation_interval: value_parser
+                .chain(err)
+                .get_opt_key("gasEscalationIntervalSecs")
+                .parse_u64()
+                .end()
+                .map(Duration::from_secs),
+            gas_escalation_percent: value_parser
+                .chain(err)
+                .get_opt_key("gasEscalationPercent")
+                .parse_u64()
+                .end(),
+            gas_price_cap: value_parser
+                .chain(err)
+                .get_opt_key("gasPriceCap")
+                .parse_u256()
+                .end(),
         })
         .unwrap_or_default();
 
@@ -76,9 +96,12 @@ pub fn build_cosmos_connection_conf(
     rpcs: &[Url],
     chain: &ValueParser,
     err: &mut ConfigParsingError,
+    default_rpc_consensus_type: &str,
     operation_batch: OperationBatchConfig,
 ) -> Option<ChainConnectionConf> {
     let mut local_err = ConfigParsingError::default();
+    let rpc_consensus_type =
+        parse_rpc_consensus_type(chain, &mut local_err, default_rpc_consensus_type);
     let grpcs =
         parse_base_and_override_urls(chain, "grpcUrls", "customGrpcUrls", "http", &mut local_err);
 
@@ -134,23 +157,146 @@ pub fn build_cosmos_connection_conf(
         .parse_u64()
         .end();
 
+    let transaction_overrides = chain
+        .get_opt_key("transactionOverrides")
+        .take_err(err, || &chain.cwp + "transaction_overrides")
+        .flatten()
+        .map(|value_parser| h_cosmos::TransactionOverrides {
+            gas_limit: value_parser
+                .chain(err)
+                .get_opt_key("gasLimit")
+                .parse_u64()
+                .end(),
+            gas_adjustment: value_parser
+                .chain(err)
+                .get_opt_key("gasAdjustment")
+                .parse_f64()
+                .end(),
+            max_fee: value_parser
+                .chain(err)
+                .get_opt_key("maxFee")
+                .parse_u256()
+                .end()
+                .map(|max_fee| max_fee.low_u128()),
+            fee_granter: value_parser
+                .chain(err)
+                .get_opt_key("feeGranter")
+                .parse_string()
+                .end()
+                .map(str::to_owned),
+        })
+        .unwrap_or_default();
+
     if !local_err.is_ok() {
         err.merge(local_err);
         None
     } else {
         Some(ChainConnectionConf::Cosmos(h_cosmos::ConnectionConf::new(
             grpcs,
-            rpcs.first().unwrap().to_string(),
+            rpcs.to_vec(),
+            rpc_consensus_type.unwrap(),
             chain_id.unwrap().to_string(),
             prefix.unwrap().to_string(),
             canonical_asset.unwrap(),
             gas_price.unwrap(),
             contract_address_bytes.unwrap().try_into().unwrap(),
             operation_batch,
+            transaction_overrides,
         )))
     }
 }
 
+pub fn build_sealevel_connection_conf(
+    rpcs: &[Url],
+    chain: &ValueParser,
+    err: &mut ConfigParsingError,
+    default_rpc_consensus_type: &str,
+    operation_batch: OperationBatchConfig,
+) -> Option<ChainConnectionConf> {
+    if rpcs.is_empty() {
+        return None;
+    }
+
+    let rpc_consensus_type = parse_rpc_consensus_type(chain, err, default_rpc_consensus_type)?;
+
+    let priority_fee = chain
+        .get_opt_key("priorityFee")
+        .take_err(err, || &chain.cwp + "priority_fee")
+        .flatten()
+        .map(|value_parser| {
+            let strategy = match value_parser
+                .chain(err)
+                .get_opt_key("strategy")
+                .parse_string()
+                .unwrap_or("fixed")
+            {
+                "fixed" => Some(h_sealevel::PriorityFeeStrategy::Fixed(
+                    value_parser
+                        .chain(err)
+                        .get_opt_key("microLamports")
+                        .parse_u64()
+                        .unwrap_or(0),
+                )),
+                "percentile" => value_parser
+                    .chain(err)
+                    .get_opt_key("percentile")
+                    .parse_u64()
+                    .unwrap_or(50)
+                    .try_into()
+                    .ok()
+                    .filter(|percentile| *percentile <= 100)
+                    .map(h_sealevel::PriorityFeeStrategy::Percentile)
+                    .ok_or_else(|| eyre!("priority fee percentile must be between 0 and 100"))
+                    .take_err(err, || &value_parser.cwp + "percentile"),
+                ty => Err(eyre!("unknown priority fee strategy `{ty}`"))
+                    .take_err(err, || &value_parser.cwp + "strategy"),
+            };
+            h_sealevel::PriorityFeeConfig {
+                strategy: strategy.unwrap_or_default(),
+                max_lamports_per_tx: value_parser
+                    .chain(err)
+                    .get_opt_key("maxLamports")
+                    .parse_u64()
+                    .end(),
+                bump_percent: value_parser
+                    .chain(err)
+                    .get_opt_key("bumpPercent")
+                    .parse_u64()
+                    .unwrap_or(50),
+            }
+        })
+        .unwrap_or_default();
+
+    Some(ChainConnectionConf::Sealevel(h_sealevel::ConnectionConf {
+        urls: rpcs.to_vec(),
+        rpc_consensus_type,
+        operation_batch,
+        priority_fee,
+        index_transaction_details: false,
+        rpc_metrics: None,
+    }))
+}
+
+/// Parse the consensus type of chains whose clients implement it themselves.
+/// With a single url, `single` behaves like `fallback`.
+fn parse_rpc_consensus_type(
+    chain: &ValueParser,
+    err: &mut ConfigParsingError,
+    default_rpc_consensus_type: &str,
+) -> Option<RpcConsensusType> {
+    match chain
+        .chain(err)
+        .get_opt_key("rpcConsensusType")
+        .parse_string()
+        .unwrap_or(default_rpc_consensus_type)
+    {
+        "single" | "fallback" => Some(RpcConsensusType::Fallback),
+        "quorum" => Some(RpcConsensusType::Quorum),
+        ty => Err(eyre!("unknown rpc consensus type `{ty}`"))
+            .take_err(err, || &chain.cwp + "rpc_consensus_type"),
+    }
+}
+
 pub fn build_connection_conf(
     domain_protocol: HyperlaneDomainProtocol,
     rpcs: &[Url],
@@ -171,14 +317,19 @@ pub fn build_connection_conf(
             .iter()
             .next()
             .map(|url| ChainConnectionConf::Fuel(h_fuel::ConnectionConf { url: url.clone() })),
-        HyperlaneDomainProtocol::Sealevel => rpcs.iter().next().map(|url| {
-            ChainConnectionConf::Sealevel(h_sealevel::ConnectionConf {
-                url: url.clone(),
-                operation_batch,
-            })
-        }),
-        HyperlaneDomainProtocol::Cosmos => {
-            build_cosmos_connection_conf(rpcs, chain, err, operation_batch)
-        }
+        HyperlaneDomainProtocol::Sealevel => build_sealevel_connection_conf(
+            rpcs,
+            chain,
+            err,
+            default_rpc_consensus_type,
+            operation_batch,
+        ),
+        HyperlaneDomainProtocol::Cosmos => build_cosmos_connection_conf(
+            rpcs,
+            chain,
+            err,
+            default_rpc_consensus_type,
+            operation_batch,
+        ),
     }
 }
@@ -1,8 +1,15 @@
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use derive_new::new;
 use eyre::Result;
+use futures_util::{
+    future::join_all,
+    stream::{self, FuturesUnordered},
+    StreamExt,
+};
+use tokio::time::timeout;
 use tracing::{debug, instrument};
 
 use hyperlane_core::{
@@ -11,6 +18,66 @@ use hyperlane_core::{
 
 use crate::{CheckpointSyncer, CoreMetrics};
 
+/// Max time to wait for a single validator's checkpoint syncer to respond.
+const VALIDATOR_FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Number of checkpoint indices to look for a quorum at concurrently when
+/// walking backwards through a range.
+const CONCURRENT_INDEX_FETCHES: usize = 4;
+
+/// Caches the latest indices and signed checkpoints fetched from validators,
+/// so they can be shared by every message from the same origin.
+///
+/// Latest indices are only cached for a few seconds since they keep moving.
+/// Signed checkpoints are only cached once their signature has been verified,
+/// and are evicted after a few minutes to bound memory usage.
+#[derive(Debug, Default)]
+pub struct CheckpointCache {
+    latest_indices: Mutex<HashMap<H160, (u32, Instant)>>,
+    checkpoints: Mutex<HashMap<(H160, u32), (SignedCheckpointWithMessageId, Instant)>>,
+}
+
+impl CheckpointCache {
+    /// Time to live for a cached latest index
+    const LATEST_INDEX_TTL: Duration = Duration::from_secs(5);
+    /// Time to live for a cached signed checkpoint
+    const CHECKPOINT_TTL: Duration = Duration::from_secs(60 * 5);
+    /// Number of cached signed checkpoints past which expired ones are evicted
+    const MAX_CHECKPOINTS: usize = 10_000;
+
+    fn latest_index(&self, validator: &H160) -> Option<u32> {
+        let latest_indices = self.latest_indices.lock().unwrap();
+        latest_indices
+            .get(validator)
+            .filter(|(_, fetched_at)| fetched_at.elapsed() < Self::LATEST_INDEX_TTL)
+            .map(|(index, _)| *index)
+    }
+
+    fn set_latest_index(&self, validator: H160, index: u32) {
+        let mut latest_indices = self.latest_indices.lock().unwrap();
+        latest_indices.insert(validator, (index, Instant::now()));
+    }
+
+    fn checkpoint(&self, validator: &H160, index: u32) -> Option<SignedCheckpointWithMessageId> {
+        let checkpoints = self.checkpoints.lock().unwrap();
+        checkpoints
+            .get(&(*validator, index))
+            .filter(|(_, fetched_at)| fetched_at.elapsed() < Self::CHECKPOINT_TTL)
+            .map(|(checkpoint, _)| checkpoint.clone())
+    }
+
+    fn set_checkpoint(&self, validator: H160, checkpoint: SignedCheckpointWithMessageId) {
+        let mut checkpoints = self.checkpoints.lock().unwrap();
+        if checkpoints.len() >= Self::MAX_CHECKPOINTS {
+            checkpoints.retain(|_, (_, fetched_at)| fetched_at.elapsed() < Self::CHECKPOINT_TTL);
+        }
+        checkpoints.insert(
+            (validator, checkpoint.value.index),
+            (checkpoint, Instant::now()),
+        );
+    }
+}
+
 /// For a particular validator set, fetches signed checkpoints from multiple
 /// validators to create MultisigSignedCheckpoints.
 #[derive(Clone, Debug, new)]
@@ -19,6 +86,9 @@ pub struct MultisigCheckpointSyncer {
     checkpoint_syncers: HashMap<H160, Arc<dyn CheckpointSyncer>>,
     metrics: Arc<CoreMetrics>,
     app_context: Option<String>,
+    /// Latest indices and signed checkpoints shared with other messages from
+    /// the same origin
+    cache: Arc<CheckpointCache>,
 }
 
 impl MultisigCheckpointSyncer {
@@ -32,32 +102,22 @@ impl MultisigCheckpointSyncer {
         origin: &HyperlaneDomain,
         destination: &HyperlaneDomain,
     ) -> Vec<u32> {
-        // Get the latest_index from each validator's checkpoint syncer.
+        // Get the latest_index from each validator's checkpoint syncer concurrently.
         // If a validator does not return a latest index, None is recorded so
         // this can be surfaced in the metrics.
-        let mut latest_indices: HashMap<H160, Option<u32>> =
-            HashMap::with_capacity(validators.len());
-
-        for validator in validators {
+        let fetches = validators.iter().filter_map(|validator| {
             let address = H160::from(*validator);
-            if let Some(checkpoint_syncer) = self.checkpoint_syncers.get(&address) {
-                // Gracefully handle errors getting the latest_index
-                match checkpoint_syncer.latest_index().await {
-                    Ok(Some(index)) => {
-                        debug!(?address, ?index, "Validator returned latest index");
-                        latest_indices.insert(H160::from(*validator), Some(index));
-                    }
-                    result => {
-                        debug!(
-                            ?address,
-                            ?result,
-                            "Failed to get latest index from validator"
-                        );
-                        latest_indices.insert(H160::from(*validator), None);
-                    }
-                }
-            }
-        }
+            let checkpoint_syncer = self.checkpoint_syncers.get(&address)?;
+            Some(async move {
+                (
+                    address,
+                    self.fetch_latest_index(address, checkpoint_syncer.as_ref())
+                        .await,
+                )
+            })
+        });
+        let latest_indices: HashMap<H160, Option<u32>> =
+            join_all(fetches).await.into_iter().collect();
 
         if let Some(app_context) = &self.app_context {
             self.metrics
@@ -75,6 +135,32 @@ impl MultisigCheckpointSyncer {
         latest_indices.values().copied().flatten().collect()
     }
 
+    async fn fetch_latest_index(
+        &self,
+        address: H160,
+        checkpoint_syncer: &dyn CheckpointSyncer,
+    ) -> Option<u32> {
+        if let Some(index) = self.cache.latest_index(&address) {
+            return Some(index);
+        }
+        // Gracefully handle errors and timeouts getting the latest_index
+        match timeout(VALIDATOR_FETCH_TIMEOUT, checkpoint_syncer.latest_index()).await {
+            Ok(Ok(Some(index))) => {
+                debug!(?address, ?index, "Validator returned latest index");
+                self.cache.set_latest_index(address, index);
+                Some(index)
+            }
+            result => {
+                debug!(
+                    ?address,
+                    ?result,
+                    "Failed to get latest index from validator"
+                );
+                None
+            }
+        }
+    }
+
     /// Attempts to get the latest checkpoint with a quorum of signatures among
     /// validators.
     ///
@@ -84,7 +170,8 @@ impl MultisigCheckpointSyncer {
     ///
     /// Attempts to find a quorum of signed checkpoints from that index,
     /// iterating backwards if unsuccessful, until the (optional) index is
-    /// reached.
+    /// reached. A few indices are tried concurrently, but the highest index
+    /// with a quorum is always the one returned.
     ///
     /// Note it's possible to not find a quorum.
     #[instrument(err, skip(self))]
@@ -123,10 +210,13 @@ impl MultisigCheckpointSyncer {
                 debug!(%start_index, %highest_quorum_index, "Highest quorum index is below the minimum index");
                 return Ok(None);
             }
-            for index in (minimum_index..=start_index).rev() {
-                if let Ok(Some(checkpoint)) =
-                    self.fetch_checkpoint(validators, threshold, index).await
-                {
+            // `buffered` yields results in the order of the indices, so the
+            // first quorum found is the highest one
+            let mut checkpoints = stream::iter((minimum_index..=start_index).rev())
+                .map(|index| self.fetch_checkpoint(validators, threshold, index))
+                .buffered(CONCURRENT_INDEX_FETCHES);
+            while let Some(result) = checkpoints.next().await {
+                if let Ok(Some(checkpoint)) = result {
                     return Ok(Some(checkpoint));
                 }
             }
@@ -138,6 +228,9 @@ impl MultisigCheckpointSyncer {
     /// Fetches a MultisigSignedCheckpointWithMessageId if there is a quorum.
     /// Validators must reflect the onchain ordering of the set
     /// Returns Ok(None) if there is no quorum.
+    ///
+    /// Validators are queried concurrently, and this returns as soon as a
+    /// quorum of them signed the same root.
     #[instrument(err, skip(self))]
     pub async fn fetch_checkpoint(
         &self,
@@ -145,77 +238,279 @@ impl MultisigCheckpointSyncer {
         threshold: usize,
         index: u32,
     ) -> Result<Option<MultisigSignedCheckpoint>> {
-        // Keeps track of signed validator checkpoints for a particular root.
+        // Keeps track of signed validator checkpoints for a particular root, along
+        // with the position of their validator in the set.
         // In practice, it's likely that validators will all sign the same root for a
         // particular index, but we'd like to be robust to this not being the case
-        let mut signed_checkpoints_per_root: HashMap<H256, Vec<SignedCheckpointWithMessageId>> =
-            HashMap::new();
-
-        for validator in validators.iter() {
-            let addr = H160::from(*validator);
-            if let Some(checkpoint_syncer) = self.checkpoint_syncers.get(&addr) {
-                // Gracefully ignore an error fetching the checkpoint from a validator's
-                // checkpoint syncer, which can happen if the validator has not
-                // signed the checkpoint at `index`.
-                if let Ok(Some(signed_checkpoint)) = checkpoint_syncer.fetch_checkpoint(index).await
-                {
-                    // If the signed checkpoint is for a different index, ignore it
-                    if signed_checkpoint.value.index != index {
-                        debug!(
-                            validator = format!("{:#x}", validator),
-                            index = index,
-                            checkpoint_index = signed_checkpoint.value.index,
-                            "Checkpoint index mismatch"
-                        );
-                        continue;
-                    }
-
-                    // Ensure that the signature is actually by the validator
-                    let signer = signed_checkpoint.recover()?;
-
-                    if H256::from(signer) != *validator {
-                        debug!(
-                            validator = format!("{:#x}", validator),
-                            index = index,
-                            "Checkpoint signature mismatch"
-                        );
-                        continue;
-                    }
-
-                    // Push the signed checkpoint into the hashmap
-                    let root = signed_checkpoint.value.root;
-                    let signed_checkpoints = signed_checkpoints_per_root.entry(root).or_default();
-                    signed_checkpoints.push(signed_checkpoint);
-
-                    // Count the number of signatures for this signed checkpoint
-                    let signature_count = signed_checkpoints.len();
-                    debug!(
-                        validator = format!("{:#x}", validator),
-                        index = index,
-                        root = format!("{:#x}", root),
-                        signature_count = signature_count,
-                        "Found signed checkpoint"
-                    );
-
-                    // If we've hit a quorum, create a MultisigSignedCheckpoint
-                    if signature_count >= threshold {
-                        let checkpoint: MultisigSignedCheckpoint = signed_checkpoints.try_into()?;
-                        debug!(checkpoint=?checkpoint, "Fetched multisig checkpoint");
-                        return Ok(Some(checkpoint));
-                    }
-                } else {
-                    debug!(
-                        validator = format!("{:#x}", validator),
-                        index = index,
-                        "Unable to find signed checkpoint"
-                    );
-                }
-            } else {
-                debug!(%validator, "Unable to find checkpoint syncer");
+        let mut signed_checkpoints_per_root: HashMap<
+            H256,
+            Vec<(usize, SignedCheckpointWithMessageId)>,
+        > = HashMap::new();
+
+        let mut fetches: FuturesUnordered<_> = validators
+            .iter()
+            .enumerate()
+            .filter_map(|(position, validator)| {
+                let addr = H160::from(*validator);
+                let Some(checkpoint_syncer) = self.checkpoint_syncers.get(&addr) else {
+                    debug!(%validator, "Unable to find checkpoint syncer");
+                    return None;
+                };
+                Some(async move {
+                    let signed_checkpoint = self
+                        .fetch_validator_checkpoint(validator, checkpoint_syncer.as_ref(), index)
+                        .await;
+                    (position, validator, signed_checkpoint)
+                })
+            })
+            .collect();
+
+        while let Some((position, validator, signed_checkpoint)) = fetches.next().await {
+            let Some(signed_checkpoint) = signed_checkpoint? else {
                 continue;
+            };
+
+            // Push the signed checkpoint into the hashmap
+            let root = signed_checkpoint.value.root;
+            let signed_checkpoints = signed_checkpoints_per_root.entry(root).or_default();
+            signed_checkpoints.push((position, signed_checkpoint));
+
+            // Count the number of signatures for this signed checkpoint
+            let signature_count = signed_checkpoints.len();
+            debug!(
+                validator = format!("{:#x}", validator),
+                index = index,
+                root = format!("{:#x}", root),
+                signature_count = signature_count,
+                "Found signed checkpoint"
+            );
+
+            // If we've hit a quorum, create a MultisigSignedCheckpoint with the
+            // signatures in the order of the validator set
+            if signature_count >= threshold {
+                signed_checkpoints.sort_by_key(|(position, _)| *position);
+                let checkpoint: MultisigSignedCheckpoint = (&mut signed_checkpoints
+                    .drain(..)
+                    .map(|(_, signed_checkpoint)| signed_checkpoint)
+                    .collect::<Vec<_>>())
+                    .try_into()?;
+                debug!(checkpoint=?checkpoint, "Fetched multisig checkpoint");
+                return Ok(Some(checkpoint));
             }
         }
         debug!("No quorum checkpoint found for message");
         Ok(None)
     }
+
+    /// Fetches a validator's signed checkpoint at `index`, returning Ok(None)
+    /// if it can't be found or isn't signed by the validator.
+    async fn fetch_validator_checkpoint(
+        &self,
+        validator: &H256,
+        checkpoint_syncer: &dyn CheckpointSyncer,
+        index: u32,
+    ) -> Result<Option<SignedCheckpointWithMessageId>> {
+        let addr = H160::from(*validator);
+        if let Some(signed_checkpoint) = self.cache.checkpoint(&addr, index) {
+            return Ok(Some(signed_checkpoint));
+        }
+
+        // Gracefully ignore an error or timeout fetching the checkpoint from a
+        // validator's checkpoint syncer, which can happen if the validator has not
+        // signed the checkpoint at `index`.
+        let Ok(Ok(Some(signed_checkpoint))) = timeout(
+            VALIDATOR_FETCH_TIMEOUT,
+            checkpoint_syncer.fetch_checkpoint(index),
+        )
+        .await
+        else {
+            debug!(
+                validator = format!("{:#x}", validator),
+                index = index,
+                "Unable to find signed checkpoint"
+            );
+            return Ok(None);
+        };
+
+        // If the signed checkpoint is for a different index, ignore it
+        if signed_checkpoint.value.index != index {
+            debug!(
+                validator = format!("{:#x}", validator),
+                index = index,
+                checkpoint_index = signed_checkpoint.value.index,
+                "Checkpoint index mismatch"
+            );
+            return Ok(None);
+        }
+
+        // Ensure that the signature is actually by the validator
+        let signer = signed_checkpoint.recover()?;
+
+        if H256::from(signer) != *validator {
+            debug!(
+                validator = format!("{:#x}", validator),
+                index = index,
+                "Checkpoint signature mismatch"
+            );
+            return Ok(None);
+        }
+
+        self.cache.set_checkpoint(addr, signed_checkpoint.clone());
+        Ok(Some(signed_checkpoint))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+    use ethers::signers::LocalWallet;
+    use hyperlane_core::{
+        Checkpoint, CheckpointWithMessageId, HyperlaneSigner, HyperlaneSignerExt,
+        KnownHyperlaneDomain, SignedAnnouncement,
+    };
+    use hyperlane_ethereum::Signers;
+    use prometheus::Registry;
+
+    use super::*;
+
+    const INDEX: u32 = 7;
+
+    #[derive(Debug)]
+    struct MockCheckpointSyncer {
+        checkpoint: SignedCheckpointWithMessageId,
+        delay: Duration,
+        fetches: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl CheckpointSyncer for MockCheckpointSyncer {
+        async fn latest_index(&self) -> Result<Option<u32>> {
+            Ok(Some(INDEX))
+        }
+
+        async fn write_latest_index(&self, _index: u32) -> Result<()> {
+            unimplemented!()
+        }
+
+        async fn fetch_checkpoint(
+            &self,
+            index: u32,
+        ) -> Result<Option<SignedCheckpointWithMessageId>> {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            Ok((index == INDEX).then(|| self.checkpoint.clone()))
+        }
+
+        async fn write_checkpoint(
+            &self,
+            _signed_checkpoint: &SignedCheckpointWithMessageId,
+        ) -> Result<()> {
+            unimplemented!()
+        }
+
+        async fn write_announcement(
+            &self,
+            _signed_announcement: &SignedAnnouncement,
+        ) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn announcement_location(&self) -> String {
+            unimplemented!()
+        }
+    }
+
+    async fn mock_validator(key: &str, delay: Duration) -> (H256, Arc<MockCheckpointSyncer>) {
+        let signer = Signers::Local(key.parse::<LocalWallet>().unwrap());
+        let checkpoint = CheckpointWithMessageId {
+            checkpoint: Checkpoint {
+                merkle_tree_hook_address: H256::zero(),
+                mailbox_domain: 1,
+                root: H256::repeat_byte(1),
+                index: INDEX,
+            },
+            message_id: H256::repeat_byte(2),
+        };
+        let syncer = MockCheckpointSyncer {
+            checkpoint: signer.sign(checkpoint).await.unwrap(),
+            delay,
+            fetches: AtomicUsize::new(0),
+        };
+        (signer.eth_address().into(), Arc::new(syncer))
+    }
+
+    #[tokio::test]
+    async fn test_fetch_checkpoint_returns_on_quorum_in_validator_order() {
+        let validators = [
+            mock_validator(
+                "0x0000000000000000000000000000000000000000000000000000000000000001",
+                Duration::from_millis(200),
+            )
+            .await,
+            mock_validator(
+                "0x0000000000000000000000000000000000000000000000000000000000000002",
+                Duration::from_millis(100),
+            )
+            .await,
+            // Never responds in time, so a quorum must be reached without it
+            mock_validator(
+                "0x0000000000000000000000000000000000000000000000000000000000000003",
+                Duration::from_secs(60),
+            )
+            .await,
+        ];
+        let syncer = MultisigCheckpointSyncer::new(
+            validators
+                .iter()
+                .map(|(validator, syncer)| {
+                    (
+                        H160::from(*validator),
+                        syncer.clone() as Arc<dyn CheckpointSyncer>,
+                    )
+                })
+                .collect(),
+            Arc::new(CoreMetrics::new("test", 9090, Registry::new()).unwrap()),
+            None,
+            Default::default(),
+        );
+        let validator_set: Vec<H256> = validators.iter().map(|(validator, _)| *validator).collect();
+        let origin: HyperlaneDomain = KnownHyperlaneDomain::Ethereum.into();
+        let destination: HyperlaneDomain = KnownHyperlaneDomain::Arbitrum.into();
+
+        let checkpoint = tokio::time::timeout(
+            Duration::from_secs(1),
+            syncer.fetch_checkpoint_in_range(
+                &validator_set,
+                2,
+                INDEX,
+                INDEX,
+                &origin,
+                &destination,
+            ),
+        )
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+        assert_eq!(checkpoint.checkpoint.index, INDEX);
+        // Signatures are ordered like the validator set, not by response time
+        assert_eq!(
+            checkpoint.signatures,
+            vec![
+                validators[0].1.checkpoint.signature,
+                validators[1].1.checkpoint.signature
+            ]
+        );
+
+        // Checkpoints are cached for subsequent messages
+        syncer
+            .fetch_checkpoint(&validator_set[..2], 2, INDEX)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(validators[0].1.fetches.load(Ordering::SeqCst), 1);
+        assert_eq!(validators[1].1.fetches.load(Ordering::SeqCst), 1);
+    }
 }
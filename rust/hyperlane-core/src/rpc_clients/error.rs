@@ -8,4 +8,12 @@ pub enum RpcClientError {
     /// Fallback providers failed
     #[error("All fallback providers failed. (Errors: {0:?})")]
     FallbackProvidersFailed(Vec<ChainCommunicationError>),
+    /// Fewer providers than the quorum returned the same response
+    #[error("No quorum of {quorum} providers agreed on a response. (Errors: {errors:?})")]
+    QuorumNotReached {
+        /// Number of providers that had to agree
+        quorum: usize,
+        /// Errors returned by the providers
+        errors: Vec<ChainCommunicationError>,
+    },
 }
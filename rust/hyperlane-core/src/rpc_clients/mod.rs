@@ -3,6 +3,9 @@ pub use self::error::*;
 #[cfg(feature = "async")]
 pub use self::fallback::*;
 
+#[cfg(feature = "async")]
+pub use self::quorum::*;
+
 #[cfg(feature = "async")]
 pub use self::retry::*;
 
@@ -10,5 +13,8 @@ mod error;
 #[cfg(feature = "async")]
 mod fallback;
 
+#[cfg(feature = "async")]
+mod quorum;
+
 #[cfg(feature = "async")]
 mod retry;
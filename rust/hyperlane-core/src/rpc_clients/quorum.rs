@@ -0,0 +1,185 @@
+use futures::future::join_all;
+use itertools::Itertools;
+use serde::Serialize;
+use std::{
+    fmt::{Debug, Formatter},
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+};
+
+use crate::ChainCommunicationError;
+
+use super::RpcClientError;
+
+/// How requests are spread over the RPC endpoints of a chain
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RpcConsensusType {
+    /// Send each request to the highest priority endpoint that is healthy
+    #[default]
+    Fallback,
+    /// Send each request to every endpoint and require a quorum of them to
+    /// return the same response
+    Quorum,
+}
+
+/// A provider that sends each request to all of its inner providers and
+/// returns the response that a majority of them agree on.
+pub struct QuorumProvider<T> {
+    providers: Arc<Vec<T>>,
+}
+
+impl<T> Clone for QuorumProvider<T> {
+    fn clone(&self) -> Self {
+        Self {
+            providers: self.providers.clone(),
+        }
+    }
+}
+
+impl<T> Debug for QuorumProvider<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QuorumProvider")
+            .field(
+                "providers",
+                &self.providers.iter().map(|v| format!("{:?}", v)).join(", "),
+            )
+            .finish()
+    }
+}
+
+impl<T> QuorumProvider<T>
+where
+    T: Clone,
+{
+    /// Create a new quorum provider
+    pub fn new(providers: impl IntoIterator<Item = T>) -> Self {
+        Self {
+            providers: Arc::new(providers.into_iter().collect()),
+        }
+    }
+
+    /// The inner providers
+    pub fn providers(&self) -> &[T] {
+        &self.providers
+    }
+
+    /// The number of providers that must agree on a response
+    pub fn quorum(&self) -> usize {
+        self.providers.len() / 2 + 1
+    }
+
+    /// Call every provider concurrently and return their results in the
+    /// order of the providers.
+    pub async fn call_all<V>(
+        &self,
+        f: impl FnMut(T) -> Pin<Box<dyn Future<Output = Result<V, ChainCommunicationError>> + Send>>,
+    ) -> Vec<Result<V, ChainCommunicationError>> {
+        join_all(self.providers.iter().cloned().map(f)).await
+    }
+
+    /// Call every provider and return the response that a quorum of them
+    /// returned. Responses are compared by their serialized form.
+    pub async fn call<V>(
+        &self,
+        f: impl FnMut(T) -> Pin<Box<dyn Future<Output = Result<V, ChainCommunicationError>> + Send>>,
+    ) -> Result<V, ChainCommunicationError>
+    where
+        V: Serialize,
+    {
+        let results = self
+            .call_all(f)
+            .await
+            .into_iter()
+            .map(|result| {
+                let response = result?;
+                let serialized =
+                    serde_json::to_value(&response).map_err(ChainCommunicationError::from_other)?;
+                Ok((serialized, response))
+            })
+            .collect();
+        self.agreed_response(results, |(serialized, _)| serialized.clone())
+            .map(|(_, response)| response)
+    }
+
+    /// The response that a quorum of `results` agree on, comparing the
+    /// responses by `key`.
+    pub fn agreed_response<V, K: PartialEq>(
+        &self,
+        results: Vec<Result<V, ChainCommunicationError>>,
+        key: impl Fn(&V) -> K,
+    ) -> Result<V, ChainCommunicationError> {
+        let mut errors = vec![];
+        // Each distinct response with the number of providers that returned it
+        let mut responses: Vec<(K, V, usize)> = vec![];
+        for result in results {
+            match result {
+                Ok(response) => {
+                    let response_key = key(&response);
+                    match responses.iter_mut().find(|(k, _, _)| *k == response_key) {
+                        Some((_, _, count)) => *count += 1,
+                        None => responses.push((response_key, response, 1)),
+                    }
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+        let quorum = self.quorum();
+        responses
+            .into_iter()
+            .find(|(_, _, count)| *count >= quorum)
+            .map(|(_, response, _)| response)
+            .ok_or_else(|| RpcClientError::QuorumNotReached { quorum, errors }.into())
+    }
+
+    /// The highest of the block heights in `results` that a quorum of
+    /// providers have reached. Providers are rarely at the exact same height,
+    /// so heights can't be compared like other responses.
+    pub fn agreed_height(
+        &self,
+        results: Vec<Result<u64, ChainCommunicationError>>,
+    ) -> Result<u64, ChainCommunicationError> {
+        let (mut heights, errors): (Vec<_>, Vec<_>) = results.into_iter().partition_result();
+        heights.sort_unstable_by(|a, b| b.cmp(a));
+        let quorum = self.quorum();
+        heights
+            .get(quorum - 1)
+            .copied()
+            .ok_or_else(|| RpcClientError::QuorumNotReached { quorum, errors }.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results(values: &[Option<u64>]) -> Vec<Result<u64, ChainCommunicationError>> {
+        values
+            .iter()
+            .map(|value| value.ok_or_else(|| ChainCommunicationError::from_other_str("error")))
+            .collect()
+    }
+
+    #[test]
+    fn test_agreed_response_requires_a_majority() {
+        let provider = QuorumProvider::new([(); 3]);
+        assert_eq!(provider.quorum(), 2);
+        let response = provider.agreed_response(results(&[Some(1), None, Some(1)]), |v| *v);
+        assert_eq!(response.unwrap(), 1);
+        let response = provider.agreed_response(results(&[Some(1), Some(2), None]), |v| *v);
+        assert!(response.is_err());
+    }
+
+    #[test]
+    fn test_agreed_height_is_reached_by_a_quorum() {
+        let provider = QuorumProvider::new([(); 4]);
+        assert_eq!(provider.quorum(), 3);
+        let height = provider.agreed_height(results(&[Some(10), Some(12), Some(11), Some(9)]));
+        assert_eq!(height.unwrap(), 10);
+        let height = provider.agreed_height(results(&[Some(10), None, Some(11), None]));
+        assert!(height.is_err());
+    }
+}
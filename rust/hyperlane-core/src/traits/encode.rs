@@ -1,7 +1,8 @@
 use std::io::{Error, ErrorKind};
 
 use crate::{
-    GasPaymentKey, HyperlaneProtocolError, Indexed, InterchainGasPayment, H160, H256, H512, U256,
+    GasPaymentKey, HyperlaneProtocolError, Indexed, InterchainGasPayment, RecipientPauseKey, H160,
+    H256, H512, U256,
 };
 
 /// Simple trait for types with a canonical encoding
@@ -207,6 +208,31 @@ impl Decode for GasPaymentKey {
     }
 }
 
+impl Encode for RecipientPauseKey {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: std::io::Write,
+    {
+        let mut written = 0;
+        written += self.destination.write_to(writer)?;
+        written += self.recipient.write_to(writer)?;
+        Ok(written)
+    }
+}
+
+impl Decode for RecipientPauseKey {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: std::io::Read,
+        Self: Sized,
+    {
+        Ok(Self {
+            destination: u32::read_from(reader)?,
+            recipient: H256::read_from(reader)?,
+        })
+    }
+}
+
 impl Encode for InterchainGasPayment {
     fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
     where
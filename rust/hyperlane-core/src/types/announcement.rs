@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use derive_new::new;
 use serde::{Deserialize, Serialize};
 use sha3::{digest::Update, Digest, Keccak256};
 use std::fmt::{Debug, Formatter};
@@ -50,3 +51,13 @@ impl Signable for Announcement {
 
 /// An announcement that has been signed.
 pub type SignedAnnouncement = SignedType<Announcement>;
+
+/// A storage location announced by a validator, as emitted by the
+/// ValidatorAnnounce contract
+#[derive(Debug, Clone, new, Eq, PartialEq, Hash)]
+pub struct ValidatorAnnouncement {
+    /// The validator address
+    pub validator: H160,
+    /// The announced location of signed checkpoints
+    pub storage_location: String,
+}
@@ -122,6 +122,15 @@ pub struct GasPaymentKey {
     pub destination: u32,
 }
 
+/// Key for an operator pause of deliveries to a recipient on a destination
+#[derive(Debug, Copy, Clone)]
+pub struct RecipientPauseKey {
+    /// Destination domain of the paused deliveries
+    pub destination: u32,
+    /// Address of the recipient on the destination
+    pub recipient: H256,
+}
+
 impl From<InterchainGasPayment> for GasPaymentKey {
     fn from(value: InterchainGasPayment) -> Self {
         Self {
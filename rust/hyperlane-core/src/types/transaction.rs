@@ -1,6 +1,13 @@
-use std::sync::Arc;
+use std::{
+    io::{Error, ErrorKind, Read, Write},
+    str::FromStr,
+    sync::Arc,
+};
 
-use crate::{ChainResult, Mailbox, U256};
+use crate::{
+    ChainResult, Decode, Encode, FixedPointNumber, HyperlaneProtocolError, Mailbox, TxOutcome,
+    H512, U256,
+};
 use derive_new::new;
 
 /// State for the next submission attempt generated by a prepare call.
@@ -13,6 +20,184 @@ pub struct MessageSubmissionData {
     pub gas_limit: U256,
 }
 
+impl Encode for MessageSubmissionData {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: Write,
+    {
+        Ok(write_bytes(&self.metadata, writer)? + self.gas_limit.write_to(writer)?)
+    }
+}
+
+impl Decode for MessageSubmissionData {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: Read,
+        Self: Sized,
+    {
+        Ok(Self {
+            metadata: read_bytes(reader)?,
+            gas_limit: U256::read_from(reader)?,
+        })
+    }
+}
+
+/// The stage of the submission pipeline a pending message is in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PendingMessageStage {
+    /// Waiting to be prepared
+    #[default]
+    Prepare,
+    /// Prepared and waiting to be submitted
+    Submit,
+    /// Submitted and waiting for the submission to be confirmed
+    Confirm,
+}
+
+/// The state of a pending message that is persisted so the message can resume
+/// from the same stage after the relayer restarts.
+#[derive(Clone, Debug, Default)]
+pub struct PendingMessageState {
+    /// The stage the message was in
+    pub stage: PendingMessageStage,
+    /// Data generated by the prepare step, if the message is waiting to be
+    /// submitted
+    pub submission_data: Option<MessageSubmissionData>,
+    /// The outcome of the last submission of the message
+    pub submission_outcome: Option<TxOutcome>,
+    /// Whether the message was submitted, possibly by another relayer, and is
+    /// waiting to be confirmed
+    pub submitted: bool,
+    /// Unix timestamp in seconds before which the message shouldn't be
+    /// attempted again
+    pub next_attempt_after: Option<u64>,
+}
+
+fn write_option<T: Encode, W: Write>(value: &Option<T>, writer: &mut W) -> std::io::Result<usize> {
+    match value {
+        Some(value) => Ok(true.write_to(writer)? + value.write_to(writer)?),
+        None => false.write_to(writer),
+    }
+}
+
+fn read_option<T: Decode, R: Read>(reader: &mut R) -> Result<Option<T>, HyperlaneProtocolError> {
+    Ok(if bool::read_from(reader)? {
+        Some(T::read_from(reader)?)
+    } else {
+        None
+    })
+}
+
+/// A message that was given up on after exceeding its retry budget
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeadLetteredMessage {
+    /// How many times delivery was attempted
+    pub num_retries: u32,
+    /// Unix timestamp in seconds of when the message was dead-lettered
+    pub dead_lettered_at: u64,
+    /// Why the message was dead-lettered, including the last error if any
+    pub reason: String,
+}
+
+fn write_bytes<W: Write>(bytes: &[u8], writer: &mut W) -> std::io::Result<usize> {
+    let written = (bytes.len() as u32).write_to(writer)?;
+    writer.write_all(bytes)?;
+    Ok(written + bytes.len())
+}
+
+fn read_bytes<R: Read>(reader: &mut R) -> Result<Vec<u8>, HyperlaneProtocolError> {
+    let mut bytes = vec![0; u32::read_from(reader)? as usize];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+impl Encode for TxOutcome {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: Write,
+    {
+        let gas_price = self.gas_price.to_string();
+        let mut written = 0;
+        written += self.transaction_id.write_to(writer)?;
+        written += self.executed.write_to(writer)?;
+        written += self.gas_used.write_to(writer)?;
+        written += write_bytes(gas_price.as_bytes(), writer)?;
+        Ok(written)
+    }
+}
+
+impl Decode for TxOutcome {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: Read,
+        Self: Sized,
+    {
+        let transaction_id = H512::read_from(reader)?;
+        let executed = bool::read_from(reader)?;
+        let gas_used = U256::read_from(reader)?;
+        let gas_price = read_bytes(reader)?;
+        let gas_price = std::str::from_utf8(&gas_price)
+            .ok()
+            .and_then(|gas_price| FixedPointNumber::from_str(gas_price).ok())
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "decoded gas price invalid"))?;
+        Ok(Self {
+            transaction_id,
+            executed,
+            gas_used,
+            gas_price,
+        })
+    }
+}
+
+impl Encode for PendingMessageState {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: Write,
+    {
+        let stage: u8 = match self.stage {
+            PendingMessageStage::Prepare => 0,
+            PendingMessageStage::Submit => 1,
+            PendingMessageStage::Confirm => 2,
+        };
+        writer.write_all(&[stage])?;
+        let mut written = 1;
+        written += write_option(&self.submission_data, writer)?;
+        written += write_option(&self.submission_outcome, writer)?;
+        written += self.submitted.write_to(writer)?;
+        written += write_option(&self.next_attempt_after, writer)?;
+        Ok(written)
+    }
+}
+
+impl Decode for PendingMessageState {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: Read,
+        Self: Sized,
+    {
+        let mut stage = [0; 1];
+        reader.read_exact(&mut stage)?;
+        let stage = match stage[0] {
+            0 => PendingMessageStage::Prepare,
+            1 => PendingMessageStage::Submit,
+            2 => PendingMessageStage::Confirm,
+            _ => {
+                return Err(HyperlaneProtocolError::IoError(Error::new(
+                    ErrorKind::InvalidData,
+                    "decoded pending message stage invalid",
+                )))
+            }
+        };
+        Ok(Self {
+            stage,
+            submission_data: read_option(reader)?,
+            submission_outcome: read_option(reader)?,
+            submitted: bool::read_from(reader)?,
+            next_attempt_after: read_option(reader)?,
+        })
+    }
+}
+
 /// A an item to be batched for submission to the chain.
 #[derive(new, Clone, Debug)]
 pub struct BatchItem<T> {
@@ -33,3 +218,86 @@ pub trait TryBatchAs<T> {
         Err(crate::ChainCommunicationError::BatchingFailed)
     }
 }
+
+impl Encode for DeadLetteredMessage {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: Write,
+    {
+        let mut written = 0;
+        written += self.num_retries.write_to(writer)?;
+        written += self.dead_lettered_at.write_to(writer)?;
+        written += write_bytes(self.reason.as_bytes(), writer)?;
+        Ok(written)
+    }
+}
+
+impl Decode for DeadLetteredMessage {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: Read,
+        Self: Sized,
+    {
+        Ok(Self {
+            num_retries: u32::read_from(reader)?,
+            dead_lettered_at: u64::read_from(reader)?,
+            reason: String::from_utf8(read_bytes(reader)?)
+                .map_err(|_| Error::new(ErrorKind::InvalidData, "decoded reason invalid"))?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pending_message_state_encoding_round_trip() {
+        let state = PendingMessageState {
+            stage: PendingMessageStage::Confirm,
+            submission_data: Some(MessageSubmissionData {
+                metadata: vec![1, 2, 3],
+                gas_limit: U256::from(100_000),
+            }),
+            submission_outcome: Some(TxOutcome {
+                transaction_id: H512::random(),
+                executed: true,
+                gas_used: U256::from(90_000),
+                gas_price: FixedPointNumber::from_str("1.5").unwrap(),
+            }),
+            submitted: true,
+            next_attempt_after: Some(1_700_000_000),
+        };
+
+        let decoded = PendingMessageState::read_from(&mut state.to_vec().as_slice()).unwrap();
+        assert_eq!(decoded.stage, state.stage);
+        let data = decoded.submission_data.unwrap();
+        assert_eq!(data.metadata, vec![1, 2, 3]);
+        assert_eq!(data.gas_limit, U256::from(100_000));
+        let outcome = decoded.submission_outcome.unwrap();
+        let expected = state.submission_outcome.unwrap();
+        assert_eq!(outcome.transaction_id, expected.transaction_id);
+        assert_eq!(outcome.gas_price, expected.gas_price);
+        assert!(decoded.submitted);
+        assert_eq!(decoded.next_attempt_after, state.next_attempt_after);
+
+        let empty =
+            PendingMessageState::read_from(&mut PendingMessageState::default().to_vec().as_slice())
+                .unwrap();
+        assert_eq!(empty.stage, PendingMessageStage::Prepare);
+        assert!(empty.submission_data.is_none());
+    }
+
+    #[test]
+    fn test_dead_lettered_message_encoding_round_trip() {
+        let dead_lettered = DeadLetteredMessage {
+            num_retries: 42,
+            dead_lettered_at: 1_700_000_000,
+            reason: "Exceeded max retries: recipient reverted".to_owned(),
+        };
+        assert_eq!(
+            DeadLetteredMessage::read_from(&mut dead_lettered.to_vec().as_slice()).unwrap(),
+            dead_lettered
+        );
+    }
+}
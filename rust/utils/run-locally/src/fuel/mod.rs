@@ -0,0 +1,479 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+use std::{env, fs};
+
+use ethers::signers::{LocalWallet, Signer};
+use ethers::types::H256;
+use fuels::prelude::{
+    abigen, Bech32ContractId, CallParameters, Contract, ContractId, DeployConfiguration, Provider,
+    WalletUnlocked,
+};
+use fuels::signers::fuel_crypto::SecretKey;
+use fuels::types::{Bits256, Identity};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use serde_json::json;
+use tempfile::tempdir;
+
+use crate::logging::log;
+use crate::program::Program;
+use crate::utils::{as_task, concat_path, stop_child, AgentHandles, TaskHandle};
+use crate::{fetch_metric, AGENT_BIN_PATH};
+
+abigen!(
+    Contract(
+        name = "Mailbox",
+        abi = "../../chains/hyperlane-fuel/abis/Mailbox.abi.json"
+    ),
+    Contract(
+        name = "InterchainGasPaymaster",
+        abi = "../../chains/hyperlane-fuel/abis/InterchainGasPaymaster.abi.json"
+    ),
+    Contract(
+        name = "MultisigIsm",
+        abi = "../../chains/hyperlane-fuel/abis/MultisigIsm.abi.json"
+    ),
+);
+
+const ENV_FUEL_CORE_PATH_KEY: &str = "E2E_FUEL_CORE_PATH";
+/// Directory holding the compiled Sway contracts, which don't live in this
+/// repository. The mailbox hardcodes its local domain, so there is one
+/// `mailbox-<domain>.bin` per chain next to `interchain_gas_paymaster.bin`,
+/// `multisig_ism.bin`, `validator_announce.bin` and `test_recipient.bin`.
+const ENV_FUEL_CONTRACTS_PATH_KEY: &str = "E2E_FUEL_CONTRACTS_PATH";
+
+/// These private keys are funded in the genesis of fuel-core's
+/// `local_testnet` chain config.
+const DEPLOYER_KEY: &str = "0xde97d8624a438121b86a1956544bd72ed68cd69f2c99555b08b1e8c51ffd511c";
+const RELAYER_KEY: &str = "0x37fa81c84ccd547c30c176b118d5cb892bdb113e8e80141f266519422ef9eefd";
+const VALIDATOR_CHAIN_KEY: &str =
+    "0x862512a2363db2b3a375c0d4bbbd27172180d89f23f2e259bac850ab02619301";
+
+/// These private keys are from hardhat/anvil's testing accounts and sign
+/// the checkpoints of each chain.
+const VALIDATOR_KEYS: &[&str] = &[
+    "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+    "0x5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fb9a804cdab365a",
+];
+
+/// fueltest1 is a known domain, the second chain is configured as a custom
+/// one.
+const DOMAINS: &[(&str, u32)] = &[("fueltest1", 13374), ("fueltest2", 13375)];
+
+const GAS_AMOUNT: u64 = 100_000;
+
+#[derive(Clone)]
+struct FuelDeployment {
+    mailbox: Bech32ContractId,
+    igp: Bech32ContractId,
+    va: Bech32ContractId,
+    recipient: Bech32ContractId,
+}
+
+struct FuelNetwork {
+    name: &'static str,
+    domain: u32,
+    port: u32,
+    node: AgentHandles,
+    deployment: FuelDeployment,
+}
+
+impl Drop for FuelNetwork {
+    fn drop(&mut self) {
+        stop_child(&mut self.node.1);
+    }
+}
+
+struct FuelHyperlaneStack {
+    validators: Vec<AgentHandles>,
+    relayer: AgentHandles,
+}
+
+impl Drop for FuelHyperlaneStack {
+    fn drop(&mut self) {
+        for v in &mut self.validators {
+            stop_child(&mut v.1);
+        }
+        stop_child(&mut self.relayer.1);
+    }
+}
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+fn wallet(key: &str, provider: Provider) -> WalletUnlocked {
+    let key = SecretKey::from_str(key.trim_start_matches("0x")).unwrap();
+    WalletUnlocked::new_from_private_key(key, Some(provider))
+}
+
+fn to_bits256(id: &Bech32ContractId) -> Bits256 {
+    Bits256(*ContractId::from(id))
+}
+
+#[apply(as_task)]
+fn launch_fuel_node(fuel_core: PathBuf, port: u32) -> AgentHandles {
+    let node = Program::new(fuel_core)
+        .cmd("run")
+        .arg("ip", "127.0.0.1")
+        .arg("port", port.to_string())
+        .arg("db-type", "in-memory")
+        .arg("chain", "local_testnet")
+        .filter_logs(|_| false)
+        .spawn("FUEL");
+
+    // give the node a chance to start serving requests
+    sleep(Duration::from_secs(5));
+    node
+}
+
+async fn deploy(
+    contracts: &Path,
+    port: u32,
+    domain: u32,
+    validator: &str,
+) -> eyre::Result<FuelDeployment> {
+    let provider = Provider::connect(format!("127.0.0.1:{port}")).await?;
+    let deployer = wallet(DEPLOYER_KEY, provider);
+    let deploy_contract = |name: String| {
+        let path = concat_path(contracts, name);
+        let deployer = deployer.clone();
+        async move {
+            Contract::deploy(
+                path.to_str().unwrap(),
+                &deployer,
+                DeployConfiguration::default(),
+            )
+            .await
+        }
+    };
+
+    let mailbox = deploy_contract(format!("mailbox-{domain}.bin")).await?;
+    let igp = deploy_contract("interchain_gas_paymaster.bin".into()).await?;
+    let ism = deploy_contract("multisig_ism.bin".into()).await?;
+    let va = deploy_contract("validator_announce.bin".into()).await?;
+    let recipient = deploy_contract("test_recipient.bin".into()).await?;
+
+    let validator = LocalWallet::from_str(validator)?.address();
+    MultisigIsm::new(ism.clone(), deployer.clone())
+        .methods()
+        .set_validators_and_threshold(vec![Bits256(H256::from(validator).0)], 1)
+        .call()
+        .await?;
+    Mailbox::new(mailbox.clone(), deployer)
+        .methods()
+        .set_default_ism(ContractId::from(&ism))
+        .call()
+        .await?;
+
+    Ok(FuelDeployment {
+        mailbox,
+        igp,
+        va,
+        recipient,
+    })
+}
+
+/// Dispatch a message from `origin` to `destination` and pay for its gas
+async fn dispatch(origin: &FuelNetwork, destination: &FuelNetwork) -> eyre::Result<()> {
+    let provider = Provider::connect(format!("127.0.0.1:{}", origin.port)).await?;
+    let sender = wallet(DEPLOYER_KEY, provider);
+
+    let message_id = Mailbox::new(origin.deployment.mailbox.clone(), sender.clone())
+        .methods()
+        .dispatch(
+            destination.domain,
+            to_bits256(&destination.deployment.recipient),
+            b"hello".to_vec(),
+        )
+        .call()
+        .await?
+        .value;
+
+    let igp = InterchainGasPaymaster::new(origin.deployment.igp.clone(), sender.clone());
+    let payment = igp
+        .methods()
+        .quote_gas_payment(destination.domain, GAS_AMOUNT)
+        .simulate()
+        .await?
+        .value;
+    igp.methods()
+        .pay_for_gas(
+            message_id,
+            destination.domain,
+            GAS_AMOUNT,
+            Identity::Address(sender.address().into()),
+        )
+        .call_params(CallParameters::new(Some(payment), None, None))
+        .call()
+        .await?;
+    Ok(())
+}
+
+fn agent_config(networks: &[FuelNetwork]) -> serde_json::Value {
+    let chains = networks
+        .iter()
+        .map(|network| {
+            let address =
+                |id: &Bech32ContractId| format!("0x{}", hex::encode(*ContractId::from(id)));
+            let mailbox = address(&network.deployment.mailbox);
+            (
+                network.name.to_string(),
+                json!({
+                    "name": network.name,
+                    "domainId": network.domain,
+                    "protocol": "fuel",
+                    "rpcUrls": [{ "http": format!("http://127.0.0.1:{}", network.port) }],
+                    // The mailbox keeps the merkle tree itself
+                    "mailbox": mailbox,
+                    "merkleTreeHook": mailbox,
+                    "interchainGasPaymaster": address(&network.deployment.igp),
+                    "validatorAnnounce": address(&network.deployment.va),
+                    "signer": { "type": "hexKey", "key": RELAYER_KEY },
+                    "index": { "from": 0, "chunk": 100 },
+                }),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+    json!({ "chains": chains })
+}
+
+#[apply(as_task)]
+fn launch_fuel_validator(
+    agent_config_path: PathBuf,
+    origin: &'static str,
+    validator_key: &'static str,
+    metrics_port: u32,
+    debug: bool,
+) -> AgentHandles {
+    let validator_bin = concat_path(format!("../../{AGENT_BIN_PATH}"), "validator");
+    let validator_base = tempdir().expect("Failed to create a temp dir").into_path();
+    let validator_base_db = concat_path(&validator_base, "db");
+
+    fs::create_dir_all(&validator_base_db).unwrap();
+    println!("Validator DB: {:?}", validator_base_db);
+
+    let checkpoint_path = concat_path(&validator_base, "checkpoint");
+
+    Program::default()
+        .bin(validator_bin)
+        .working_dir("../../")
+        .env("CONFIG_FILES", agent_config_path.to_str().unwrap())
+        .env("RUST_BACKTRACE", "1")
+        .hyp_env("CHECKPOINTSYNCER_PATH", checkpoint_path.to_str().unwrap())
+        .hyp_env("CHECKPOINTSYNCER_TYPE", "localStorage")
+        .hyp_env("ORIGINCHAINNAME", origin)
+        .hyp_env("DB", validator_base_db.to_str().unwrap())
+        .hyp_env("METRICSPORT", metrics_port.to_string())
+        .hyp_env("VALIDATOR_KEY", validator_key)
+        // Announcing is paid for by a different key than the relayer's
+        .hyp_env(
+            format!("CHAINS_{}_SIGNER_KEY", origin.to_uppercase()),
+            VALIDATOR_CHAIN_KEY,
+        )
+        .hyp_env("TRACING_LEVEL", if debug { "debug" } else { "info" })
+        .spawn("VAL")
+}
+
+#[apply(as_task)]
+fn launch_fuel_relayer(
+    agent_config_path: PathBuf,
+    relay_chains: Vec<String>,
+    metrics_port: u32,
+    debug: bool,
+) -> AgentHandles {
+    let relayer_bin = concat_path(format!("../../{AGENT_BIN_PATH}"), "relayer");
+    let relayer_base = tempdir().unwrap();
+
+    Program::default()
+        .bin(relayer_bin)
+        .working_dir("../../")
+        .env("CONFIG_FILES", agent_config_path.to_str().unwrap())
+        .env("RUST_BACKTRACE", "1")
+        .hyp_env("RELAYCHAINS", relay_chains.join(","))
+        .hyp_env("DB", relayer_base.as_ref().to_str().unwrap())
+        .hyp_env("ALLOWLOCALCHECKPOINTSYNCERS", "true")
+        .hyp_env("TRACING_LEVEL", if debug { "debug" } else { "info" })
+        .hyp_env("GASPAYMENTENFORCEMENT", "[{\"type\": \"none\"}]")
+        .hyp_env("METRICSPORT", metrics_port.to_string())
+        .spawn("RLY")
+}
+
+#[allow(dead_code)]
+fn run_locally() {
+    const TIMEOUT_SECS: u64 = 60 * 10;
+    let debug = false;
+
+    log!("Building rust...");
+    Program::new("cargo")
+        .cmd("build")
+        .working_dir("../../")
+        .arg("features", "test-utils")
+        .arg("bin", "relayer")
+        .arg("bin", "validator")
+        .filter_logs(|l| !l.contains("workspace-inheritance"))
+        .run()
+        .join();
+
+    let fuel_core = env::var(ENV_FUEL_CORE_PATH_KEY)
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| which::which("fuel-core").expect("fuel-core is not installed"));
+    let contracts = PathBuf::from(
+        env::var(ENV_FUEL_CONTRACTS_PATH_KEY)
+            .unwrap_or_else(|_| panic!("{ENV_FUEL_CONTRACTS_PATH_KEY} is not set")),
+    );
+
+    let port_start = 4000u32;
+    let metrics_port_start = 9090u32;
+
+    let nodes = (0..DOMAINS.len())
+        .map(|i| launch_fuel_node(fuel_core.clone(), port_start + i as u32))
+        .collect::<Vec<_>>();
+
+    let networks = nodes
+        .into_iter()
+        .zip(DOMAINS)
+        .enumerate()
+        .map(|(i, (node, &(name, domain)))| {
+            let node = node.join();
+            let port = port_start + i as u32;
+            log!("Deploying hyperlane contracts to {}...", name);
+            let deployment = block_on(deploy(&contracts, port, domain, VALIDATOR_KEYS[i]))
+                .expect("Failed to deploy the fuel contracts");
+            FuelNetwork {
+                name,
+                domain,
+                port,
+                node,
+                deployment,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let config_dir = tempdir().unwrap();
+    let agent_config_path = concat_path(&config_dir, "config.json");
+    fs::write(
+        &agent_config_path,
+        serde_json::to_string_pretty(&agent_config(&networks)).unwrap(),
+    )
+    .unwrap();
+
+    let hpl_val = networks
+        .iter()
+        .enumerate()
+        .map(|(i, network)| {
+            launch_fuel_validator(
+                agent_config_path.clone(),
+                network.name,
+                VALIDATOR_KEYS[i],
+                metrics_port_start + i as u32,
+                debug,
+            )
+        })
+        .collect::<Vec<_>>();
+    let hpl_rly_metrics_port = metrics_port_start + networks.len() as u32 + 1;
+    let hpl_rly = launch_fuel_relayer(
+        agent_config_path,
+        networks.iter().map(|v| v.name.to_string()).collect(),
+        hpl_rly_metrics_port,
+        debug,
+    );
+
+    // give things a chance to fully start.
+    sleep(Duration::from_secs(10));
+
+    // dispatch a message between every pair of chains
+    let mut dispatched_messages = 0;
+    for origin in networks.iter() {
+        for destination in networks.iter().filter(|v| v.domain != origin.domain) {
+            log!("Dispatching {} -> {}", origin.name, destination.name);
+            block_on(dispatch(origin, destination)).expect("Failed to dispatch a message");
+            dispatched_messages += 1;
+        }
+    }
+
+    let _stack = FuelHyperlaneStack {
+        validators: hpl_val.into_iter().map(|v| v.join()).collect(),
+        relayer: hpl_rly.join(),
+    };
+
+    let loop_start = Instant::now();
+    let mut failure_occurred = false;
+    loop {
+        // look for the end condition.
+        if termination_invariants_met(hpl_rly_metrics_port, dispatched_messages).unwrap_or(false) {
+            // end condition reached successfully
+            break;
+        } else if (Instant::now() - loop_start).as_secs() > TIMEOUT_SECS {
+            // we ran out of time
+            log!("timeout reached before message submission was confirmed");
+            failure_occurred = true;
+            break;
+        }
+
+        sleep(Duration::from_secs(5));
+    }
+
+    if failure_occurred {
+        panic!("E2E tests failed");
+    } else {
+        log!("E2E tests passed");
+    }
+}
+
+fn termination_invariants_met(
+    relayer_metrics_port: u32,
+    messages_expected: u32,
+) -> eyre::Result<bool> {
+    let gas_payments_scraped = fetch_metric(
+        &relayer_metrics_port.to_string(),
+        "hyperlane_contract_sync_stored_events",
+        &hashmap! {"data_type" => "gas_payment"},
+    )?
+    .iter()
+    .sum::<u32>();
+    if gas_payments_scraped != messages_expected {
+        log!(
+            "Relayer has indexed {} gas payments, expected {}",
+            gas_payments_scraped,
+            messages_expected
+        );
+        return Ok(false);
+    }
+
+    let delivered_messages_scraped = fetch_metric(
+        &relayer_metrics_port.to_string(),
+        "hyperlane_operations_processed_count",
+        &hashmap! {"phase" => "confirmed"},
+    )?
+    .iter()
+    .sum::<u32>();
+    if delivered_messages_scraped != messages_expected {
+        log!(
+            "Relayer confirmed {} submitted messages, expected {}",
+            delivered_messages_scraped,
+            messages_expected
+        );
+        return Ok(false);
+    }
+
+    log!("Termination invariants have been meet");
+    Ok(true)
+}
+
+#[cfg(feature = "fuel")]
+mod test {
+
+    #[test]
+    fn test_run() {
+        use crate::fuel::run_locally;
+
+        run_locally()
+    }
+}
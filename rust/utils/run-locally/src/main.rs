@@ -39,6 +39,7 @@ use crate::{
 mod config;
 mod cosmos;
 mod ethereum;
+mod fuel;
 mod invariants;
 mod logging;
 mod metrics;
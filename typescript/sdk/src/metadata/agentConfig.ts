@@ -120,6 +120,34 @@ export type AgentCosmosGasPrice = z.infer<
   typeof AgentCosmosChainMetadataSchema
 >['gasPrice'];
 
+// Additional chain metadata for Sealevel chains used by the agents.
+const AgentSealevelChainMetadataSchema = z.object({
+  priorityFee: z
+    .object({
+      strategy: z
+        .enum(['fixed', 'percentile'])
+        .optional()
+        .describe(
+          'How to choose the priority fee: a fixed price, or a percentile of recent fees for the accounts used. Defaults to fixed.',
+        ),
+      microLamports: ZUint.optional().describe(
+        'The fixed price to pay, in micro-lamports per compute unit. Defaults to 0.',
+      ),
+      percentile: ZUint.lte(100)
+        .optional()
+        .describe(
+          'The percentile of recent priority fees to pay. Defaults to 50.',
+        ),
+      maxLamports: ZUint.optional().describe(
+        'The most a single transaction may pay in priority fees, in lamports.',
+      ),
+      bumpPercent: ZUint.optional().describe(
+        'How much to raise the priority fee by, in percent, when a transaction expires before landing. Defaults to 50.',
+      ),
+    })
+    .describe('Priority fee configuration for submitted transactions.'),
+});
+
 export const AgentChainMetadataSchema = ChainMetadataSchemaObject.merge(
   HyperlaneDeploymentArtifactsSchema,
 )
@@ -155,6 +183,7 @@ export const AgentChainMetadataSchema = ChainMetadataSchemaObject.merge(
       .optional(),
   })
   .merge(AgentCosmosChainMetadataSchema.partial())
+  .merge(AgentSealevelChainMetadataSchema.partial())
   .refine((metadata) => {
     // Make sure that the signer is valid for the protocol
 
@@ -255,6 +284,7 @@ export enum GasPaymentEnforcementPolicyType {
   None = 'none',
   Minimum = 'minimum',
   OnChainFeeQuoting = 'onChainFeeQuoting',
+  ExchangeRate = 'exchangeRate',
 }
 
 const GasPaymentEnforcementBaseSchema = z.object({
@@ -262,6 +292,13 @@ const GasPaymentEnforcementBaseSchema = z.object({
     'An optional matching list, any message that matches will use this policy. By default all messages will match.',
   ),
 });
+const TokenExchangeRateSchema = z.object({
+  price: z.union([
+    z.number().nonnegative(),
+    z.string().regex(/^\d+(\.\d+)?$/),
+  ]),
+  decimals: ZUint.optional().describe('Defaults to 18.'),
+});
 const GasPaymentEnforcementSchema = z.union([
   GasPaymentEnforcementBaseSchema.extend({
     type: z.literal(GasPaymentEnforcementPolicyType.None).optional(),
@@ -277,6 +314,31 @@ const GasPaymentEnforcementSchema = z.union([
       .regex(/^\d+ ?\/ ?[1-9]\d*$/)
       .optional(),
   }),
+  GasPaymentEnforcementBaseSchema.extend({
+    type: z.literal(GasPaymentEnforcementPolicyType.ExchangeRate),
+    margin: z
+      .union([z.number().positive(), z.string().regex(/^\d+(\.\d+)?$/)])
+      .optional()
+      .describe(
+        'The transaction cost is multiplied by this before being compared to the payment. Defaults to 1.',
+      ),
+    exchangeRates: z
+      .record(TokenExchangeRateSchema)
+      .optional()
+      .describe(
+        "Exchange rates of each chain's native token to a common currency such as USD, keyed by chain name.",
+      ),
+    exchangeRatesSource: z
+      .string()
+      .min(1)
+      .optional()
+      .describe(
+        'A local JSON file path or an HTTP(S) URL serving exchange rates in the same shape as `exchangeRates`, which take precedence.',
+      ),
+    refreshInterval: ZUint.optional().describe(
+      'How often in seconds to re-read `exchangeRatesSource`. Defaults to 60.',
+    ),
+  }),
 ]);
 export type GasPaymentEnforcement = z.infer<typeof GasPaymentEnforcementSchema>;
 
@@ -287,6 +349,26 @@ const MetricAppContextSchema = z.object({
   ),
 });
 
+const AppSchedulingSchema = z.object({
+  name: z.string().min(1),
+  matchingList: MatchingListSchema.describe(
+    'A matching list, any message that matches will be scheduled as part of this app.',
+  ),
+  weight: ZNzUint.optional().describe(
+    'The share of submissions this app gets relative to other apps with ready messages. Defaults to 1.',
+  ),
+  maxPerSecond: z
+    .number()
+    .positive()
+    .optional()
+    .describe(
+      'The maximum number of messages of this app submitted per second to each destination. Unlimited if not set.',
+    ),
+  burst: ZNzUint.optional().describe(
+    'The number of messages that can be submitted at once before the rate limit applies. Defaults to one second worth of messages.',
+  ),
+});
+
 export const RelayerAgentConfigSchema = AgentConfigSchema.extend({
   db: z
     .string()
@@ -332,15 +414,67 @@ export const RelayerAgentConfigSchema = AgentConfigSchema.extend({
     .describe(
       'A list of app contexts and their matching lists to use for metrics. A message will be classified as the first matching app context.',
     ),
+  appScheduling: z
+    .union([z.array(AppSchedulingSchema), z.string().min(1)])
+    .optional()
+    .describe(
+      'A list of apps used to share submissions fairly between apps and to rate limit them. A message belongs to the first matching app.',
+    ),
+  maxMessageRetries: ZUint.optional().describe(
+    'If set, messages are moved to the dead-letter store after this many failed delivery attempts.',
+  ),
+  maxMessageAge: ZUint.optional().describe(
+    'If set, messages are moved to the dead-letter store once this many seconds have passed since they were first seen without being delivered.',
+  ),
+  dryRun: z
+    .boolean()
+    .optional()
+    .describe(
+      'If true, messages are prepared but never submitted. What would have been submitted is logged and reported in metrics instead.',
+    ),
 });
 
 export type RelayerConfig = z.infer<typeof RelayerAgentConfigSchema>;
 
 export const ScraperAgentConfigSchema = AgentConfigSchema.extend({
-  db: z.string().min(1).describe('Database connection string'),
+  db: z
+    .string()
+    .min(1)
+    .describe(
+      'Database connection string, either a postgresql:// or sqlite:// URL',
+    ),
   chainsToScrape: CommaSeperatedChainList.describe(
     'Comma separated list of chain names to scrape',
   ),
+  enableApi: z
+    .boolean()
+    .optional()
+    .describe(
+      'If true, the scraper serves an HTTP API for querying scraped messages.',
+    ),
+  backfill: z
+    .object({
+      chains: z
+        .record(
+          z.object({
+            from: ZUint.describe('First block height or sequence to scrape.'),
+            to: ZUint.describe('Last block height or sequence to scrape.'),
+          }),
+        )
+        .describe(
+          'Range to scrape for each chain. Chains must also be in chainsToScrape.',
+        ),
+      concurrency: ZNzUint.optional().describe(
+        'Number of chunks fetched at once on each chain.',
+      ),
+      batchSize: ZNzUint.optional().describe(
+        'Number of chunks whose logs are written to the database together.',
+      ),
+    })
+    .optional()
+    .describe(
+      'If set, the scraper scrapes a fixed range of history and exits instead of following the chain head.',
+    ),
 });
 
 export type ScraperConfig = z.infer<typeof ScraperAgentConfigSchema>;
@@ -4,7 +4,7 @@
  */
 import { z } from 'zod';
 
-import { ZHash, ZNzUint } from './customZodTypes.js';
+import { ZHash, ZNzUint, ZUWei, ZUint } from './customZodTypes.js';
 
 const DomainSchema = z.union([
   z.literal('*'),
@@ -14,14 +14,59 @@ const DomainSchema = z.union([
 
 const AddressSchema = z.union([z.literal('*'), ZHash, z.array(ZHash)]);
 
-const MatchingListElementSchema = z.object({
-  originDomain: DomainSchema.optional(),
-  senderAddress: AddressSchema.optional(),
-  destinationDomain: DomainSchema.optional(),
-  recipientAddress: AddressSchema.optional(),
+const HexBytesSchema = z.string().regex(/^(0x)?([0-9a-fA-F]{2})*$/);
+
+const BytesSchema = z.union([
+  z.literal('*'),
+  HexBytesSchema,
+  z.array(HexBytesSchema),
+]);
+
+const SelectorSchema = z.string().regex(/^(0x)?[0-9a-fA-F]{8}$/);
+
+const UintRangeSchema = z.object({
+  min: z.union([ZUint, z.string().regex(/^\d+$/)]).optional(),
+  max: z.union([ZUint, z.string().regex(/^\d+$/)]).optional(),
 });
 
+const BodyUintSchema = z.object({
+  offset: ZUint,
+  min: ZUWei.optional(),
+  max: ZUWei.optional(),
+});
+
+export type MatchingListElement = {
+  originDomain?: z.infer<typeof DomainSchema>;
+  senderAddress?: z.infer<typeof AddressSchema>;
+  destinationDomain?: z.infer<typeof DomainSchema>;
+  recipientAddress?: z.infer<typeof AddressSchema>;
+  messageId?: z.infer<typeof AddressSchema>;
+  nonce?: z.infer<typeof UintRangeSchema>;
+  bodyPrefix?: z.infer<typeof BytesSchema>;
+  functionSelector?: '*' | string | string[];
+  bodyLength?: z.infer<typeof UintRangeSchema>;
+  bodyUint?: z.infer<typeof BodyUintSchema>;
+  not?: MatchingListElement[];
+};
+
+const MatchingListElementSchema: z.ZodType<MatchingListElement> = z.lazy(() =>
+  z.object({
+    originDomain: DomainSchema.optional(),
+    senderAddress: AddressSchema.optional(),
+    destinationDomain: DomainSchema.optional(),
+    recipientAddress: AddressSchema.optional(),
+    messageId: AddressSchema.optional(),
+    nonce: UintRangeSchema.optional(),
+    bodyPrefix: BytesSchema.optional(),
+    functionSelector: z
+      .union([z.literal('*'), SelectorSchema, z.array(SelectorSchema)])
+      .optional(),
+    bodyLength: UintRangeSchema.optional(),
+    bodyUint: BodyUintSchema.optional(),
+    not: z.array(MatchingListElementSchema).optional(),
+  }),
+);
+
 export const MatchingListSchema = z.array(MatchingListElementSchema);
 
-export type MatchingListElement = z.infer<typeof MatchingListElementSchema>;
 export type MatchingList = z.infer<typeof MatchingListSchema>;